@@ -0,0 +1,5 @@
+ALTER TABLE cashu_mint
+    DROP COLUMN nickname;
+
+ALTER TABLE fedimint
+    DROP COLUMN nickname;
@@ -0,0 +1,5 @@
+ALTER TABLE fedimint
+    ADD COLUMN nickname TEXT;
+
+ALTER TABLE cashu_mint
+    ADD COLUMN nickname TEXT;
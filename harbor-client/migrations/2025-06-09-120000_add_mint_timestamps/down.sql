@@ -0,0 +1,11 @@
+ALTER TABLE cashu_mint
+    DROP COLUMN last_used_at;
+
+ALTER TABLE cashu_mint
+    DROP COLUMN joined_at;
+
+ALTER TABLE fedimint
+    DROP COLUMN last_used_at;
+
+ALTER TABLE fedimint
+    DROP COLUMN joined_at;
@@ -0,0 +1,35 @@
+ALTER TABLE fedimint
+    ADD COLUMN joined_at TIMESTAMP;
+
+ALTER TABLE fedimint
+    ADD COLUMN last_used_at TIMESTAMP;
+
+ALTER TABLE cashu_mint
+    ADD COLUMN joined_at TIMESTAMP;
+
+ALTER TABLE cashu_mint
+    ADD COLUMN last_used_at TIMESTAMP;
+
+-- Mints joined before this have no join time on record, so their first transaction stands in for
+-- it and their latest for the last use. Mints without any transactions keep both unset.
+WITH mint_transactions AS (SELECT fedimint_id, created_at FROM lightning_payments
+                           UNION ALL
+                           SELECT fedimint_id, created_at FROM lightning_receives
+                           UNION ALL
+                           SELECT fedimint_id, created_at FROM on_chain_payments
+                           UNION ALL
+                           SELECT fedimint_id, created_at FROM on_chain_receives)
+UPDATE fedimint
+SET joined_at    = (SELECT MIN(created_at) FROM mint_transactions WHERE fedimint_id = fedimint.id),
+    last_used_at = (SELECT MAX(created_at) FROM mint_transactions WHERE fedimint_id = fedimint.id);
+
+WITH mint_transactions AS (SELECT cashu_mint_url, created_at FROM lightning_payments
+                           UNION ALL
+                           SELECT cashu_mint_url, created_at FROM lightning_receives
+                           UNION ALL
+                           SELECT cashu_mint_url, created_at FROM on_chain_payments
+                           UNION ALL
+                           SELECT cashu_mint_url, created_at FROM on_chain_receives)
+UPDATE cashu_mint
+SET joined_at    = (SELECT MIN(created_at) FROM mint_transactions WHERE cashu_mint_url = cashu_mint.mint_url),
+    last_used_at = (SELECT MAX(created_at) FROM mint_transactions WHERE cashu_mint_url = cashu_mint.mint_url);
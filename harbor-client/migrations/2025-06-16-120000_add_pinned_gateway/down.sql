@@ -0,0 +1,2 @@
+ALTER TABLE fedimint
+    DROP COLUMN pinned_gateway;
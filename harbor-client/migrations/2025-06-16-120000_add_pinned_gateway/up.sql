@@ -0,0 +1,2 @@
+ALTER TABLE fedimint
+    ADD COLUMN pinned_gateway TEXT;
@@ -0,0 +1,5 @@
+ALTER TABLE cashu_mint
+    DROP COLUMN notes;
+
+ALTER TABLE fedimint
+    DROP COLUMN notes;
@@ -0,0 +1,5 @@
+ALTER TABLE fedimint
+    ADD COLUMN notes TEXT;
+
+ALTER TABLE cashu_mint
+    ADD COLUMN notes TEXT;
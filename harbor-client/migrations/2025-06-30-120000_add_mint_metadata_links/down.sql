@@ -0,0 +1,5 @@
+ALTER TABLE mint_metadata
+    DROP COLUMN site_url;
+
+ALTER TABLE mint_metadata
+    DROP COLUMN icon_url;
@@ -0,0 +1,5 @@
+ALTER TABLE mint_metadata
+    ADD COLUMN icon_url TEXT;
+
+ALTER TABLE mint_metadata
+    ADD COLUMN site_url TEXT;
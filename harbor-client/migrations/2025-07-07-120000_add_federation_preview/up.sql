@@ -0,0 +1,7 @@
+CREATE TABLE federation_preview
+(
+    id           TEXT PRIMARY KEY NOT NULL,
+    config       BLOB             NOT NULL,
+    fetched_at   TIMESTAMP        NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_used_at TIMESTAMP        NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
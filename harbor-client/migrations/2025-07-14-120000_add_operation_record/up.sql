@@ -0,0 +1,11 @@
+CREATE TABLE operation_record
+(
+    id         TEXT PRIMARY KEY NOT NULL,
+    kind       INTEGER          NOT NULL,
+    target     TEXT             NOT NULL,
+    mint       TEXT             NOT NULL,
+    started_at TIMESTAMP        NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ended_at   TIMESTAMP,
+    outcome    INTEGER          NOT NULL DEFAULT 0,
+    error_kind TEXT
+);
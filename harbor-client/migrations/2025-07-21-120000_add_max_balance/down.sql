@@ -0,0 +1,8 @@
+ALTER TABLE cashu_mint
+    DROP COLUMN max_balance_msats;
+
+ALTER TABLE fedimint
+    DROP COLUMN max_balance_msats;
+
+ALTER TABLE mint_metadata
+    DROP COLUMN max_balance_msats;
@@ -0,0 +1,8 @@
+ALTER TABLE mint_metadata
+    ADD COLUMN max_balance_msats BIGINT;
+
+ALTER TABLE fedimint
+    ADD COLUMN max_balance_msats BIGINT;
+
+ALTER TABLE cashu_mint
+    ADD COLUMN max_balance_msats BIGINT;
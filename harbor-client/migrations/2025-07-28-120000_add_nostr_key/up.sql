@@ -0,0 +1,2 @@
+ALTER TABLE profile
+    ADD COLUMN nostr_secret_key TEXT;
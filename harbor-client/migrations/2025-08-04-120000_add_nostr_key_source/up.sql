@@ -0,0 +1,2 @@
+ALTER TABLE profile
+    ADD COLUMN nostr_key_derived INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// What diagnostics say about a task that was held back
+pub const SKIPPED_REASON: &str = "skipped: background data disabled";
+
+/// The network work Harbor does on its own, without the user asking for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BackgroundTask {
+    GuardianHealthChecks,
+    MetadataRefresh,
+    IconDownloads,
+}
+
+impl std::fmt::Display for BackgroundTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GuardianHealthChecks => write!(f, "guardian health checks"),
+            Self::MetadataRefresh => write!(f, "metadata refresh"),
+            Self::IconDownloads => write!(f, "icon downloads"),
+        }
+    }
+}
+
+/// The one place every background task asks before going to the network.
+///
+/// Reducing background data holds all of them back at once. Anything the user asks for doesn't
+/// go through here. The tasks keep their schedules while held back, so turning it off again
+/// picks them up on their next run.
+#[derive(Debug, Clone, Default)]
+pub struct BackgroundGate {
+    reduced: Arc<AtomicBool>,
+    /// Tasks held back so far, cleared once background data is allowed again
+    skipped: Arc<Mutex<BTreeSet<BackgroundTask>>>,
+}
+
+impl BackgroundGate {
+    pub fn set_reduced(&self, reduced: bool) {
+        self.reduced.store(reduced, Ordering::Relaxed);
+        if !reduced {
+            if let Ok(mut skipped) = self.skipped.lock() {
+                skipped.clear();
+            }
+        }
+    }
+
+    pub fn is_reduced(&self) -> bool {
+        self.reduced.load(Ordering::Relaxed)
+    }
+
+    /// Whether `task` can go ahead, noting that it was held back if not
+    pub fn allow(&self, task: BackgroundTask) -> bool {
+        if !self.is_reduced() {
+            return true;
+        }
+        log::debug!("Not running {task}, background data is reduced");
+        if let Ok(mut skipped) = self.skipped.lock() {
+            skipped.insert(task);
+        }
+        false
+    }
+
+    /// A line for each task held back since background data was reduced, like
+    /// "metadata refresh: skipped: background data disabled"
+    pub fn skipped(&self) -> Vec<String> {
+        let Ok(skipped) = self.skipped.lock() else {
+            return vec![];
+        };
+        skipped
+            .iter()
+            .map(|task| format!("{task}: {SKIPPED_REASON}"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_background_gate() {
+        let gate = BackgroundGate::default();
+        assert!(gate.allow(BackgroundTask::MetadataRefresh));
+        assert!(gate.skipped().is_empty());
+
+        // Everything is held back and remembered, once per task however often it comes up
+        gate.set_reduced(true);
+        assert!(!gate.allow(BackgroundTask::IconDownloads));
+        assert!(!gate.allow(BackgroundTask::GuardianHealthChecks));
+        assert!(!gate.allow(BackgroundTask::GuardianHealthChecks));
+        assert_eq!(
+            gate.skipped(),
+            vec![
+                "guardian health checks: skipped: background data disabled".to_string(),
+                "icon downloads: skipped: background data disabled".to_string(),
+            ]
+        );
+
+        // Clones share the setting, and allowing it again starts over
+        #[allow(clippy::redundant_clone)]
+        let clone = gate.clone();
+        clone.set_reduced(false);
+        assert!(gate.allow(BackgroundTask::IconDownloads));
+        assert!(gate.skipped().is_empty());
+    }
+}
@@ -198,8 +198,9 @@ pub fn spawn_lightning_payment_thread(
                 );
                 let preimage: [u8; 32] = FromHex::from_hex(&outgoing.preimage.unwrap())
                     .expect("preimage must be valid hex");
+                let fee = Amount::from_sats(outgoing.fee_paid.into());
                 let params = if is_transfer {
-                    SendSuccessMsg::Transfer
+                    SendSuccessMsg::Transfer { fee: Some(fee) }
                 } else {
                     SendSuccessMsg::Lightning { preimage }
                 };
@@ -221,8 +222,6 @@ pub fn spawn_lightning_payment_thread(
                 )
                 .await;
 
-                let fee = Amount::from_sats(outgoing.fee_paid.into());
-
                 if let Err(e) =
                     storage.set_lightning_as_complete(quote.id, preimage, Some(fee.msats))
                 {
@@ -273,10 +272,17 @@ pub fn spawn_lightning_receive_thread(
             };
 
             if mint_quote_response.state == MintQuoteState::Paid {
-                client
-                    .mint(&quote.id, SplitTarget::default(), None)
-                    .await
-                    .expect("Failed to mint receive tokens");
+                // The quote stays paid in the wallet, so it can still be claimed later
+                if let Err(e) = client.mint(&quote.id, SplitTarget::default(), None).await {
+                    error!("Failed to mint receive tokens: {e}");
+                    HarborCore::send_msg(
+                        &mut sender,
+                        Some(msg_id),
+                        CoreUIMsg::ReceiveFailed(format!("Paid, but not issued yet: {e}")),
+                    )
+                    .await;
+                    break;
+                }
 
                 let params = if is_transfer {
                     ReceiveSuccessMsg::Transfer
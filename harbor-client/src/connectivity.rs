@@ -0,0 +1,106 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+/// How often the connection is checked while the wallet is open
+pub const CONNECTIVITY_CHECK_EVERY: Duration = Duration::from_secs(15);
+
+// Anycast resolvers that are up whenever the internet is. Only a TCP handshake is made and
+// nothing is sent, going by IP so a broken DNS setup doesn't look like being offline. With Tor
+// on this is still a direct connection, it only shows the machine is online, not what it talks to.
+const PROBE_ADDRS: [&str; 2] = ["1.1.1.1:443", "9.9.9.9:443"];
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+// One lost handshake isn't enough to call the connection gone and disable everything
+const FAILURES_UNTIL_OFFLINE: u32 = 2;
+
+async fn probe() -> bool {
+    for addr in PROBE_ADDRS {
+        let Ok(addr) = addr.parse::<SocketAddr>() else {
+            continue;
+        };
+        if let Ok(Ok(_)) = tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect(addr)).await {
+            return true;
+        }
+    }
+    false
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ConnectivityState {
+    online: bool,
+    failures: u32,
+}
+
+impl Default for ConnectivityState {
+    fn default() -> Self {
+        Self {
+            online: true,
+            failures: 0,
+        }
+    }
+}
+
+/// Whether the machine is online going by the last checks, shared so anything about to go to
+/// the network can give up straight away when it isn't. Online until a check says otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct Connectivity {
+    state: Arc<Mutex<ConnectivityState>>,
+}
+
+impl Connectivity {
+    pub fn is_online(&self) -> bool {
+        self.state.lock().map_or(true, |state| state.online)
+    }
+
+    // Records a check, returning whether the machine is online now if that changed
+    fn observe(&self, reachable: bool) -> Option<bool> {
+        let mut state = self.state.lock().ok()?;
+        state.failures = if reachable { 0 } else { state.failures + 1 };
+        let online = reachable || (state.online && state.failures < FAILURES_UNTIL_OFFLINE);
+        if online == state.online {
+            return None;
+        }
+        state.online = online;
+        Some(online)
+    }
+
+    /// Checks the connection, returning whether the machine is online now if that changed
+    pub async fn check(&self) -> Option<bool> {
+        let changed = self.observe(probe().await);
+        match changed {
+            Some(false) => log::warn!("Lost the internet connection"),
+            Some(true) => log::info!("Internet connection is back"),
+            None => {}
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connectivity() {
+        let connectivity = Connectivity::default();
+        assert!(connectivity.is_online());
+        assert_eq!(connectivity.observe(true), None);
+
+        // A single failed check is let go
+        assert_eq!(connectivity.observe(false), None);
+        assert!(connectivity.is_online());
+        assert_eq!(connectivity.observe(true), None);
+
+        // Two in a row is offline, and staying offline isn't reported again
+        assert_eq!(connectivity.observe(false), None);
+        assert_eq!(connectivity.observe(false), Some(false));
+        assert!(!connectivity.is_online());
+        assert_eq!(connectivity.observe(false), None);
+
+        // The first check that gets through brings it back
+        assert_eq!(connectivity.observe(true), Some(true));
+        assert!(connectivity.is_online());
+    }
+}
@@ -1,10 +1,16 @@
+use crate::MintIdentifier;
+use crate::db_models::federation_preview::FederationPreview;
 use crate::db_models::mint_metadata::MintMetadata;
+use crate::db_models::operation_record::{OperationOutcome, OperationRecord};
 use crate::db_models::transaction_item::TransactionItem;
 use crate::db_models::{
     CashuMint, Fedimint, LightningPayment, LightningReceive, NewFedimint, NewProfile,
     OnChainPayment, OnChainReceive, Profile,
 };
 use crate::metadata::FederationMeta;
+use crate::mint_error::AddMintErrorKind;
+use crate::nostr::{NostrIdentity, NostrKeySource};
+use crate::operations::OperationKind;
 use anyhow::anyhow;
 use bip39::{Language, Mnemonic};
 use bitcoin::{Address, Txid};
@@ -23,6 +29,7 @@ use log::{error, info};
 use rusqlite::{Connection, OpenFlags};
 use std::str::FromStr;
 use std::{sync::Arc, time::Duration};
+use uuid::Uuid;
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
 
@@ -84,6 +91,12 @@ pub trait DBConnection {
     // Sets the tor enabled flag
     fn set_tor_enabled(&self, enabled: bool) -> anyhow::Result<()>;
 
+    // Sets or removes the user's nostr key
+    fn set_nostr_identity(
+        &self,
+        identity: Option<(&NostrIdentity, NostrKeySource)>,
+    ) -> anyhow::Result<()>;
+
     // Retrieves the mnemonic from the DB
     fn retrieve_mnemonic(&self) -> anyhow::Result<Mnemonic>;
 
@@ -96,6 +109,9 @@ pub trait DBConnection {
     // Removes a federation from the DB
     fn remove_federation(&self, f: FederationId) -> anyhow::Result<()>;
 
+    // Deletes a federation that was never fully joined
+    fn delete_federation(&self, f: FederationId) -> anyhow::Result<()>;
+
     fn remove_cashu_mint(&self, f: &MintUrl) -> anyhow::Result<()>;
 
     // Sets a federation as active
@@ -104,6 +120,29 @@ pub trait DBConnection {
     // Gets a federation's invite code
     fn get_federation_invite_code(&self, f: FederationId) -> anyhow::Result<Option<InviteCode>>;
 
+    // Gets the stored record for a federation, archived or not
+    fn get_fedimint(&self, f: FederationId) -> anyhow::Result<Option<Fedimint>>;
+
+    // Sets or clears the user-defined nickname for a federation
+    fn set_federation_nickname(
+        &self,
+        f: FederationId,
+        nickname: Option<String>,
+    ) -> anyhow::Result<()>;
+
+    // Sets or clears the user's notes about a federation
+    fn set_federation_notes(&self, f: FederationId, notes: Option<String>) -> anyhow::Result<()>;
+
+    // Sets or clears the user's cap on how much to hold in a federation
+    fn set_federation_max_balance(
+        &self,
+        f: FederationId,
+        max: Option<Amount>,
+    ) -> anyhow::Result<()>;
+
+    // Sets or clears the gateway LN operations in a federation should use
+    fn set_pinned_gateway(&self, f: FederationId, gateway: Option<String>) -> anyhow::Result<()>;
+
     // gets the federation data for a specific federation
     fn get_federation_value(&self, id: String) -> anyhow::Result<Option<Vec<u8>>>;
 
@@ -120,6 +159,22 @@ pub trait DBConnection {
 
     fn set_cashu_mint_active(&self, url: String) -> anyhow::Result<()>;
 
+    // Gets the stored record for a cashu mint, archived or not
+    fn get_cashu_mint(&self, url: &MintUrl) -> anyhow::Result<Option<CashuMint>>;
+
+    // Sets or clears the user-defined nickname for a cashu mint
+    fn set_cashu_mint_nickname(
+        &self,
+        url: &MintUrl,
+        nickname: Option<String>,
+    ) -> anyhow::Result<()>;
+
+    // Sets or clears the user's notes about a cashu mint
+    fn set_cashu_mint_notes(&self, url: &MintUrl, notes: Option<String>) -> anyhow::Result<()>;
+
+    // Sets or clears the user's cap on how much to hold in a cashu mint
+    fn set_cashu_mint_max_balance(&self, url: &MintUrl, max: Option<Amount>) -> anyhow::Result<()>;
+
     // updates the federation data
     fn update_fedimint_data(&self, id: String, value: Vec<u8>) -> anyhow::Result<()>;
 
@@ -190,7 +245,11 @@ pub trait DBConnection {
 
     fn mark_onchain_receive_as_confirmed(&self, operation_id: String) -> anyhow::Result<()>;
 
-    fn get_transaction_history(&self) -> anyhow::Result<Vec<TransactionItem>>;
+    // Everything when `mint` is None, otherwise only what went through that mint
+    fn get_transaction_history(
+        &self,
+        mint: Option<&MintIdentifier>,
+    ) -> anyhow::Result<Vec<TransactionItem>>;
 
     fn get_pending_onchain_receives(&self) -> anyhow::Result<Vec<OnChainReceive>>;
 
@@ -221,6 +280,34 @@ pub trait DBConnection {
         id: FederationId,
         metadata: FederationMeta,
     ) -> anyhow::Result<()>;
+
+    fn get_federation_preview(&self, id: FederationId)
+    -> anyhow::Result<Option<FederationPreview>>;
+
+    fn upsert_federation_preview(&self, id: FederationId, config: Vec<u8>) -> anyhow::Result<()>;
+
+    fn remove_federation_preview(&self, id: FederationId) -> anyhow::Result<()>;
+
+    fn prune_federation_previews(&self, unused_for: Duration) -> anyhow::Result<usize>;
+
+    fn start_operation_record(
+        &self,
+        id: Uuid,
+        kind: OperationKind,
+        target: String,
+        mint: String,
+    ) -> anyhow::Result<()>;
+
+    fn finish_operation_record(
+        &self,
+        id: Uuid,
+        outcome: OperationOutcome,
+        error_kind: Option<AddMintErrorKind>,
+    ) -> anyhow::Result<()>;
+
+    fn interrupt_running_operations(&self) -> anyhow::Result<usize>;
+
+    fn get_operation_records(&self) -> anyhow::Result<Vec<OperationRecord>>;
 }
 
 pub struct SQLConnection {
@@ -254,6 +341,14 @@ impl DBConnection for SQLConnection {
         Ok(())
     }
 
+    fn set_nostr_identity(
+        &self,
+        identity: Option<(&NostrIdentity, NostrKeySource)>,
+    ) -> anyhow::Result<()> {
+        let conn = &mut self.db.get()?;
+        Profile::set_nostr_identity(conn, identity)
+    }
+
     fn get_federation_value(&self, id: String) -> anyhow::Result<Option<Vec<u8>>> {
         let conn = &mut self.db.get()?;
         Fedimint::get_value(conn, id)
@@ -279,6 +374,40 @@ impl DBConnection for SQLConnection {
         Fedimint::set_active(conn, f.to_string())
     }
 
+    fn get_fedimint(&self, f: FederationId) -> anyhow::Result<Option<Fedimint>> {
+        let conn = &mut self.db.get()?;
+        Fedimint::get(conn, f.to_string())
+    }
+
+    fn set_federation_nickname(
+        &self,
+        f: FederationId,
+        nickname: Option<String>,
+    ) -> anyhow::Result<()> {
+        let conn = &mut self.db.get()?;
+        Fedimint::set_nickname(conn, f.to_string(), nickname)
+    }
+
+    fn set_federation_notes(&self, f: FederationId, notes: Option<String>) -> anyhow::Result<()> {
+        let conn = &mut self.db.get()?;
+        Fedimint::set_notes(conn, f.to_string(), notes)
+    }
+
+    fn set_federation_max_balance(
+        &self,
+        f: FederationId,
+        max: Option<Amount>,
+    ) -> anyhow::Result<()> {
+        let max = max.map(|max| i64::try_from(max.msats)).transpose()?;
+        let conn = &mut self.db.get()?;
+        Fedimint::set_max_balance(conn, f.to_string(), max)
+    }
+
+    fn set_pinned_gateway(&self, f: FederationId, gateway: Option<String>) -> anyhow::Result<()> {
+        let conn = &mut self.db.get()?;
+        Fedimint::set_pinned_gateway(conn, f.to_string(), gateway)
+    }
+
     fn list_cashu_mints(&self) -> anyhow::Result<Vec<String>> {
         let conn = &mut self.db.get()?;
         CashuMint::get_mints(conn)
@@ -294,6 +423,31 @@ impl DBConnection for SQLConnection {
         CashuMint::set_active(conn, &url)
     }
 
+    fn get_cashu_mint(&self, url: &MintUrl) -> anyhow::Result<Option<CashuMint>> {
+        let conn = &mut self.db.get()?;
+        CashuMint::get(conn, url.to_string())
+    }
+
+    fn set_cashu_mint_nickname(
+        &self,
+        url: &MintUrl,
+        nickname: Option<String>,
+    ) -> anyhow::Result<()> {
+        let conn = &mut self.db.get()?;
+        CashuMint::set_nickname(conn, url.to_string(), nickname)
+    }
+
+    fn set_cashu_mint_notes(&self, url: &MintUrl, notes: Option<String>) -> anyhow::Result<()> {
+        let conn = &mut self.db.get()?;
+        CashuMint::set_notes(conn, url.to_string(), notes)
+    }
+
+    fn set_cashu_mint_max_balance(&self, url: &MintUrl, max: Option<Amount>) -> anyhow::Result<()> {
+        let max = max.map(|max| i64::try_from(max.msats)).transpose()?;
+        let conn = &mut self.db.get()?;
+        CashuMint::set_max_balance(conn, url.to_string(), max)
+    }
+
     fn create_ln_receive(
         &self,
         operation_id: String,
@@ -305,6 +459,8 @@ impl DBConnection for SQLConnection {
     ) -> anyhow::Result<()> {
         let conn = &mut self.db.get()?;
 
+        touch_mint_last_used(conn, fedimint_id, cashu_mint_url.as_ref())?;
+
         LightningReceive::create(
             conn,
             operation_id,
@@ -345,6 +501,8 @@ impl DBConnection for SQLConnection {
     ) -> anyhow::Result<()> {
         let conn = &mut self.db.get()?;
 
+        touch_mint_last_used(conn, fedimint_id, cashu_mint_url.as_ref())?;
+
         LightningPayment::create(
             conn,
             operation_id,
@@ -388,6 +546,8 @@ impl DBConnection for SQLConnection {
     ) -> anyhow::Result<()> {
         let conn = &mut self.db.get()?;
 
+        touch_mint_last_used(conn, fedimint_id, cashu_mint_url.as_ref())?;
+
         OnChainReceive::create(conn, operation_id, fedimint_id, cashu_mint_url, address)?;
 
         Ok(())
@@ -404,6 +564,8 @@ impl DBConnection for SQLConnection {
     ) -> anyhow::Result<()> {
         let conn = &mut self.db.get()?;
 
+        touch_mint_last_used(conn, fedimint_id, cashu_mint_url.as_ref())?;
+
         OnChainPayment::create(
             conn,
             operation_id,
@@ -463,13 +625,16 @@ impl DBConnection for SQLConnection {
         Ok(())
     }
 
-    fn get_transaction_history(&self) -> anyhow::Result<Vec<TransactionItem>> {
+    fn get_transaction_history(
+        &self,
+        mint: Option<&MintIdentifier>,
+    ) -> anyhow::Result<Vec<TransactionItem>> {
         let conn = &mut self.db.get()?;
 
-        let onchain_payments = OnChainPayment::get_history(conn)?;
-        let onchain_receives = OnChainReceive::get_history(conn)?;
-        let lightning_payments = LightningPayment::get_history(conn)?;
-        let lightning_receives = LightningReceive::get_history(conn)?;
+        let onchain_payments = OnChainPayment::get_history(conn, mint)?;
+        let onchain_receives = OnChainReceive::get_history(conn, mint)?;
+        let lightning_payments = LightningPayment::get_history(conn, mint)?;
+        let lightning_receives = LightningReceive::get_history(conn, mint)?;
 
         let mut items: Vec<TransactionItem> = Vec::with_capacity(
             onchain_payments.len()
@@ -506,6 +671,11 @@ impl DBConnection for SQLConnection {
         Ok(())
     }
 
+    fn delete_federation(&self, f: FederationId) -> anyhow::Result<()> {
+        let conn = &mut self.db.get()?;
+        Fedimint::delete(conn, f.to_string())
+    }
+
     fn remove_cashu_mint(&self, f: &MintUrl) -> anyhow::Result<()> {
         let conn = &mut self.db.get()?;
         CashuMint::remove_mint(conn, f.to_string())?;
@@ -605,15 +775,72 @@ impl DBConnection for SQLConnection {
         Ok(())
     }
 
+    fn get_federation_preview(
+        &self,
+        id: FederationId,
+    ) -> anyhow::Result<Option<FederationPreview>> {
+        let conn = &mut self.db.get()?;
+        FederationPreview::get(conn, id.to_string())
+    }
+
+    fn upsert_federation_preview(&self, id: FederationId, config: Vec<u8>) -> anyhow::Result<()> {
+        let conn = &mut self.db.get()?;
+        FederationPreview::upsert(conn, id.to_string(), config)
+    }
+
+    fn remove_federation_preview(&self, id: FederationId) -> anyhow::Result<()> {
+        let conn = &mut self.db.get()?;
+        FederationPreview::remove(conn, id.to_string())
+    }
+
+    fn prune_federation_previews(&self, unused_for: Duration) -> anyhow::Result<usize> {
+        let cutoff = chrono::Utc::now().naive_utc() - chrono::Duration::from_std(unused_for)?;
+        let conn = &mut self.db.get()?;
+        FederationPreview::prune(conn, cutoff)
+    }
+
+    fn start_operation_record(
+        &self,
+        id: Uuid,
+        kind: OperationKind,
+        target: String,
+        mint: String,
+    ) -> anyhow::Result<()> {
+        let conn = &mut self.db.get()?;
+        OperationRecord::start(conn, id.to_string(), kind, target, mint)
+    }
+
+    fn finish_operation_record(
+        &self,
+        id: Uuid,
+        outcome: OperationOutcome,
+        error_kind: Option<AddMintErrorKind>,
+    ) -> anyhow::Result<()> {
+        let conn = &mut self.db.get()?;
+        OperationRecord::finish(conn, id.to_string(), outcome, error_kind)
+    }
+
+    fn interrupt_running_operations(&self) -> anyhow::Result<usize> {
+        let conn = &mut self.db.get()?;
+        OperationRecord::interrupt_running(conn)
+    }
+
+    fn get_operation_records(&self) -> anyhow::Result<Vec<OperationRecord>> {
+        let conn = &mut self.db.get()?;
+        OperationRecord::list(conn)
+    }
+
     fn get_archived_fedimints(&self) -> anyhow::Result<Vec<MintMetadata>> {
         let conn = &mut self.db.get()?;
         let ids = Fedimint::get_archived_ids(conn)?;
         let mut result = Vec::with_capacity(ids.len());
         for id in ids {
-            let m = MintMetadata::get(conn, id.to_string())?;
-            if let Some(m) = m {
-                result.push(m);
-            }
+            // Still list a federation whose metadata never arrived, it just won't have a name
+            let m = match MintMetadata::get(conn, id.to_string())? {
+                Some(m) => m,
+                None => MintMetadata::from(FederationId::from_str(&id)?, FederationMeta::default()),
+            };
+            result.push(m);
         }
         Ok(result)
     }
@@ -634,6 +861,20 @@ impl DBConnection for SQLConnection {
     }
 }
 
+fn touch_mint_last_used(
+    conn: &mut SqliteConnection,
+    fedimint_id: Option<FederationId>,
+    cashu_mint_url: Option<&MintUrl>,
+) -> anyhow::Result<()> {
+    if let Some(id) = fedimint_id {
+        Fedimint::touch_last_used(conn, id.to_string())?;
+    }
+    if let Some(url) = cashu_mint_url {
+        CashuMint::touch_last_used(conn, url.to_string())?;
+    }
+    Ok(())
+}
+
 fn normalize_password(password: &str) -> String {
     password.replace("'", "''")
 }
@@ -741,6 +982,32 @@ mod tests {
         assert_eq!(seed.unwrap(), p.seed_words);
     }
 
+    #[test]
+    fn test_nostr_identity() {
+        let db = setup_test_db_with_data();
+        let profile = db.get_profile().unwrap().unwrap();
+        assert_eq!(profile.nostr_identity(), None);
+
+        let identity = NostrIdentity::generate().unwrap();
+        db.set_nostr_identity(Some((&identity, NostrKeySource::Separate)))
+            .unwrap();
+        let profile = db.get_profile().unwrap().unwrap();
+        assert_eq!(profile.nostr_identity(), Some(identity));
+        assert_eq!(profile.nostr_key_source(), NostrKeySource::Separate);
+
+        let derived = NostrIdentity::from_seed(&profile.mnemonic()).unwrap();
+        db.set_nostr_identity(Some((&derived, NostrKeySource::Seed)))
+            .unwrap();
+        let profile = db.get_profile().unwrap().unwrap();
+        assert_eq!(profile.nostr_identity(), Some(derived));
+        assert_eq!(profile.nostr_key_source(), NostrKeySource::Seed);
+
+        db.set_nostr_identity(None).unwrap();
+        let profile = db.get_profile().unwrap().unwrap();
+        assert_eq!(profile.nostr_identity(), None);
+        assert_eq!(profile.nostr_key_source(), NostrKeySource::Separate);
+    }
+
     #[test]
     fn test_insert_new_federation() {
         let db = setup_test_db();
@@ -767,6 +1034,404 @@ mod tests {
         assert_eq!(federation.unwrap(), new_fedimint.value);
     }
 
+    #[test]
+    fn test_mint_nickname() {
+        let db = setup_test_db_with_data();
+        let federation_id = FederationId::from_str(FEDERATION_ID).unwrap();
+
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.nickname, None);
+
+        db.set_federation_nickname(federation_id, Some("Meetup mint".to_string()))
+            .unwrap();
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.nickname.as_deref(), Some("Meetup mint"));
+
+        // nickname should survive archiving the federation
+        db.remove_federation(federation_id).unwrap();
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.nickname.as_deref(), Some("Meetup mint"));
+
+        db.set_federation_nickname(federation_id, None).unwrap();
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.nickname, None);
+
+        let mint_url = MintUrl::from_str("https://mint.example.com").unwrap();
+        db.insert_new_cashu_mint(mint_url.to_string()).unwrap();
+        db.set_cashu_mint_nickname(&mint_url, Some("Squirrel".to_string()))
+            .unwrap();
+        let mint = db.get_cashu_mint(&mint_url).unwrap().unwrap();
+        assert_eq!(mint.nickname.as_deref(), Some("Squirrel"));
+    }
+
+    #[test]
+    fn test_mint_notes() {
+        let db = setup_test_db_with_data();
+        let federation_id = FederationId::from_str(FEDERATION_ID).unwrap();
+
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.notes, None);
+
+        let notes = "Local meetup mint, fees spike on weekends";
+        db.set_federation_notes(federation_id, Some(notes.to_string()))
+            .unwrap();
+
+        // Notes survive archiving and rejoining
+        db.remove_federation(federation_id).unwrap();
+        db.set_federation_active(federation_id).unwrap();
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.notes.as_deref(), Some(notes));
+
+        let mint_url = MintUrl::from_str("https://mint.example.com").unwrap();
+        db.insert_new_cashu_mint(mint_url.to_string()).unwrap();
+        db.set_cashu_mint_notes(&mint_url, Some(notes.to_string()))
+            .unwrap();
+        let mint = db.get_cashu_mint(&mint_url).unwrap().unwrap();
+        assert_eq!(mint.notes.as_deref(), Some(notes));
+
+        db.set_cashu_mint_notes(&mint_url, None).unwrap();
+        let mint = db.get_cashu_mint(&mint_url).unwrap().unwrap();
+        assert_eq!(mint.notes, None);
+    }
+
+    #[test]
+    fn test_mint_max_balance() {
+        let db = setup_test_db_with_data();
+        let federation_id = FederationId::from_str(FEDERATION_ID).unwrap();
+        let max = Amount::from_sats(100_000);
+
+        db.set_federation_max_balance(federation_id, Some(max))
+            .unwrap();
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.max_balance_msats, Some(100_000_000));
+
+        db.set_federation_max_balance(federation_id, None).unwrap();
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.max_balance_msats, None);
+
+        let mint_url = MintUrl::from_str("https://mint.example.com").unwrap();
+        db.insert_new_cashu_mint(mint_url.to_string()).unwrap();
+        db.set_cashu_mint_max_balance(&mint_url, Some(max)).unwrap();
+        let mint = db.get_cashu_mint(&mint_url).unwrap().unwrap();
+        assert_eq!(mint.max_balance_msats, Some(100_000_000));
+
+        // A cap too big to store is refused rather than wrapped
+        assert!(
+            db.set_cashu_mint_max_balance(&mint_url, Some(Amount::from_msats(u64::MAX)))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_mint_timestamps() {
+        let db = setup_test_db_with_data();
+        let federation_id = FederationId::from_str(FEDERATION_ID).unwrap();
+
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert!(federation.joined_at.is_some());
+        assert_eq!(federation.last_used_at, None);
+
+        let address = Address::from_str("tb1qd28npep0s8frcm3y7dxqajkcy2m40eysplyr9v")
+            .unwrap()
+            .assume_checked();
+        db.create_onchain_receive(
+            OperationId::new_random().fmt_full().to_string(),
+            Some(federation_id),
+            None,
+            address,
+        )
+        .unwrap();
+
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert!(federation.last_used_at.is_some());
+        assert!(federation.last_used_at >= federation.joined_at);
+
+        let mint_url = MintUrl::from_str("https://mint.example.com").unwrap();
+        db.insert_new_cashu_mint(mint_url.to_string()).unwrap();
+        let mint = db.get_cashu_mint(&mint_url).unwrap().unwrap();
+        assert!(mint.joined_at.is_some());
+        assert_eq!(mint.last_used_at, None);
+    }
+
+    #[test]
+    fn test_mint_timestamps_backfill() {
+        use crate::db_models::schema::{cashu_mint, fedimint};
+        use diesel::{Connection, QueryDsl, RunQueryDsl};
+
+        let conn = &mut SqliteConnection::establish(":memory:").unwrap();
+
+        // Bring the schema up to just before the timestamps were added
+        for migration in conn.pending_migrations(MIGRATIONS).unwrap() {
+            if migration.name().to_string() == "2025-06-09-120000_add_mint_timestamps" {
+                break;
+            }
+            conn.run_migration(&migration).unwrap();
+        }
+
+        conn.batch_execute(&format!(
+            "INSERT INTO fedimint (id, invite_code, value) VALUES ('{FEDERATION_ID}', '{INVITE_CODE}', x'');
+             INSERT INTO fedimint (id, invite_code, value) VALUES ('unused', 'unused', x'');
+             INSERT INTO cashu_mint (mint_url) VALUES ('https://mint.example.com');
+             INSERT INTO on_chain_receives (operation_id, fedimint_id, address, status, created_at)
+                 VALUES ('a', '{FEDERATION_ID}', 'address', 0, '2024-01-01 00:00:00');
+             INSERT INTO lightning_payments (operation_id, fedimint_id, payment_hash, bolt11, amount_msats, fee_msats, status, created_at)
+                 VALUES ('b', '{FEDERATION_ID}', 'hash', 'bolt11', 1000, 0, 0, '2024-02-01 00:00:00');
+             INSERT INTO lightning_receives (operation_id, cashu_mint_url, payment_hash, bolt11, amount_msats, fee_msats, status, created_at)
+                 VALUES ('c', 'https://mint.example.com', 'hash', 'bolt11', 1000, 0, 0, '2024-03-01 00:00:00');"
+        ))
+        .unwrap();
+        conn.run_pending_migrations(MIGRATIONS).unwrap();
+
+        let at =
+            |s: &str| Some(chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap());
+        let mut federation_times = |id: &str| {
+            fedimint::table
+                .find(id.to_string())
+                .select((fedimint::joined_at, fedimint::last_used_at))
+                .first::<(Option<chrono::NaiveDateTime>, Option<chrono::NaiveDateTime>)>(conn)
+                .unwrap()
+        };
+
+        // The first and latest transactions stand in for the join and last use
+        assert_eq!(
+            federation_times(FEDERATION_ID),
+            (at("2024-01-01 00:00:00"), at("2024-02-01 00:00:00"))
+        );
+        // and a mint without any keeps both unset
+        assert_eq!(federation_times("unused"), (None, None));
+
+        let mint_times = cashu_mint::table
+            .select((cashu_mint::joined_at, cashu_mint::last_used_at))
+            .first::<(Option<chrono::NaiveDateTime>, Option<chrono::NaiveDateTime>)>(conn)
+            .unwrap();
+        assert_eq!(
+            mint_times,
+            (at("2024-03-01 00:00:00"), at("2024-03-01 00:00:00"))
+        );
+    }
+
+    #[test]
+    fn test_archived_fedimints() {
+        let db = setup_test_db_with_data();
+        let federation_id = FederationId::from_str(FEDERATION_ID).unwrap();
+        assert!(db.get_archived_fedimints().unwrap().is_empty());
+
+        // Archived federations are listed even if their metadata was never fetched
+        db.remove_federation(federation_id).unwrap();
+        let archived = db.get_archived_fedimints().unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].id, FEDERATION_ID);
+        assert_eq!(archived[0].name, None);
+    }
+
+    #[test]
+    fn test_federation_preview_cache() {
+        use crate::db_models::schema::federation_preview;
+        use diesel::prelude::*;
+
+        let db = setup_test_db_with_data();
+        let joined = FederationId::from_str(FEDERATION_ID).unwrap();
+        let previewed = FederationId::dummy();
+        let metadata = FederationMeta {
+            federation_name: Some("Previewed".to_string()),
+            ..FederationMeta::default()
+        };
+
+        assert_eq!(db.get_federation_preview(previewed).unwrap(), None);
+        db.upsert_federation_preview(previewed, vec![1, 2, 3])
+            .unwrap();
+        db.upsert_federation_metadata(previewed, metadata.clone())
+            .unwrap();
+        db.upsert_federation_metadata(joined, metadata).unwrap();
+
+        let preview = db.get_federation_preview(previewed).unwrap().unwrap();
+        assert_eq!(preview.config, vec![1, 2, 3]);
+
+        // A preview shown recently survives pruning, and so does its metadata
+        assert_eq!(
+            db.prune_federation_previews(Duration::from_secs(60))
+                .unwrap(),
+            0
+        );
+        assert!(db.get_federation_metadata(previewed).unwrap().is_some());
+
+        let old = chrono::Utc::now().naive_utc() - chrono::Duration::days(31);
+        let mut conn = db.db.get().unwrap();
+        diesel::update(federation_preview::table)
+            .set(federation_preview::last_used_at.eq(old))
+            .execute(&mut conn)
+            .unwrap();
+        drop(conn);
+
+        assert_eq!(
+            db.prune_federation_previews(Duration::from_secs(30 * 24 * 60 * 60))
+                .unwrap(),
+            1
+        );
+        assert_eq!(db.get_federation_preview(previewed).unwrap(), None);
+        assert_eq!(db.get_federation_metadata(previewed).unwrap(), None);
+
+        // Metadata of a joined federation is never pruned, archived or not
+        db.remove_federation(joined).unwrap();
+        db.prune_federation_previews(Duration::ZERO).unwrap();
+        assert!(db.get_federation_metadata(joined).unwrap().is_some());
+
+        db.upsert_federation_preview(previewed, vec![4]).unwrap();
+        db.remove_federation_preview(previewed).unwrap();
+        assert_eq!(db.get_federation_preview(previewed).unwrap(), None);
+    }
+
+    #[test]
+    fn test_operation_records() {
+        use crate::db_models::operation_record::MAX_OPERATION_RECORDS;
+
+        let db = setup_test_db_with_data();
+        let joined = Uuid::new_v4();
+        let failed = Uuid::new_v4();
+        let running = Uuid::new_v4();
+
+        db.start_operation_record(
+            joined,
+            OperationKind::Join,
+            "https://mint.example.com".to_string(),
+            "https://mint.example.com".to_string(),
+        )
+        .unwrap();
+        db.finish_operation_record(joined, OperationOutcome::Succeeded, None)
+            .unwrap();
+        db.start_operation_record(
+            failed,
+            OperationKind::Peek,
+            "fed11invite".to_string(),
+            FEDERATION_ID.to_string(),
+        )
+        .unwrap();
+        db.finish_operation_record(
+            failed,
+            OperationOutcome::Failed,
+            Some(AddMintErrorKind::TimedOut),
+        )
+        .unwrap();
+        db.start_operation_record(
+            running,
+            OperationKind::Resync,
+            FEDERATION_ID.to_string(),
+            FEDERATION_ID.to_string(),
+        )
+        .unwrap();
+
+        // Newest first, with how each one ended
+        let records = db.get_operation_records().unwrap();
+        let ids: Vec<_> = records.iter().map(|r| r.id.clone()).collect();
+        assert_eq!(
+            ids,
+            vec![running.to_string(), failed.to_string(), joined.to_string()]
+        );
+        assert_eq!(records[0].outcome(), OperationOutcome::Running);
+        assert_eq!(records[0].ended_at, None);
+        assert_eq!(records[1].kind(), OperationKind::Peek);
+        assert_eq!(records[1].outcome(), OperationOutcome::Failed);
+        assert_eq!(records[1].error_kind(), Some(AddMintErrorKind::TimedOut));
+        assert_eq!(records[1].target, "fed11invite");
+        assert!(records[2].ended_at.is_some());
+        assert_eq!(records[2].error_kind(), None);
+
+        // Whatever was still running when Harbor closed is interrupted at the next start
+        assert_eq!(db.interrupt_running_operations().unwrap(), 1);
+        assert_eq!(db.interrupt_running_operations().unwrap(), 0);
+        let records = db.get_operation_records().unwrap();
+        assert_eq!(records[0].outcome(), OperationOutcome::Interrupted);
+        assert_eq!(records[1].outcome(), OperationOutcome::Failed);
+
+        // Only the newest records are kept
+        for _ in 0..MAX_OPERATION_RECORDS {
+            db.start_operation_record(
+                Uuid::new_v4(),
+                OperationKind::Peek,
+                String::new(),
+                String::new(),
+            )
+            .unwrap();
+        }
+        let records = db.get_operation_records().unwrap();
+        assert_eq!(
+            records.len(),
+            usize::try_from(MAX_OPERATION_RECORDS).unwrap()
+        );
+        assert!(!records.iter().any(|r| r.id == joined.to_string()));
+    }
+
+    #[test]
+    fn test_transaction_history_mint_filter() {
+        let db = setup_test_db_with_data();
+        let federation_id = FederationId::from_str(FEDERATION_ID).unwrap();
+        let mint_url = MintUrl::from_str("https://mint.example.com").unwrap();
+        db.insert_new_cashu_mint(mint_url.to_string()).unwrap();
+
+        let invoice = Bolt11Invoice::from_str("lntbs10u1pny86cupp52lkv666juacc9evu0fpfmduac6l6qp0qypxr0yk9wfpze2u5sngshp57t8sp5tcchfv0y29yg46nqujktk2ufwcjcc7zvyd8rteadd7rjyscqzzsxqyz5vqsp5nnhtrhvyfh077g6rdfrs7ml9hqks4mj6f0e50nyeejc73ee7gl3q9qyyssq3urmp6hy3c95rtddevae0djrfn8au0rumgd05zvddzshg8krwupzc4htl38kqufp27el5ev5l8ea4736y3a3rpq5cewxwftsdk2v52cp9w25a0").unwrap();
+
+        let fedimint_op = OperationId::new_random().fmt_full().to_string();
+        db.create_ln_receive(
+            fedimint_op.clone(),
+            Some(federation_id),
+            None,
+            invoice.clone(),
+            Amount::from_sats(1_000),
+            Amount::ZERO,
+        )
+        .unwrap();
+        db.mark_ln_receive_as_success(fedimint_op).unwrap();
+
+        let cashu_op = OperationId::new_random().fmt_full().to_string();
+        db.create_ln_receive(
+            cashu_op.clone(),
+            None,
+            Some(mint_url.clone()),
+            invoice,
+            Amount::from_sats(1_000),
+            Amount::ZERO,
+        )
+        .unwrap();
+        db.mark_ln_receive_as_success(cashu_op).unwrap();
+
+        assert_eq!(db.get_transaction_history(None).unwrap().len(), 2);
+
+        let fedimint = MintIdentifier::Fedimint(federation_id);
+        let history = db.get_transaction_history(Some(&fedimint)).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].mint_identifier, fedimint);
+
+        let cashu = MintIdentifier::Cashu(mint_url);
+        let history = db.get_transaction_history(Some(&cashu)).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].mint_identifier, cashu);
+    }
+
+    #[test]
+    fn test_pinned_gateway() {
+        let db = setup_test_db_with_data();
+        let federation_id = FederationId::from_str(FEDERATION_ID).unwrap();
+
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.pinned_gateway, None);
+
+        let gateway = "035f2f7912e0f570841d5c0d8976a40af0dcca5609198436f596e78d2c851ee58a";
+        db.set_pinned_gateway(federation_id, Some(gateway.to_string()))
+            .unwrap();
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.pinned_gateway.as_deref(), Some(gateway));
+
+        // The pin stays put across archiving and rejoining
+        db.remove_federation(federation_id).unwrap();
+        db.set_federation_active(federation_id).unwrap();
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.pinned_gateway.as_deref(), Some(gateway));
+
+        db.set_pinned_gateway(federation_id, None).unwrap();
+        let federation = db.get_fedimint(federation_id).unwrap().unwrap();
+        assert_eq!(federation.pinned_gateway, None);
+    }
+
     #[test]
     fn test_lightning_payment_db() {
         let db = setup_test_db_with_data();
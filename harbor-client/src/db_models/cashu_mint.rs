@@ -17,6 +17,12 @@ use serde::{Deserialize, Serialize};
 pub struct CashuMint {
     pub mint_url: String,
     pub active: i32,
+    pub nickname: Option<String>,
+    pub joined_at: Option<chrono::NaiveDateTime>,
+    pub last_used_at: Option<chrono::NaiveDateTime>,
+    pub notes: Option<String>,
+    /// The most the user wants to hold in this mint
+    pub max_balance_msats: Option<i64>,
 }
 
 impl CashuMint {
@@ -57,6 +63,50 @@ impl CashuMint {
         Ok(())
     }
 
+    pub fn set_nickname(
+        conn: &mut SqliteConnection,
+        url: String,
+        nickname: Option<String>,
+    ) -> anyhow::Result<()> {
+        diesel::update(cashu_mint::table)
+            .filter(cashu_mint::mint_url.eq(url))
+            .set(cashu_mint::nickname.eq(nickname))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn set_notes(
+        conn: &mut SqliteConnection,
+        url: String,
+        notes: Option<String>,
+    ) -> anyhow::Result<()> {
+        diesel::update(cashu_mint::table)
+            .filter(cashu_mint::mint_url.eq(url))
+            .set(cashu_mint::notes.eq(notes))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn set_max_balance(
+        conn: &mut SqliteConnection,
+        url: String,
+        max_balance_msats: Option<i64>,
+    ) -> anyhow::Result<()> {
+        diesel::update(cashu_mint::table)
+            .filter(cashu_mint::mint_url.eq(url))
+            .set(cashu_mint::max_balance_msats.eq(max_balance_msats))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn touch_last_used(conn: &mut SqliteConnection, url: String) -> anyhow::Result<()> {
+        diesel::update(cashu_mint::table)
+            .filter(cashu_mint::mint_url.eq(url))
+            .set(cashu_mint::last_used_at.eq(chrono::Utc::now().naive_utc()))
+            .execute(conn)?;
+        Ok(())
+    }
+
     pub fn get_mints(conn: &mut SqliteConnection) -> anyhow::Result<Vec<String>> {
         Ok(cashu_mint::table
             .filter(cashu_mint::active.eq(1))
@@ -90,6 +140,11 @@ impl CashuMint {
         let mint = Self {
             mint_url,
             active: 1,
+            nickname: None,
+            joined_at: Some(chrono::Utc::now().naive_utc()),
+            last_used_at: None,
+            notes: None,
+            max_balance_msats: None,
         };
 
         diesel::insert_into(cashu_mint::table)
@@ -0,0 +1,82 @@
+use crate::db_models::schema::{federation_preview, fedimint, mint_metadata};
+use diesel::prelude::*;
+
+/// The config of a federation that was previewed but not necessarily joined, so previewing it
+/// again doesn't have to wait on its guardians
+#[derive(QueryableByName, Queryable, Debug, Clone, PartialEq)]
+#[diesel(table_name = federation_preview)]
+pub struct FederationPreview {
+    pub id: String,
+    /// The consensus encoded client config
+    pub config: Vec<u8>,
+    pub fetched_at: chrono::NaiveDateTime,
+    /// Last time the preview was shown, pruning goes by this rather than when it was fetched
+    pub last_used_at: chrono::NaiveDateTime,
+}
+
+impl FederationPreview {
+    /// Looks up the preview and marks it as used
+    pub fn get(conn: &mut SqliteConnection, id: String) -> anyhow::Result<Option<Self>> {
+        diesel::update(federation_preview::table)
+            .filter(federation_preview::id.eq(&id))
+            .set(federation_preview::last_used_at.eq(chrono::Utc::now().naive_utc()))
+            .execute(conn)?;
+
+        Ok(federation_preview::table
+            .filter(federation_preview::id.eq(id))
+            .first::<Self>(conn)
+            .optional()?)
+    }
+
+    pub fn upsert(conn: &mut SqliteConnection, id: String, config: Vec<u8>) -> anyhow::Result<()> {
+        let now = chrono::Utc::now().naive_utc();
+        diesel::insert_into(federation_preview::table)
+            .values((
+                federation_preview::id.eq(&id),
+                federation_preview::config.eq(&config),
+                federation_preview::fetched_at.eq(now),
+                federation_preview::last_used_at.eq(now),
+            ))
+            .on_conflict(federation_preview::id)
+            .do_update()
+            .set((
+                federation_preview::config.eq(&config),
+                federation_preview::fetched_at.eq(now),
+                federation_preview::last_used_at.eq(now),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    pub fn remove(conn: &mut SqliteConnection, id: String) -> anyhow::Result<()> {
+        diesel::delete(federation_preview::table.filter(federation_preview::id.eq(id)))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Drops previews that haven't been shown since `cutoff`, along with the metadata of any
+    /// federation that is neither joined nor previewed anymore. Returns how many previews went.
+    pub fn prune(
+        conn: &mut SqliteConnection,
+        cutoff: chrono::NaiveDateTime,
+    ) -> anyhow::Result<usize> {
+        let pruned = diesel::delete(
+            federation_preview::table.filter(federation_preview::last_used_at.lt(cutoff)),
+        )
+        .execute(conn)?;
+
+        // Joined federations, archived ones included, keep their metadata
+        diesel::delete(
+            mint_metadata::table
+                .filter(mint_metadata::id.ne_all(fedimint::table.select(fedimint::id)))
+                .filter(
+                    mint_metadata::id
+                        .ne_all(federation_preview::table.select(federation_preview::id)),
+                ),
+        )
+        .execute(conn)?;
+
+        Ok(pruned)
+    }
+}
@@ -11,6 +11,14 @@ pub struct Fedimint {
     pub invite_code: String,
     pub value: Vec<u8>,
     pub active: i32,
+    pub nickname: Option<String>,
+    pub joined_at: Option<chrono::NaiveDateTime>,
+    pub last_used_at: Option<chrono::NaiveDateTime>,
+    /// Gateway id the user wants LN operations in this federation to go through
+    pub pinned_gateway: Option<String>,
+    pub notes: Option<String>,
+    /// The most the user wants to hold here, over whatever the federation recommends
+    pub max_balance_msats: Option<i64>,
 }
 
 impl Fedimint {
@@ -51,6 +59,13 @@ impl Fedimint {
         Ok(())
     }
 
+    /// Deletes the federation outright, only for undoing a join that never finished. A joined
+    /// federation is archived with [`Self::remove_federation`] instead, so its history stays.
+    pub fn delete(conn: &mut SqliteConnection, id: String) -> anyhow::Result<()> {
+        diesel::delete(fedimint::table.filter(fedimint::id.eq(id))).execute(conn)?;
+        Ok(())
+    }
+
     pub fn set_active(conn: &mut SqliteConnection, id: String) -> anyhow::Result<()> {
         diesel::update(fedimint::table)
             .filter(fedimint::id.eq(id))
@@ -77,6 +92,62 @@ impl Fedimint {
             .collect())
     }
 
+    pub fn set_nickname(
+        conn: &mut SqliteConnection,
+        id: String,
+        nickname: Option<String>,
+    ) -> anyhow::Result<()> {
+        diesel::update(fedimint::table)
+            .filter(fedimint::id.eq(id))
+            .set(fedimint::nickname.eq(nickname))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn set_notes(
+        conn: &mut SqliteConnection,
+        id: String,
+        notes: Option<String>,
+    ) -> anyhow::Result<()> {
+        diesel::update(fedimint::table)
+            .filter(fedimint::id.eq(id))
+            .set(fedimint::notes.eq(notes))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn set_max_balance(
+        conn: &mut SqliteConnection,
+        id: String,
+        max_balance_msats: Option<i64>,
+    ) -> anyhow::Result<()> {
+        diesel::update(fedimint::table)
+            .filter(fedimint::id.eq(id))
+            .set(fedimint::max_balance_msats.eq(max_balance_msats))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn set_pinned_gateway(
+        conn: &mut SqliteConnection,
+        id: String,
+        gateway: Option<String>,
+    ) -> anyhow::Result<()> {
+        diesel::update(fedimint::table)
+            .filter(fedimint::id.eq(id))
+            .set(fedimint::pinned_gateway.eq(gateway))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn touch_last_used(conn: &mut SqliteConnection, id: String) -> anyhow::Result<()> {
+        diesel::update(fedimint::table)
+            .filter(fedimint::id.eq(id))
+            .set(fedimint::last_used_at.eq(chrono::Utc::now().naive_utc()))
+            .execute(conn)?;
+        Ok(())
+    }
+
     pub fn update_value(
         conn: &mut SqliteConnection,
         id: String,
@@ -106,6 +177,12 @@ impl From<&NewFedimint> for Fedimint {
             invite_code: new_fedimint.invite_code.clone(),
             value: new_fedimint.value.clone(),
             active: 1,
+            nickname: None,
+            joined_at: None,
+            last_used_at: None,
+            pinned_gateway: None,
+            notes: None,
+            max_balance_msats: None,
         }
     }
 }
@@ -116,6 +193,14 @@ impl NewFedimint {
             .values(self)
             .execute(conn)?;
 
-        Ok(self.into())
+        let joined_at = chrono::Utc::now().naive_utc();
+        diesel::update(fedimint::table)
+            .filter(fedimint::id.eq(&self.id))
+            .set(fedimint::joined_at.eq(joined_at))
+            .execute(conn)?;
+
+        let mut fedimint: Fedimint = self.into();
+        fedimint.joined_at = Some(joined_at);
+        Ok(fedimint)
     }
 }
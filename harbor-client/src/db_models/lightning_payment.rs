@@ -183,10 +183,24 @@ impl LightningPayment {
         Ok(())
     }
 
-    pub fn get_history(conn: &mut SqliteConnection) -> anyhow::Result<Vec<Self>> {
-        Ok(lightning_payments::table
+    pub fn get_history(
+        conn: &mut SqliteConnection,
+        mint: Option<&MintIdentifier>,
+    ) -> anyhow::Result<Vec<Self>> {
+        let mut query = lightning_payments::table
             .filter(lightning_payments::status.eq(PaymentStatus::Success as i32))
-            .load::<Self>(conn)?)
+            .into_boxed();
+        // Only the mint's own rows when filtering, everything otherwise
+        match mint {
+            Some(MintIdentifier::Fedimint(id)) => {
+                query = query.filter(lightning_payments::fedimint_id.eq(id.to_string()));
+            }
+            Some(MintIdentifier::Cashu(url)) => {
+                query = query.filter(lightning_payments::cashu_mint_url.eq(url.to_string()));
+            }
+            None => {}
+        }
+        Ok(query.load::<Self>(conn)?)
     }
 
     pub fn get_pending(conn: &mut SqliteConnection) -> anyhow::Result<Vec<Self>> {
@@ -154,10 +154,23 @@ impl LightningReceive {
         Ok(())
     }
 
-    pub fn get_history(conn: &mut SqliteConnection) -> anyhow::Result<Vec<Self>> {
-        Ok(lightning_receives::table
+    pub fn get_history(
+        conn: &mut SqliteConnection,
+        mint: Option<&MintIdentifier>,
+    ) -> anyhow::Result<Vec<Self>> {
+        let mut query = lightning_receives::table
             .filter(lightning_receives::status.eq(PaymentStatus::Success as i32))
-            .load::<Self>(conn)?)
+            .into_boxed();
+        match mint {
+            Some(MintIdentifier::Fedimint(id)) => {
+                query = query.filter(lightning_receives::fedimint_id.eq(id.to_string()));
+            }
+            Some(MintIdentifier::Cashu(url)) => {
+                query = query.filter(lightning_receives::cashu_mint_url.eq(url.to_string()));
+            }
+            None => {}
+        }
+        Ok(query.load::<Self>(conn)?)
     }
 
     pub fn get_pending(conn: &mut SqliteConnection) -> anyhow::Result<Vec<Self>> {
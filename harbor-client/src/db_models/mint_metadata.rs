@@ -17,10 +17,16 @@ pub struct MintMetadata {
     pub popup_countdown_message: Option<String>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
+    pub icon_url: Option<String>,
+    pub site_url: Option<String>,
+    pub max_balance_msats: Option<i64>,
 }
 
 impl MintMetadata {
     pub fn from(id: FederationId, meta: FederationMeta) -> Self {
+        let max_balance_msats = meta
+            .max_balance()
+            .and_then(|max| i64::try_from(max.msats).ok());
         Self {
             id: id.to_string(),
             federation_expiry_timestamp: meta
@@ -35,6 +41,9 @@ impl MintMetadata {
             popup_countdown_message: meta.popup_countdown_message,
             created_at: Default::default(),
             updated_at: Default::default(),
+            max_balance_msats,
+            icon_url: meta.federation_icon_url,
+            site_url: meta.federation_site_url,
         }
     }
 
@@ -55,6 +64,9 @@ impl MintMetadata {
                 mint_metadata::preview_message.eq(&self.preview_message),
                 mint_metadata::popup_end_timestamp.eq(&self.popup_end_timestamp),
                 mint_metadata::popup_countdown_message.eq(&self.popup_countdown_message),
+                mint_metadata::icon_url.eq(&self.icon_url),
+                mint_metadata::site_url.eq(&self.site_url),
+                mint_metadata::max_balance_msats.eq(&self.max_balance_msats),
             ))
             .on_conflict(mint_metadata::id)
             .do_update()
@@ -65,6 +77,9 @@ impl MintMetadata {
                 mint_metadata::preview_message.eq(&self.preview_message),
                 mint_metadata::popup_end_timestamp.eq(&self.popup_end_timestamp),
                 mint_metadata::popup_countdown_message.eq(&self.popup_countdown_message),
+                mint_metadata::icon_url.eq(&self.icon_url),
+                mint_metadata::site_url.eq(&self.site_url),
+                mint_metadata::max_balance_msats.eq(&self.max_balance_msats),
             ))
             .execute(conn)?;
 
@@ -79,11 +94,13 @@ impl From<MintMetadata> for FederationMeta {
             federation_expiry_timestamp: value.federation_expiry_timestamp.map(|f| f.to_string()),
             welcome_message: value.welcome_message,
             vetted_gateways: None,
-            federation_icon_url: None,
+            federation_icon_url: value.icon_url,
+            federation_site_url: value.site_url,
             meta_external_url: None,
             preview_message: value.preview_message,
             popup_end_timestamp: value.popup_end_timestamp.map(|f| f.to_string()),
             popup_countdown_message: value.popup_countdown_message,
+            max_balance_msats: value.max_balance_msats.map(|max| max.to_string()),
         }
     }
 }
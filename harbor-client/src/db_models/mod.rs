@@ -21,24 +21,195 @@ pub use onchain_receive::*;
 
 pub(crate) mod schema;
 
+pub mod federation_preview;
 pub mod mint_metadata;
+pub mod operation_record;
 pub mod transaction_item;
 
 use crate::MintIdentifier;
+use crate::db_models::transaction_item::{FeeSummary, TransactionDirection, TransactionItem};
 use crate::metadata::FederationMeta;
 use fedimint_core::config::FederationId;
 use fedimint_core::core::ModuleKind;
+use fedimint_core::invite_code::InviteCode;
+use fedimint_core::{Amount, PeerId};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardianHealth {
+    pub peer_id: PeerId,
+    pub name: String,
+    pub online: bool,
+    /// Host of the guardian's API endpoint, from the federation config
+    pub endpoint_host: String,
+    pub onion: bool,
+    /// Unix timestamp of the last probe that got an answer
+    pub last_contact: Option<u64>,
+    /// The fedimintd version the guardian reports, if it answers that at all
+    pub version: Option<String>,
+    /// Why the latest probe failed, `None` while the guardian is online
+    pub last_error: Option<String>,
+}
+
+/// A lightning gateway registered with a federation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GatewayInfo {
+    pub gateway_id: String,
+    pub alias: String,
+    pub base_msat: u32,
+    pub proportional_millionths: u32,
+    pub vetted: bool,
+    pub supports_private_payments: bool,
+    /// Unix timestamp of when the registration lapses unless the gateway checks in again
+    pub registered_until: u64,
+}
+
+/// A federation's session count as seen by this client, the closest thing to a heartbeat
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionProgress {
+    pub session_count: u64,
+    /// Unix timestamp of when the count was last seen going up, `None` if it hasn't yet
+    pub last_progress_at: Option<u64>,
+    /// Unix timestamp of the first observation
+    pub watching_since: u64,
+}
+
+impl SessionProgress {
+    /// Whether nothing has moved for longer than `window_secs`, as of `now`
+    pub fn is_stale(&self, now: u64, window_secs: u64) -> bool {
+        let since = self.last_progress_at.unwrap_or(self.watching_since);
+        now.saturating_sub(since) > window_secs
+    }
+}
+
+/// Where an active federation's client is in opening after the wallet was unlocked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FederationInitState {
+    Opening,
+    Ready,
+    Failed(String),
+}
+
+/// Where a federation is in restoring its ecash after the wallet was recovered from seed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryState {
+    /// Recovery is needed but the modules haven't reported any progress yet
+    NotStarted,
+    /// `complete` out of `total` sessions scanned, summed across modules
+    Scanning {
+        complete: u32,
+        total: u32,
+    },
+    Complete,
+    Failed(String),
+}
+
+impl RecoveryState {
+    /// How far along the scan is, from 0 to 1
+    // Only ever shown as a percentage, so the precision f32 drops doesn't matter
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fraction(&self) -> f32 {
+        match self {
+            Self::NotStarted | Self::Failed(_) | Self::Scanning { total: 0, .. } => 0.0,
+            Self::Scanning { complete, total } => (*complete as f32 / *total as f32).min(1.0),
+            Self::Complete => 1.0,
+        }
+    }
+
+    /// The balance isn't known until the scan is done, so nothing can be sent before then
+    pub const fn blocks_sending(&self) -> bool {
+        !matches!(self, Self::Complete)
+    }
+}
+
+/// Fees a mint charges by its own config, on top of whatever a gateway or the network takes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub peg_in_msats: Option<u64>,
+    pub peg_out_msats: Option<u64>,
+    /// Charged by the federation for funding and claiming lightning contracts
+    pub lightning_contract_input_msats: Option<u64>,
+    pub lightning_contract_output_msats: Option<u64>,
+    /// Cashu's fee per 1000 proofs spent, from the active keyset
+    pub input_fee_ppk: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MintFees {
+    /// `None` when the mint doesn't publish one we can read
+    pub schedule: Option<FeeSchedule>,
+    pub paid: FeeSummary,
+}
+
+/// What paying one invoice would cost from one mint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendFeeEstimate {
+    pub mint: MintIdentifier,
+    /// The fee on top of the invoice amount, or why it couldn't be worked out
+    pub fee: Result<Amount, String>,
+    /// The mint's balance covers the invoice and the fee
+    pub sufficient_balance: bool,
+}
+
+impl SendFeeEstimate {
+    /// The cheapest of `estimates` from a mint that can afford it
+    pub fn cheapest(estimates: &[Self]) -> Option<&Self> {
+        estimates
+            .iter()
+            .filter(|estimate| estimate.sufficient_balance)
+            .filter_map(|estimate| estimate.fee.as_ref().ok().map(|fee| (*fee, estimate)))
+            .min_by_key(|(fee, _)| *fee)
+            .map(|(_, estimate)| estimate)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleVersion {
+    pub kind: ModuleKind,
+    pub version: String,
+}
+
+/// Versions a federation is running, as of the last time its guardians were asked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FederationVersions {
+    pub consensus_version: String,
+    pub modules: Vec<ModuleVersion>,
+    /// The federation runs a newer consensus version than this build of Harbor was made for
+    pub consensus_unsupported: bool,
+    /// False when the guardians couldn't be reached and the config from joining was used instead
+    pub fresh: bool,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MintItem {
     pub id: MintIdentifier,
     pub name: String,
+    /// User-defined name that takes precedence over the announced one
+    pub nickname: Option<String>,
+    pub invite_code: Option<InviteCode>,
     pub balance: u64,
     pub guardians: Option<Vec<String>>,
+    /// Result of the last guardian probe, `None` until one has finished
+    pub guardian_health: Option<Vec<GuardianHealth>>,
     pub module_kinds: Option<Vec<ModuleKind>>,
     pub metadata: FederationMeta,
     pub on_chain_supported: bool,
     pub active: bool,
+    /// The balance is still being worked out, e.g. ecash is being restored
+    pub syncing: bool,
+    /// Unix timestamp of when the mint was first joined, if known
+    pub joined_at: Option<u64>,
+    /// Unix timestamp of the last send or receive through the mint, if any
+    pub last_used_at: Option<u64>,
+    /// The user's own notes about the mint
+    pub notes: Option<String>,
+    /// The most the user wants to hold in the mint, in place of what it recommends
+    pub max_balance_override: Option<Amount>,
+    /// Where the mint's icon has been downloaded to, if it has one
+    pub icon: Option<PathBuf>,
+    /// What's on its way in or out and not part of `balance` yet
+    pub pending: PendingAmounts,
 }
 
 impl MintItem {
@@ -46,13 +217,147 @@ impl MintItem {
         Self {
             id: MintIdentifier::Fedimint(id),
             name: "Unknown".to_string(),
+            nickname: None,
+            invite_code: None,
             balance: 0,
             guardians: None,
+            guardian_health: None,
             module_kinds: None,
             metadata: FederationMeta::default(),
             on_chain_supported: false,
             active: true,
+            syncing: false,
+            joined_at: None,
+            last_used_at: None,
+            notes: None,
+            max_balance_override: None,
+            icon: None,
+            pending: PendingAmounts::default(),
+        }
+    }
+
+    /// The name to show the user, preferring their nickname if they set one
+    pub fn display_name(&self) -> &str {
+        self.nickname.as_deref().unwrap_or(&self.name)
+    }
+
+    /// The most to hold in the mint, the user's own cap if they set one, otherwise what the
+    /// federation recommends
+    pub fn max_balance(&self) -> Option<Amount> {
+        self.max_balance_override
+            .or_else(|| self.metadata.max_balance())
+    }
+
+    /// Whether the balance is over [`Self::max_balance`]
+    pub fn over_max_balance(&self) -> bool {
+        self.max_balance()
+            .is_some_and(|max| Amount::from_sats(self.balance) > max)
+    }
+
+    /// When anything last happened in the mint, its last send or receive, or when it was
+    /// joined if there hasn't been either
+    pub fn last_activity(&self) -> Option<u64> {
+        self.last_used_at.or(self.joined_at)
+    }
+
+    /// Whole days the mint has sat empty and unused as of `now`. `None` while it holds or is
+    /// expecting anything, or when there's no record of its last activity.
+    pub fn idle_empty_days(&self, now: u64) -> Option<u64> {
+        if self.balance > 0 || !self.pending.is_empty() || self.syncing {
+            return None;
+        }
+        self.last_activity()
+            .map(|at| now.saturating_sub(at) / (24 * 60 * 60))
+    }
+
+    /// What the mint can be used for, going by the modules its federation runs. They're
+    /// read from the client's config each time the list is built, so a resync that picks up
+    /// a changed module set shows here on the next refresh.
+    pub fn capabilities(&self) -> MintCapabilities {
+        // Cashu mints are lightning only, a federation whose client isn't open yet gets the
+        // benefit of the doubt
+        let everything = MintCapabilities {
+            lightning: true,
+            on_chain: true,
+            ecash: true,
+        };
+        let modules = match (&self.id, &self.module_kinds) {
+            (MintIdentifier::Cashu(_), _) => {
+                return MintCapabilities {
+                    on_chain: false,
+                    ..everything
+                };
+            }
+            (MintIdentifier::Fedimint(_), None) => return everything,
+            (MintIdentifier::Fedimint(_), Some(modules)) => modules,
+        };
+        let has = |kinds: &[&str]| modules.iter().any(|m| kinds.contains(&m.as_str()));
+        MintCapabilities {
+            lightning: has(&["ln", "lnv2"]),
+            on_chain: has(&["wallet"]),
+            ecash: has(&["mint"]),
+        }
+    }
+}
+
+/// What a mint supports, from the modules its federation runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MintCapabilities {
+    pub lightning: bool,
+    pub on_chain: bool,
+    /// Holding and spending ecash, which every mint that works at all has
+    pub ecash: bool,
+}
+
+impl MintCapabilities {
+    /// Whether funds can come into the mint, over lightning or on-chain
+    pub const fn can_receive(&self) -> bool {
+        self.lightning || self.on_chain
+    }
+
+    /// Whether funds can leave the mint, by paying an invoice or an on-chain address
+    pub const fn can_send(&self) -> bool {
+        self.lightning || self.on_chain
+    }
+}
+
+/// Money moving in or out of a mint that hasn't settled yet, in sats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingAmounts {
+    /// Deposits that have been seen but can't be spent yet
+    pub incoming: u64,
+    /// Payments, fees included, that have left the balance but haven't finished. They come
+    /// back to the balance if they fail.
+    pub outgoing: u64,
+}
+
+impl PendingAmounts {
+    pub const fn is_empty(&self) -> bool {
+        self.incoming == 0 && self.outgoing == 0
+    }
+
+    /// Adds up what's still in flight for each mint. An unpaid invoice or an address nothing
+    /// has been sent to isn't money on its way, so incoming only counts what's been seen
+    /// arriving and is waiting for confirmations.
+    pub fn tally(items: &[TransactionItem]) -> HashMap<MintIdentifier, Self> {
+        let mut pending: HashMap<MintIdentifier, Self> = HashMap::new();
+        for item in items {
+            match (item.direction, item.status) {
+                (TransactionDirection::Incoming, PaymentStatus::WaitingConfirmation) => {
+                    let amounts = pending.entry(item.mint_identifier.clone()).or_default();
+                    amounts.incoming += item.amount;
+                }
+                (
+                    TransactionDirection::Outgoing,
+                    PaymentStatus::Pending | PaymentStatus::WaitingConfirmation,
+                ) => {
+                    let amounts = pending.entry(item.mint_identifier.clone()).or_default();
+                    amounts.outgoing += item.amount + item.fee_msats / 1_000;
+                }
+                _ => {}
+            }
         }
+        pending
     }
 }
 
@@ -141,10 +141,23 @@ impl OnChainPayment {
         Ok(())
     }
 
-    pub fn get_history(conn: &mut SqliteConnection) -> anyhow::Result<Vec<Self>> {
-        Ok(on_chain_payments::table
+    pub fn get_history(
+        conn: &mut SqliteConnection,
+        mint: Option<&MintIdentifier>,
+    ) -> anyhow::Result<Vec<Self>> {
+        let mut query = on_chain_payments::table
             .filter(on_chain_payments::status.eq(PaymentStatus::Success as i32))
-            .load::<Self>(conn)?)
+            .into_boxed();
+        match mint {
+            Some(MintIdentifier::Fedimint(id)) => {
+                query = query.filter(on_chain_payments::fedimint_id.eq(id.to_string()));
+            }
+            Some(MintIdentifier::Cashu(url)) => {
+                query = query.filter(on_chain_payments::cashu_mint_url.eq(url.to_string()));
+            }
+            None => {}
+        }
+        Ok(query.load::<Self>(conn)?)
     }
 
     pub fn get_pending(conn: &mut SqliteConnection) -> anyhow::Result<Vec<Self>> {
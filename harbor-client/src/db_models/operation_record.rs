@@ -0,0 +1,147 @@
+use crate::db_models::schema::operation_record;
+use crate::mint_error::AddMintErrorKind;
+use crate::operations::OperationKind;
+use diesel::prelude::*;
+
+/// How many operations the activity log keeps, the oldest go as new ones start
+pub const MAX_OPERATION_RECORDS: i64 = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperationOutcome {
+    /// Still going
+    Running = 0,
+    Succeeded = 1,
+    Failed = 2,
+    Cancelled = 3,
+    /// Was still running when Harbor last closed
+    Interrupted = 4,
+}
+
+impl OperationOutcome {
+    pub const ALL: [Self; 5] = [
+        Self::Running,
+        Self::Succeeded,
+        Self::Failed,
+        Self::Cancelled,
+        Self::Interrupted,
+    ];
+
+    pub fn from_i32(outcome: i32) -> Self {
+        match outcome {
+            0 => Self::Running,
+            1 => Self::Succeeded,
+            2 => Self::Failed,
+            3 => Self::Cancelled,
+            4 => Self::Interrupted,
+            _ => panic!("invalid operation outcome"),
+        }
+    }
+}
+
+impl std::fmt::Display for OperationOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Running => write!(f, "Running"),
+            Self::Succeeded => write!(f, "Succeeded"),
+            Self::Failed => write!(f, "Failed"),
+            Self::Cancelled => write!(f, "Cancelled"),
+            Self::Interrupted => write!(f, "Interrupted"),
+        }
+    }
+}
+
+/// A preview, join or resync, kept after it finishes so there's a record of what happened
+#[derive(QueryableByName, Queryable, Debug, Clone, PartialEq)]
+#[diesel(table_name = operation_record)]
+pub struct OperationRecord {
+    pub id: String,
+    kind: i32,
+    /// What the operation was started with, the invite code or mint URL for previews and
+    /// joins and the mint for a resync
+    pub target: String,
+    /// The federation ID or mint URL, for showing
+    pub mint: String,
+    pub started_at: chrono::NaiveDateTime,
+    pub ended_at: Option<chrono::NaiveDateTime>,
+    outcome: i32,
+    error_kind: Option<String>,
+}
+
+impl OperationRecord {
+    pub fn kind(&self) -> OperationKind {
+        OperationKind::from_i32(self.kind)
+    }
+
+    pub fn outcome(&self) -> OperationOutcome {
+        OperationOutcome::from_i32(self.outcome)
+    }
+
+    pub fn error_kind(&self) -> Option<AddMintErrorKind> {
+        self.error_kind.as_deref().map(AddMintErrorKind::from_code)
+    }
+
+    /// Records an operation as running, dropping the oldest records past
+    /// [`MAX_OPERATION_RECORDS`]
+    pub fn start(
+        conn: &mut SqliteConnection,
+        id: String,
+        kind: OperationKind,
+        target: String,
+        mint: String,
+    ) -> anyhow::Result<()> {
+        diesel::insert_into(operation_record::table)
+            .values((
+                operation_record::id.eq(id),
+                operation_record::kind.eq(kind as i32),
+                operation_record::target.eq(target),
+                operation_record::mint.eq(mint),
+                operation_record::started_at.eq(chrono::Utc::now().naive_utc()),
+                operation_record::outcome.eq(OperationOutcome::Running as i32),
+            ))
+            .execute(conn)?;
+
+        let keep: Vec<String> = operation_record::table
+            .select(operation_record::id)
+            .order(operation_record::started_at.desc())
+            .limit(MAX_OPERATION_RECORDS)
+            .load(conn)?;
+        diesel::delete(operation_record::table.filter(operation_record::id.ne_all(keep)))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    pub fn finish(
+        conn: &mut SqliteConnection,
+        id: String,
+        outcome: OperationOutcome,
+        error_kind: Option<AddMintErrorKind>,
+    ) -> anyhow::Result<()> {
+        diesel::update(operation_record::table)
+            .filter(operation_record::id.eq(id))
+            .set((
+                operation_record::ended_at.eq(chrono::Utc::now().naive_utc()),
+                operation_record::outcome.eq(outcome as i32),
+                operation_record::error_kind.eq(error_kind.map(AddMintErrorKind::code)),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    /// Marks everything still running as interrupted, for startup when nothing can be.
+    /// Returns how many there were.
+    pub fn interrupt_running(conn: &mut SqliteConnection) -> anyhow::Result<usize> {
+        Ok(diesel::update(operation_record::table)
+            .filter(operation_record::outcome.eq(OperationOutcome::Running as i32))
+            .set(operation_record::outcome.eq(OperationOutcome::Interrupted as i32))
+            .execute(conn)?)
+    }
+
+    /// Every record, newest first
+    pub fn list(conn: &mut SqliteConnection) -> anyhow::Result<Vec<Self>> {
+        Ok(operation_record::table
+            .order(operation_record::started_at.desc())
+            .load::<Self>(conn)?)
+    }
+}
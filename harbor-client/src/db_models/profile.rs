@@ -1,4 +1,5 @@
 use crate::db_models::schema::profile;
+use crate::nostr::{NostrIdentity, NostrKeySource, NostrPublicIdentity};
 use bip39::Mnemonic;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -13,6 +14,10 @@ pub struct Profile {
     pub seed_words: String,
     onchain_receive_enabled: i32,
     tor_enabled: i32,
+    /// Hex, kept here so it's encrypted along with the seed
+    nostr_secret_key: Option<String>,
+    /// Whether the key above was derived from the seed rather than generated or imported
+    nostr_key_derived: i32,
 }
 
 impl Profile {
@@ -48,6 +53,41 @@ impl Profile {
         Ok(())
     }
 
+    pub fn set_nostr_identity(
+        conn: &mut SqliteConnection,
+        identity: Option<(&NostrIdentity, NostrKeySource)>,
+    ) -> anyhow::Result<()> {
+        let derived = identity.is_some_and(|(_, source)| source == NostrKeySource::Seed);
+        diesel::update(profile::table)
+            .set((
+                profile::nostr_secret_key.eq(identity.map(|(identity, _)| identity.to_hex())),
+                profile::nostr_key_derived.eq(i32::from(derived)),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn nostr_identity(&self) -> Option<NostrIdentity> {
+        self.nostr_secret_key
+            .as_deref()
+            .and_then(|hex| NostrIdentity::from_hex(hex).ok())
+    }
+
+    pub fn nostr_key_source(&self) -> NostrKeySource {
+        if self.nostr_key_derived == 1 {
+            NostrKeySource::Seed
+        } else {
+            NostrKeySource::Separate
+        }
+    }
+
+    pub fn nostr_public_identity(&self) -> Option<NostrPublicIdentity> {
+        self.nostr_identity().map(|identity| NostrPublicIdentity {
+            npub: identity.npub(),
+            source: self.nostr_key_source(),
+        })
+    }
+
     pub fn mnemonic(&self) -> Mnemonic {
         Mnemonic::from_str(self.seed_words.as_str()).expect("valid mnemonic")
     }
@@ -71,6 +111,8 @@ impl From<&NewProfile> for Profile {
             seed_words: new_profile.seed_words.clone(),
             onchain_receive_enabled: 0,
             tor_enabled: 1,
+            nostr_secret_key: None,
+            nostr_key_derived: 0,
         }
     }
 }
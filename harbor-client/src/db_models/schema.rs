@@ -4,6 +4,11 @@ diesel::table! {
     cashu_mint (mint_url) {
         mint_url -> Text,
         active -> Integer,
+        nickname -> Nullable<Text>,
+        joined_at -> Nullable<Timestamp>,
+        last_used_at -> Nullable<Timestamp>,
+        notes -> Nullable<Text>,
+        max_balance_msats -> Nullable<BigInt>,
     }
 }
 
@@ -13,6 +18,21 @@ diesel::table! {
         invite_code -> Text,
         value -> Binary,
         active -> Integer,
+        nickname -> Nullable<Text>,
+        joined_at -> Nullable<Timestamp>,
+        last_used_at -> Nullable<Timestamp>,
+        pinned_gateway -> Nullable<Text>,
+        notes -> Nullable<Text>,
+        max_balance_msats -> Nullable<BigInt>,
+    }
+}
+
+diesel::table! {
+    federation_preview (id) {
+        id -> Text,
+        config -> Binary,
+        fetched_at -> Timestamp,
+        last_used_at -> Timestamp,
     }
 }
 
@@ -58,6 +78,9 @@ diesel::table! {
         popup_countdown_message -> Nullable<Text>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        icon_url -> Nullable<Text>,
+        site_url -> Nullable<Text>,
+        max_balance_msats -> Nullable<BigInt>,
     }
 }
 
@@ -91,12 +114,27 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    operation_record (id) {
+        id -> Text,
+        kind -> Integer,
+        target -> Text,
+        mint -> Text,
+        started_at -> Timestamp,
+        ended_at -> Nullable<Timestamp>,
+        outcome -> Integer,
+        error_kind -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     profile (id) {
         id -> Text,
         seed_words -> Text,
         onchain_receive_enabled -> Integer,
         tor_enabled -> Integer,
+        nostr_secret_key -> Nullable<Text>,
+        nostr_key_derived -> Integer,
     }
 }
 
@@ -111,11 +149,13 @@ diesel::joinable!(on_chain_receives -> fedimint (fedimint_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     cashu_mint,
+    federation_preview,
     fedimint,
     lightning_payments,
     lightning_receives,
     mint_metadata,
     on_chain_payments,
     on_chain_receives,
+    operation_record,
     profile,
 );
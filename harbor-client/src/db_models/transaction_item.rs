@@ -3,6 +3,15 @@ use crate::db_models::PaymentStatus;
 use bitcoin::Txid;
 use bitcoin::hashes::Hash;
 use fedimint_core::config::FederationId;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+const SEVEN_DAYS_SECS: u64 = 7 * DAY_SECS;
+const THIRTY_DAYS_SECS: u64 = 30 * DAY_SECS;
+
+/// How many daily points [`MintStats::balance_history`] has, today included
+pub const BALANCE_HISTORY_DAYS: u64 = 30;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransactionItemKind {
@@ -58,3 +67,336 @@ impl TransactionItem {
         }
     }
 }
+
+/// Fees paid, split by the kind of transaction they were paid on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeTotals {
+    pub lightning_msats: u64,
+    pub onchain_msats: u64,
+}
+
+impl FeeTotals {
+    fn add(&mut self, item: &TransactionItem) {
+        match item.kind {
+            TransactionItemKind::Lightning => self.lightning_msats += item.fee_msats,
+            TransactionItemKind::Onchain => self.onchain_msats += item.fee_msats,
+        }
+    }
+}
+
+/// What a mint has cost in fees, according to local history
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeSummary {
+    pub all_time: FeeTotals,
+    pub last_30_days: FeeTotals,
+}
+
+impl FeeSummary {
+    /// Sums the fees of every successful transaction made through `mint`, as of `now` (unix seconds)
+    pub fn from_history(history: &[TransactionItem], mint: &MintIdentifier, now: u64) -> Self {
+        let cutoff = now.saturating_sub(THIRTY_DAYS_SECS);
+        history
+            .iter()
+            // Failed and pending transactions never actually charged anything
+            .filter(|item| &item.mint_identifier == mint && item.status == PaymentStatus::Success)
+            .fold(Self::default(), |mut summary, item| {
+                summary.all_time.add(item);
+                if item.timestamp >= cutoff {
+                    summary.last_30_days.add(item);
+                }
+                summary
+            })
+    }
+}
+
+/// What went in and out of a mint over some stretch of time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlowTotals {
+    pub received_sats: u64,
+    pub sent_sats: u64,
+    pub fees_msats: u64,
+    pub count: usize,
+}
+
+impl FlowTotals {
+    fn add(&mut self, item: &TransactionItem) {
+        match item.direction {
+            TransactionDirection::Incoming => self.received_sats += item.amount,
+            TransactionDirection::Outgoing => self.sent_sats += item.amount,
+        }
+        self.fees_msats += item.fee_msats;
+        self.count += 1;
+    }
+}
+
+/// A mint's activity according to local history
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MintStats {
+    pub last_7_days: FlowTotals,
+    pub last_30_days: FlowTotals,
+    pub all_time: FlowTotals,
+    /// The balance in sats at each of the last [`BALANCE_HISTORY_DAYS`] days, oldest first and
+    /// ending with now
+    pub balance_history: Vec<u64>,
+}
+
+impl MintStats {
+    /// Adds up every successful transaction made through `mint` as of `now` (unix seconds).
+    /// The balance history is worked back from the current `balance` by undoing each
+    /// transaction in turn, so anything history doesn't know about, like ecash restored in a
+    /// recovery, shows up as if it had always been there.
+    pub fn from_history(
+        history: &[TransactionItem],
+        mint: &MintIdentifier,
+        balance: u64,
+        now: u64,
+    ) -> Self {
+        let mut items: Vec<&TransactionItem> = history
+            .iter()
+            .filter(|item| &item.mint_identifier == mint && item.status == PaymentStatus::Success)
+            .collect();
+
+        let mut stats = Self::default();
+        for item in &items {
+            stats.all_time.add(item);
+            if item.timestamp >= now.saturating_sub(THIRTY_DAYS_SECS) {
+                stats.last_30_days.add(item);
+            }
+            if item.timestamp >= now.saturating_sub(SEVEN_DAYS_SECS) {
+                stats.last_7_days.add(item);
+            }
+        }
+
+        items.sort_by_key(|item| Reverse(item.timestamp));
+        let mut newest_first = items.into_iter().peekable();
+        let mut at = balance;
+        stats.balance_history = (0..BALANCE_HISTORY_DAYS)
+            .map(|days_ago| {
+                let point = now.saturating_sub(days_ago * DAY_SECS);
+                while let Some(item) = newest_first.next_if(|item| item.timestamp > point) {
+                    at = match item.direction {
+                        TransactionDirection::Incoming => at.saturating_sub(item.amount),
+                        TransactionDirection::Outgoing => at + item.amount + item.fee_msats / 1_000,
+                    };
+                }
+                at
+            })
+            .collect();
+        stats.balance_history.reverse();
+        stats
+    }
+}
+
+// What a cached entry was worked out from, it's out of date once any of it changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StatsKey {
+    entries: usize,
+    newest: u64,
+    balance: u64,
+    day: u64,
+}
+
+impl StatsKey {
+    fn new(history: &[TransactionItem], mint: &MintIdentifier, balance: u64, now: u64) -> Self {
+        let (entries, newest) = history
+            .iter()
+            .filter(|item| &item.mint_identifier == mint)
+            .fold((0, 0), |(entries, newest), item| {
+                (entries + 1, newest.max(item.timestamp))
+            });
+        Self {
+            entries,
+            newest,
+            balance,
+            day: now / DAY_SECS,
+        }
+    }
+}
+
+/// [`MintStats`] for each mint, only worked out again when that mint's history or balance
+/// changes, or a new day moves the windows along
+#[derive(Debug, Clone, Default)]
+pub struct MintStatsCache {
+    stats: HashMap<MintIdentifier, (StatsKey, MintStats)>,
+}
+
+impl MintStatsCache {
+    /// Brings `mint`'s stats up to date, returning whether they had to be worked out again
+    pub fn update(
+        &mut self,
+        history: &[TransactionItem],
+        mint: &MintIdentifier,
+        balance: u64,
+        now: u64,
+    ) -> bool {
+        let key = StatsKey::new(history, mint, balance, now);
+        if self
+            .stats
+            .get(mint)
+            .is_some_and(|(cached, _)| *cached == key)
+        {
+            return false;
+        }
+        let stats = MintStats::from_history(history, mint, balance, now);
+        self.stats.insert(mint.clone(), (key, stats));
+        true
+    }
+
+    pub fn get(&self, mint: &MintIdentifier) -> Option<&MintStats> {
+        self.stats.get(mint).map(|(_, stats)| stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cdk::mint_url::MintUrl;
+    use std::str::FromStr;
+
+    const NOW: u64 = 1_750_000_000;
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn item(kind: TransactionItemKind, fee_msats: u64, days_ago: u64) -> TransactionItem {
+        TransactionItem {
+            kind,
+            fee_msats,
+            timestamp: NOW - days_ago * DAY,
+            ..TransactionItem::make_dummy()
+        }
+    }
+
+    #[test]
+    fn test_fee_summary() {
+        let fedimint = MintIdentifier::Fedimint(FederationId::dummy());
+        let cashu = MintIdentifier::Cashu(MintUrl::from_str("https://mint.example.com").unwrap());
+
+        let history = vec![
+            item(TransactionItemKind::Lightning, 1_000, 1),
+            item(TransactionItemKind::Lightning, 2_000, 29),
+            item(TransactionItemKind::Lightning, 4_000, 31),
+            item(TransactionItemKind::Onchain, 10_000, 2),
+            item(TransactionItemKind::Onchain, 20_000, 90),
+            // Doesn't count, it never went through
+            TransactionItem {
+                status: PaymentStatus::Failed,
+                ..item(TransactionItemKind::Lightning, 8_000, 1)
+            },
+            // Doesn't count, it was paid through another mint
+            TransactionItem {
+                mint_identifier: cashu.clone(),
+                ..item(TransactionItemKind::Lightning, 16_000, 1)
+            },
+        ];
+
+        let summary = FeeSummary::from_history(&history, &fedimint, NOW);
+        assert_eq!(
+            summary.all_time,
+            FeeTotals {
+                lightning_msats: 7_000,
+                onchain_msats: 30_000,
+            }
+        );
+        assert_eq!(
+            summary.last_30_days,
+            FeeTotals {
+                lightning_msats: 3_000,
+                onchain_msats: 10_000,
+            }
+        );
+
+        let summary = FeeSummary::from_history(&history, &cashu, NOW);
+        assert_eq!(summary.all_time.lightning_msats, 16_000);
+        assert_eq!(summary.last_30_days.lightning_msats, 16_000);
+
+        assert_eq!(
+            FeeSummary::from_history(&[], &fedimint, NOW),
+            FeeSummary::default()
+        );
+    }
+
+    fn flow(direction: TransactionDirection, amount: u64, days_ago: u64) -> TransactionItem {
+        TransactionItem {
+            direction,
+            amount,
+            fee_msats: 2_000,
+            timestamp: NOW - days_ago * DAY - 60,
+            ..TransactionItem::make_dummy()
+        }
+    }
+
+    #[test]
+    fn test_mint_stats() {
+        let fedimint = MintIdentifier::Fedimint(FederationId::dummy());
+        let history = vec![
+            flow(TransactionDirection::Incoming, 10_000, 40),
+            flow(TransactionDirection::Incoming, 5_000, 10),
+            flow(TransactionDirection::Outgoing, 3_000, 2),
+            // Never went through, so it's left out of everything
+            TransactionItem {
+                status: PaymentStatus::Failed,
+                ..flow(TransactionDirection::Outgoing, 1_000, 1)
+            },
+        ];
+        // 10,000 + 5,000 - 3,000 - 2 sats in fees
+        let balance = 11_998;
+
+        let stats = MintStats::from_history(&history, &fedimint, balance, NOW);
+        assert_eq!(
+            stats.all_time,
+            FlowTotals {
+                received_sats: 15_000,
+                sent_sats: 3_000,
+                fees_msats: 6_000,
+                count: 3,
+            }
+        );
+        assert_eq!(stats.last_30_days.count, 2);
+        assert_eq!(stats.last_30_days.received_sats, 5_000);
+        assert_eq!(stats.last_7_days.count, 1);
+        assert_eq!(stats.last_7_days.sent_sats, 3_000);
+
+        // Oldest first, ending with what's there now
+        let points = &stats.balance_history;
+        assert_eq!(points.len(), 30);
+        assert_eq!(points[29], 11_998);
+        // The payment was made just before this time two days ago
+        assert_eq!(points[29 - 2], 11_998);
+        assert_eq!(points[29 - 3], 15_000);
+        // Before the deposit ten days ago, only the first one was there
+        assert_eq!(points[29 - 10], 15_000);
+        assert_eq!(points[29 - 11], 10_000);
+        assert_eq!(points[0], 10_000);
+
+        // Nothing in history is a flat line at the balance
+        let empty = MintStats::from_history(&[], &fedimint, 500, NOW);
+        assert_eq!(empty.all_time, FlowTotals::default());
+        assert!(empty.balance_history.iter().all(|b| *b == 500));
+    }
+
+    #[test]
+    fn test_mint_stats_cache() {
+        let fedimint = MintIdentifier::Fedimint(FederationId::dummy());
+        let cashu = MintIdentifier::Cashu(MintUrl::from_str("https://mint.example.com").unwrap());
+        let mut history = vec![flow(TransactionDirection::Incoming, 1_000, 1)];
+        let mut cache = MintStatsCache::default();
+
+        assert!(cache.update(&history, &fedimint, 1_000, NOW));
+        assert!(!cache.update(&history, &fedimint, 1_000, NOW + 60));
+        assert_eq!(cache.get(&fedimint).unwrap().all_time.count, 1);
+
+        // Another mint's activity leaves this one's stats alone
+        history.push(TransactionItem {
+            mint_identifier: cashu.clone(),
+            ..flow(TransactionDirection::Incoming, 2_000, 0)
+        });
+        assert!(!cache.update(&history, &fedimint, 1_000, NOW));
+        assert!(cache.update(&history, &cashu, 2_000, NOW));
+
+        // A new entry, a new balance or a new day all mean working it out again
+        history.push(flow(TransactionDirection::Outgoing, 500, 0));
+        assert!(cache.update(&history, &fedimint, 498, NOW));
+        assert_eq!(cache.get(&fedimint).unwrap().all_time.count, 2);
+        assert!(cache.update(&history, &fedimint, 600, NOW));
+        assert!(cache.update(&history, &fedimint, 600, NOW + DAY));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::diagnostics::scrub_text;
+use crate::mint_error::AddMintError;
+use bitcoin::Network;
+use uuid::Uuid;
+
+/// What to paste into a bug report when an operation fails, instead of a screenshot of a
+/// cut off error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorReport {
+    /// What the user was doing, like "Mint join"
+    pub operation: String,
+    pub id: Uuid,
+    /// Unix timestamp of when it failed
+    pub failed_at: u64,
+    pub error: AddMintError,
+    pub harbor_version: String,
+    pub network: Network,
+    /// Anything else that helps pin it down, like the federation ID. These are kept as is.
+    pub context: Vec<(String, String)>,
+}
+
+impl ErrorReport {
+    /// A plain text block for copying, scrubbed of anything that looks like a key, seed
+    /// words or ecash. Only the context values are trusted to be safe.
+    pub fn to_text(&self) -> String {
+        let failed_at = i64::try_from(self.failed_at)
+            .ok()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .map_or_else(|| self.failed_at.to_string(), |time| time.to_rfc3339());
+
+        let mut lines = vec![
+            format!("Operation: {} ({})", self.operation, self.id),
+            format!("Failed at: {failed_at}"),
+            format!("Category: {:?}", self.error.kind),
+            format!("Error: {}", self.error.message),
+        ];
+        for (i, cause) in self.error.chain.iter().enumerate() {
+            lines.push(format!("  {}: {cause}", i + 1));
+        }
+        lines.push(format!("Harbor: {}", self.harbor_version));
+        lines.push(format!("Network: {}", self.network));
+        for (name, value) in &self.context {
+            lines.push(format!("{name}: {value}"));
+        }
+
+        let keep: Vec<String> = self
+            .context
+            .iter()
+            .map(|(_, value)| value.clone())
+            .collect();
+        scrub_text(&lines.join("\n"), &keep)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mint_error::AddMintErrorKind;
+
+    const FEDERATION_ID: &str = "c8d423964c7ad944d30f57359b6e5b260e211dcfdb945140e28d4df51fd572d2";
+
+    #[test]
+    fn test_error_report_text() {
+        let id = Uuid::nil();
+        let report = ErrorReport {
+            operation: "Mint join".to_string(),
+            id,
+            failed_at: 1_700_000_000,
+            error: AddMintError {
+                kind: AddMintErrorKind::NetworkUnreachable,
+                message: "Could not download federation info".to_string(),
+                chain: vec![
+                    "error trying to connect".to_string(),
+                    "token cashuBo2FteCJodHRwczovL21pbnQuZXhhbXBsZS5jb20 rejected".to_string(),
+                ],
+            },
+            harbor_version: "1.0.0".to_string(),
+            network: Network::Signet,
+            context: vec![("Federation".to_string(), FEDERATION_ID.to_string())],
+        };
+
+        assert_eq!(
+            report.to_text(),
+            format!(
+                "Operation: Mint join ({id})\n\
+                 Failed at: 2023-11-14T22:13:20+00:00\n\
+                 Category: NetworkUnreachable\n\
+                 Error: Could not download federation info\n  \
+                 1: error trying to connect\n  \
+                 2: token [redacted] rejected\n\
+                 Harbor: 1.0.0\n\
+                 Network: signet\n\
+                 Federation: {FEDERATION_ID}"
+            )
+        );
+    }
+}
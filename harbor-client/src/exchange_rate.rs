@@ -0,0 +1,253 @@
+use crate::http::{make_get_request_direct, make_get_request_tor};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+
+// Where prices come from, it answers with every currency at once so one request covers
+// switching between them
+const PRICES_URL: &str = "https://mempool.space/api/v1/prices";
+
+/// How long a fetched price is used before asking again
+pub const RATE_FRESH_FOR_SECS: u64 = 10 * 60;
+
+const SATS_PER_BTC: f64 = 100_000_000.0;
+
+/// The currencies balances can be shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FiatCurrency {
+    Usd,
+    Eur,
+    Gbp,
+    Cad,
+    Chf,
+    Aud,
+    Jpy,
+}
+
+impl FiatCurrency {
+    pub const ALL: [Self; 7] = [
+        Self::Usd,
+        Self::Eur,
+        Self::Gbp,
+        Self::Cad,
+        Self::Chf,
+        Self::Aud,
+        Self::Jpy,
+    ];
+
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::Usd => "USD",
+            Self::Eur => "EUR",
+            Self::Gbp => "GBP",
+            Self::Cad => "CAD",
+            Self::Chf => "CHF",
+            Self::Aud => "AUD",
+            Self::Jpy => "JPY",
+        }
+    }
+
+    const fn symbol(self) -> &'static str {
+        match self {
+            Self::Usd | Self::Cad | Self::Aud => "$",
+            Self::Eur => "€",
+            Self::Gbp => "£",
+            Self::Chf => "CHF ",
+            Self::Jpy => "¥",
+        }
+    }
+
+    // Yen don't have cents
+    const fn decimals(self) -> usize {
+        match self {
+            Self::Jpy => 0,
+            _ => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for FiatCurrency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// The price of one bitcoin in a currency, and when it was fetched
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExchangeRate {
+    pub currency: FiatCurrency,
+    pub btc_price: f64,
+    /// Unix seconds
+    pub fetched_at: u64,
+}
+
+impl ExchangeRate {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_fiat(&self, sats: u64) -> f64 {
+        sats as f64 / SATS_PER_BTC * self.btc_price
+    }
+
+    /// What `sats` is worth, like "≈ $12.34"
+    pub fn format(&self, sats: u64) -> String {
+        format!(
+            "≈ {}{:.*}",
+            self.currency.symbol(),
+            self.currency.decimals(),
+            self.to_fiat(sats)
+        )
+    }
+
+    /// Whether the price is old enough that it's probably off, because fetching has been
+    /// failing since
+    pub const fn is_stale(&self, now: u64) -> bool {
+        now.saturating_sub(self.fetched_at) > 2 * RATE_FRESH_FOR_SECS
+    }
+}
+
+// What the prices endpoint answers with, any currency it leaves out is just not available
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct Prices {
+    usd: Option<f64>,
+    eur: Option<f64>,
+    gbp: Option<f64>,
+    cad: Option<f64>,
+    chf: Option<f64>,
+    aud: Option<f64>,
+    jpy: Option<f64>,
+}
+
+impl Prices {
+    const fn get(&self, currency: FiatCurrency) -> Option<f64> {
+        match currency {
+            FiatCurrency::Usd => self.usd,
+            FiatCurrency::Eur => self.eur,
+            FiatCurrency::Gbp => self.gbp,
+            FiatCurrency::Cad => self.cad,
+            FiatCurrency::Chf => self.chf,
+            FiatCurrency::Aud => self.aud,
+            FiatCurrency::Jpy => self.jpy,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CachedPrices {
+    prices: Option<(Prices, u64)>,
+    /// Set after a failed fetch until one works, so a long outage is only logged once
+    failing: bool,
+}
+
+/// The last prices fetched, shared so every request for a rate within
+/// [`RATE_FRESH_FOR_SECS`] is answered without going to the network
+#[derive(Debug, Clone, Default)]
+pub struct ExchangeRates {
+    cached: Arc<Mutex<CachedPrices>>,
+}
+
+impl ExchangeRates {
+    fn cached_rate(&self, currency: FiatCurrency) -> Option<ExchangeRate> {
+        let cached = self.cached.lock().ok()?;
+        let (prices, fetched_at) = cached.prices?;
+        prices.get(currency).map(|btc_price| ExchangeRate {
+            currency,
+            btc_price,
+            fetched_at,
+        })
+    }
+
+    fn is_fresh(&self, now: u64) -> bool {
+        self.cached
+            .lock()
+            .ok()
+            .and_then(|cached| cached.prices)
+            .is_some_and(|(_, fetched_at)| now.saturating_sub(fetched_at) < RATE_FRESH_FOR_SECS)
+    }
+
+    fn store(&self, fetched: anyhow::Result<Prices>, now: u64) {
+        let Ok(mut cached) = self.cached.lock() else {
+            return;
+        };
+        match fetched {
+            Ok(prices) => {
+                cached.prices = Some((prices, now));
+                cached.failing = false;
+            }
+            Err(e) => {
+                if !cached.failing {
+                    log::warn!("Could not fetch exchange rates, showing sats only: {e}");
+                }
+                cached.failing = true;
+            }
+        }
+    }
+
+    /// The price of bitcoin in `currency`, fetched again once the last one is older than
+    /// [`RATE_FRESH_FOR_SECS`]. A failed fetch falls back to the last price there was, so
+    /// this is only `None` when there's never been one.
+    pub async fn get(
+        &self,
+        currency: FiatCurrency,
+        tor_enabled: bool,
+        cancel_handle: Arc<AtomicBool>,
+    ) -> Option<ExchangeRate> {
+        let now = chrono::Utc::now().timestamp().unsigned_abs();
+        if !self.is_fresh(now) {
+            let fetched = if tor_enabled {
+                make_get_request_tor::<Prices>(PRICES_URL, cancel_handle).await
+            } else {
+                make_get_request_direct::<Prices>(PRICES_URL).await
+            };
+            self.store(fetched, now);
+        }
+        self.cached_rate(currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exchange_rate() {
+        let rate = ExchangeRate {
+            currency: FiatCurrency::Usd,
+            btc_price: 60_000.0,
+            fetched_at: 1_000,
+        };
+        assert_eq!(rate.format(100_000_000), "≈ $60000.00");
+        assert_eq!(rate.format(12_345), "≈ $7.41");
+        assert_eq!(rate.format(0), "≈ $0.00");
+
+        let yen = ExchangeRate {
+            currency: FiatCurrency::Jpy,
+            btc_price: 9_000_000.0,
+            ..rate
+        };
+        assert_eq!(yen.format(1_000), "≈ ¥90");
+
+        // Only flagged once it's had time to be refreshed and wasn't
+        assert!(!rate.is_stale(1_000 + RATE_FRESH_FOR_SECS));
+        assert!(rate.is_stale(1_000 + 3 * RATE_FRESH_FOR_SECS));
+
+        // A currency the endpoint left out has no rate, failures keep the last prices
+        let rates = ExchangeRates::default();
+        let prices: Prices = serde_json::from_str(r#"{"time":1,"USD":60000,"EUR":55000}"#).unwrap();
+        rates.store(Ok(prices), 1_000);
+        assert_eq!(
+            rates
+                .cached_rate(FiatCurrency::Eur)
+                .unwrap()
+                .format(100_000_000),
+            "≈ €55000.00"
+        );
+        assert!(rates.cached_rate(FiatCurrency::Jpy).is_none());
+        rates.store(Err(anyhow::anyhow!("offline")), 5_000);
+        assert_eq!(
+            rates.cached_rate(FiatCurrency::Usd).unwrap().fetched_at,
+            1_000
+        );
+        assert!(rates.is_fresh(1_000 + RATE_FRESH_FOR_SECS - 1));
+        assert!(!rates.is_fresh(5_000));
+    }
+}
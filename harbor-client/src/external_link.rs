@@ -0,0 +1,108 @@
+use url::Url;
+
+/// Why a link wasn't handed to the browser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalLinkError {
+    Invalid,
+    /// Anything but http and https, like `file:` or a custom app scheme
+    UnsupportedScheme(String),
+}
+
+impl std::fmt::Display for ExternalLinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid => write!(f, "This link isn't a valid web address"),
+            Self::UnsupportedScheme(scheme) => {
+                write!(f, "Only web links can be opened, not {scheme}: links")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExternalLinkError {}
+
+/// A link from outside Harbor, like a mint's website from its metadata, checked before it goes
+/// anywhere near the system's opener
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalLink {
+    url: Url,
+}
+
+impl ExternalLink {
+    pub fn parse(s: &str) -> Result<Self, ExternalLinkError> {
+        let url = Url::parse(s.trim()).map_err(|_| ExternalLinkError::Invalid)?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(ExternalLinkError::UnsupportedScheme(
+                url.scheme().to_string(),
+            ));
+        }
+        if url.host_str().is_none_or(str::is_empty) {
+            return Err(ExternalLinkError::Invalid);
+        }
+        Ok(Self { url })
+    }
+
+    /// The link as the browser gets it, normalized and percent encoded
+    pub fn as_str(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// Where the link actually goes, so something like `https://bank.com@evil.example` can't
+    /// pass as another site
+    pub fn host(&self) -> &str {
+        self.url.host_str().unwrap_or_default()
+    }
+
+    /// A username or password before the host, which is mostly used to disguise where a
+    /// link goes
+    pub fn has_credentials(&self) -> bool {
+        !self.url.username().is_empty() || self.url.password().is_some()
+    }
+
+    /// Onion services only open in Tor Browser, not whatever the default browser is
+    pub fn is_onion(&self) -> bool {
+        self.host()
+            .rsplit_once('.')
+            .is_some_and(|(_, tld)| tld.eq_ignore_ascii_case("onion"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_link() {
+        let link = ExternalLink::parse(" https://mint.example.com/tos ").unwrap();
+        assert_eq!(link.as_str(), "https://mint.example.com/tos");
+        assert_eq!(link.host(), "mint.example.com");
+        assert!(!link.is_onion() && !link.has_credentials());
+
+        // The host is what comes after any credentials
+        let disguised = ExternalLink::parse("https://bank.com@evil.example/login").unwrap();
+        assert_eq!(disguised.host(), "evil.example");
+        assert!(disguised.has_credentials());
+
+        let onion = ExternalLink::parse(
+            "http://2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion/",
+        )
+        .unwrap();
+        assert!(onion.is_onion());
+
+        // Nothing but web links
+        assert_eq!(
+            ExternalLink::parse("file:///etc/passwd"),
+            Err(ExternalLinkError::UnsupportedScheme("file".to_string()))
+        );
+        assert_eq!(
+            ExternalLink::parse("javascript:alert(1)"),
+            Err(ExternalLinkError::UnsupportedScheme(
+                "javascript".to_string()
+            ))
+        );
+        assert_eq!(
+            ExternalLink::parse("not a link"),
+            Err(ExternalLinkError::Invalid)
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     CoreUIMsg, CoreUIMsgPacket, HarborCore, MintIdentifier, ReceiveSuccessMsg, SendSuccessMsg,
+    StatusSeverity,
 };
 use crate::{db::DBConnection, db_models::NewFedimint};
 use anyhow::anyhow;
@@ -7,11 +8,14 @@ use async_trait::async_trait;
 use bip39::Mnemonic;
 use bitcoin::Network;
 use bitcoin::hashes::hex::FromHex;
+use fedimint_api_client::api::FederationApiExt;
+use fedimint_api_client::query::FilterMapThreshold;
 use fedimint_bip39::Bip39RootSecretStrategy;
 use fedimint_client::ClientHandleArc;
 use fedimint_client::backup::Metadata;
 use fedimint_client::secret::{RootSecretStrategy, get_default_client_secret};
 use fedimint_client_module::oplog::UpdateStreamOrOutcome;
+use fedimint_core::Amount;
 use fedimint_core::config::FederationId;
 use fedimint_core::core::OperationId;
 use fedimint_core::db::IDatabaseTransactionOps;
@@ -20,17 +24,24 @@ use fedimint_core::db::IRawDatabaseTransaction;
 use fedimint_core::db::PrefixStream;
 use fedimint_core::db::mem_impl::MemDatabase;
 use fedimint_core::db::mem_impl::MemTransaction;
+use fedimint_core::module::ApiRequestErased;
+use fedimint_core::util::SafeUrl;
+use fedimint_core::{NumPeersExt, PeerId};
 use fedimint_core::{db::IDatabaseTransactionOpsCore, invite_code::InviteCode};
 use fedimint_ln_client::{
     InternalPayState, LightningClientInit, LightningClientModule, LnPayState, LnReceiveState,
 };
 use fedimint_ln_common::LightningGateway;
+use fedimint_ln_common::lightning_invoice::Bolt11Invoice;
 use fedimint_lnv2_client::{LightningOperationMeta, ReceiveOperationState, SendOperationState};
+use fedimint_lnv2_common::endpoint_constants::GATEWAYS_ENDPOINT;
+use fedimint_lnv2_common::gateway_api::{GatewayConnection, PaymentFee, RealGatewayConnection};
 use fedimint_mint_client::MintClientInit;
 use fedimint_wallet_client::{DepositStateV2, WalletClientInit, WalletClientModule, WithdrawState};
 use futures::StreamExt;
 use futures::channel::mpsc::Sender;
 use log::{debug, error, info, trace};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
 use std::ops::Range;
 use std::path::Path;
@@ -160,53 +171,19 @@ impl FedimintClient {
                             e
                         })?;
 
+                    // The scan itself runs in the background, HarborCore follows it from here
                     HarborCore::send_msg(
                         &mut sender,
                         msg_id,
                         CoreUIMsg::StatusUpdate {
                             message: "Recovering federation notes".to_string(),
                             operation_id: msg_id,
+                            target: Some(MintIdentifier::Fedimint(federation_id)),
+                            severity: StatusSeverity::Info,
                         },
                     )
                     .await;
-                    match client.wait_for_all_recoveries().await {
-                        Ok(()) => {
-                            info!("Federation successfully recovered");
-                            HarborCore::send_msg(
-                                &mut sender,
-                                msg_id,
-                                CoreUIMsg::StatusUpdate {
-                                    message: "Successfully recovered federation notes".to_string(),
-                                    operation_id: msg_id,
-                                },
-                            )
-                            .await;
-
-                            let fut = Box::pin(Self::new(
-                                storage,
-                                invite_or_id,
-                                mnemonic,
-                                network,
-                                stop,
-                                sender,
-                                msg_id,
-                            ));
-                            return fut.await;
-                        }
-                        Err(e) => {
-                            error!("Could not recover federation: {e}");
-                            HarborCore::send_msg(
-                                &mut sender,
-                                msg_id,
-                                CoreUIMsg::StatusUpdate {
-                                    message: "Failed recovered federation notes".to_string(),
-                                    operation_id: msg_id,
-                                },
-                            )
-                            .await;
-                            return Err(e);
-                        }
-                    }
+                    Arc::new(client)
                 }
             }
         } else {
@@ -216,6 +193,16 @@ impl FedimintClient {
             ));
         };
 
+        // Modules only come up once recovery is done, so there's nothing more to set up until
+        // HarborCore reopens the client
+        if fedimint_client.has_pending_recoveries() {
+            info!("Federation {federation_id} is still recovering");
+            return Ok(Self {
+                fedimint_client,
+                stop,
+            });
+        }
+
         trace!("Retrieving fedimint wallet client module");
 
         // we can't check network during a recovery
@@ -285,6 +272,10 @@ impl FedimintClient {
     pub fn federation_id(&self) -> FederationId {
         self.fedimint_client.federation_id()
     }
+
+    pub fn is_recovering(&self) -> bool {
+        self.fedimint_client.has_pending_recoveries()
+    }
 }
 
 pub(crate) async fn select_gateway(client: &ClientHandleArc) -> Option<LightningGateway> {
@@ -328,12 +319,69 @@ pub(crate) async fn select_gateway(client: &ClientHandleArc) -> Option<Lightning
     selected_gateway
 }
 
+// The fee an LNv2 send of `amount` to `invoice` would pay, quoted from the first of the
+// federation's gateways that answers. Gateways more guardians list are tried first, like the
+// LNv2 module does when it picks one to send with.
+pub(crate) async fn lnv2_send_fee(
+    client: &ClientHandleArc,
+    invoice: &Bolt11Invoice,
+    amount: Amount,
+) -> anyhow::Result<Amount> {
+    if !cfg!(feature = "lnv2") {
+        return Err(anyhow!("LNv2 is not enabled"));
+    }
+
+    let lnv2 = client.get_first_module::<fedimint_lnv2_client::LightningClientModule>()?;
+    let by_peer: BTreeMap<PeerId, Vec<SafeUrl>> = lnv2
+        .api
+        .request_with_strategy(
+            FilterMapThreshold::new(
+                |_, gateways| Ok(gateways),
+                lnv2.api.all_peers().to_num_peers(),
+            ),
+            GATEWAYS_ENDPOINT.to_string(),
+            ApiRequestErased::default(),
+        )
+        .await?;
+    let mut gateways: Vec<SafeUrl> = by_peer
+        .values()
+        .flatten()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    gateways.sort_by_cached_key(|gateway| {
+        by_peer
+            .values()
+            .filter(|listed| !listed.contains(gateway))
+            .count()
+    });
+
+    let federation_id = client.federation_id();
+    for gateway in gateways {
+        let Ok(Some(routing_info)) = RealGatewayConnection
+            .routing_info(gateway, &federation_id)
+            .await
+        else {
+            continue;
+        };
+        // The send would refuse a fee over the limit and go to LNv1
+        let (fee, _) = routing_info.send_parameters(invoice);
+        if !fee.le(&PaymentFee::SEND_FEE_LIMIT) {
+            return Err(anyhow!("The LNv2 gateway charges more than the fee limit"));
+        }
+        return Ok(fee.add_to(amount.msats).saturating_sub(amount));
+    }
+
+    Err(anyhow!("No LNv2 gateway answered"))
+}
+
 pub(crate) async fn update_history(
     storage: Arc<dyn DBConnection + Send + Sync>,
     msg_id: Uuid,
     sender: &mut Sender<CoreUIMsgPacket>,
 ) {
-    if let Ok(history) = storage.get_transaction_history() {
+    if let Ok(history) = storage.get_transaction_history(None) {
         HarborCore::send_msg(
             sender,
             Some(msg_id),
@@ -601,16 +649,17 @@ pub(crate) async fn spawn_lnv2_payment_subscription(
                 }
                 SendOperationState::Success(preimage) => {
                     info!("Payment success");
+                    let fee_msats = get_ln_tx_fee(&client, operation_id).await.ok();
                     let params = if is_transfer {
-                        SendSuccessMsg::Transfer
+                        SendSuccessMsg::Transfer {
+                            fee: fee_msats.map(Amount::from_msats),
+                        }
                     } else {
                         SendSuccessMsg::Lightning { preimage }
                     };
                     HarborCore::send_msg(&mut sender, Some(msg_id), CoreUIMsg::SendSuccess(params))
                         .await;
 
-                    let fee_msats = get_ln_tx_fee(&client, operation_id).await.ok();
-
                     if let Err(e) = storage.set_lightning_as_complete(
                         operation_id.fmt_full().to_string(),
                         preimage,
@@ -693,16 +742,17 @@ pub(crate) async fn spawn_invoice_payment_subscription(
                     info!("Payment success");
                     let preimage: [u8; 32] =
                         FromHex::from_hex(&preimage).expect("Invalid preimage");
+                    let fee_msats = get_ln_tx_fee(&client, operation_id).await.ok();
                     let params = if is_transfer {
-                        SendSuccessMsg::Transfer
+                        SendSuccessMsg::Transfer {
+                            fee: fee_msats.map(Amount::from_msats),
+                        }
                     } else {
                         SendSuccessMsg::Lightning { preimage }
                     };
                     HarborCore::send_msg(&mut sender, Some(msg_id), CoreUIMsg::SendSuccess(params))
                         .await;
 
-                    let fee_msats = get_ln_tx_fee(&client, operation_id).await.ok();
-
                     if let Err(e) = storage.set_lightning_as_complete(
                         operation_id.fmt_full().to_string(),
                         preimage,
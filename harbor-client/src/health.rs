@@ -0,0 +1,168 @@
+use crate::MintIdentifier;
+use crate::db_models::GuardianHealth;
+use std::collections::HashMap;
+
+/// A mint is only notified about this often, however much its health flaps
+pub const HEALTH_NOTIFY_EVERY_SECS: u64 = 6 * 60 * 60;
+
+/// How a federation is doing going by its guardians' last health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MintHealth {
+    Healthy,
+    /// Some guardians are down but there are still enough for the federation to work
+    Degraded,
+    /// Too few guardians answer for the federation to work
+    Unreachable,
+}
+
+impl MintHealth {
+    /// Fedimint federations keep working as long as more than two thirds of guardians are up
+    pub fn of(guardians: &[GuardianHealth]) -> Self {
+        let total = guardians.len();
+        let online = guardians.iter().filter(|g| g.online).count();
+        let threshold = total - total.saturating_sub(1) / 3;
+        if online == total {
+            Self::Healthy
+        } else if online >= threshold {
+            Self::Degraded
+        } else {
+            Self::Unreachable
+        }
+    }
+}
+
+impl std::fmt::Display for MintHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Healthy => write!(f, "healthy"),
+            Self::Degraded => write!(f, "degraded"),
+            Self::Unreachable => write!(f, "unreachable"),
+        }
+    }
+}
+
+/// A mint getting worse, or getting back to healthy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthChange {
+    pub mint: MintIdentifier,
+    pub from: MintHealth,
+    pub to: MintHealth,
+    /// Whether it's been long enough since the last notification for this mint to send one
+    pub notify: bool,
+}
+
+/// Follows each mint's health across health checks to spot when it changes
+#[derive(Debug, Clone, Default)]
+pub struct HealthMonitor {
+    last: HashMap<MintIdentifier, MintHealth>,
+    notified_at: HashMap<MintIdentifier, u64>,
+}
+
+impl HealthMonitor {
+    /// Records the latest health of `mint`, returning the change if it got worse or recovered.
+    /// A mint seen for the first time counts as having been healthy, so one that's already
+    /// down is reported. Getting better without being healthy again isn't a change worth
+    /// reporting.
+    pub fn observe(
+        &mut self,
+        mint: &MintIdentifier,
+        health: MintHealth,
+        now: u64,
+    ) -> Option<HealthChange> {
+        let from = self
+            .last
+            .insert(mint.clone(), health)
+            .unwrap_or(MintHealth::Healthy);
+        let worse = health > from;
+        let recovered = health == MintHealth::Healthy && from != MintHealth::Healthy;
+        if !worse && !recovered {
+            return None;
+        }
+
+        let notify = self
+            .notified_at
+            .get(mint)
+            .is_none_or(|at| now.saturating_sub(*at) >= HEALTH_NOTIFY_EVERY_SECS);
+        if notify {
+            self.notified_at.insert(mint.clone(), now);
+        }
+
+        Some(HealthChange {
+            mint: mint.clone(),
+            from,
+            to: health,
+            notify,
+        })
+    }
+
+    /// Forgets a mint, for when it's archived or removed
+    pub fn forget(&mut self, mint: &MintIdentifier) {
+        self.last.remove(mint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fedimint_core::PeerId;
+    use fedimint_core::config::FederationId;
+
+    fn guardians(online: usize, total: usize) -> Vec<GuardianHealth> {
+        (0..total)
+            .map(|i| GuardianHealth {
+                peer_id: PeerId::from(u16::try_from(i).unwrap()),
+                name: format!("Guardian {i}"),
+                online: i < online,
+                endpoint_host: "example.com".to_string(),
+                onion: false,
+                last_contact: None,
+                version: None,
+                last_error: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mint_health() {
+        assert_eq!(MintHealth::of(&guardians(4, 4)), MintHealth::Healthy);
+        assert_eq!(MintHealth::of(&guardians(3, 4)), MintHealth::Degraded);
+        assert_eq!(MintHealth::of(&guardians(2, 4)), MintHealth::Unreachable);
+        assert_eq!(MintHealth::of(&guardians(1, 1)), MintHealth::Healthy);
+        assert_eq!(MintHealth::of(&guardians(0, 1)), MintHealth::Unreachable);
+
+        let mint = MintIdentifier::Fedimint(FederationId::dummy());
+        let mut monitor = HealthMonitor::default();
+
+        // Nothing to say about a mint that's fine
+        assert_eq!(monitor.observe(&mint, MintHealth::Healthy, 0), None);
+
+        // Going down is reported and notified
+        let change = monitor.observe(&mint, MintHealth::Degraded, 10).unwrap();
+        assert_eq!(change.from, MintHealth::Healthy);
+        assert!(change.notify);
+        assert_eq!(monitor.observe(&mint, MintHealth::Degraded, 20), None);
+
+        // Getting worse is reported, but too soon after the last one to notify again
+        let change = monitor.observe(&mint, MintHealth::Unreachable, 30).unwrap();
+        assert!(!change.notify);
+
+        // Partly better isn't recovered
+        assert_eq!(monitor.observe(&mint, MintHealth::Degraded, 40), None);
+
+        // Recovering once the throttle is up notifies
+        let change = monitor
+            .observe(&mint, MintHealth::Healthy, 10 + HEALTH_NOTIFY_EVERY_SECS)
+            .unwrap();
+        assert_eq!(change.to, MintHealth::Healthy);
+        assert!(change.notify);
+
+        // A mint that's already down when first seen is reported
+        let other = MintIdentifier::Fedimint(FederationId::dummy());
+        let mut monitor = HealthMonitor::default();
+        assert!(
+            monitor
+                .observe(&other, MintHealth::Unreachable, 0)
+                .is_some()
+        );
+    }
+}
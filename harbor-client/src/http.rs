@@ -2,18 +2,21 @@ use anyhow::anyhow;
 use arti_client::{TorAddr, TorClient};
 use fedimint_core::util::SafeUrl;
 use http_body_util::Empty;
-use hyper::body::{Body, Bytes};
+use hyper::body::Bytes;
+use hyper::client::conn::http1::SendRequest;
 use hyper::header::LOCATION;
 use hyper::{Request, Uri};
-use hyper_rustls::HttpsConnectorBuilder;
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
 use hyper_util::client::legacy::Client;
-use hyper_util::rt::TokioExecutor;
-use once_cell::sync::OnceCell;
+use hyper_util::client::legacy::connect::{Connect, HttpConnector};
+use hyper_util::rt::{TokioExecutor, TokioTimer};
+use once_cell::sync::{Lazy, OnceCell};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_rustls::rustls::RootCertStore;
 use tor_rtcompat::PreferredRuntime;
@@ -43,13 +46,30 @@ fn get_tor_client() -> anyhow::Result<Arc<TorClient<PreferredRuntime>>> {
     }
 }
 
+// How long opening a connection and its TLS handshake each get, set from the user's settings
+static CONNECT_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(30);
+
+/// Sets how long connecting and the TLS handshake each get, for requests started after this
+pub fn set_connect_timeout(timeout: Duration) {
+    CONNECT_TIMEOUT_SECS.store(timeout.as_secs(), Ordering::Relaxed);
+}
+
+fn connect_timeout() -> Duration {
+    Duration::from_secs(CONNECT_TIMEOUT_SECS.load(Ordering::Relaxed))
+}
+
 const MAX_REDIRECTS: u8 = 5;
+
+// How long a connection can sit unused and still be reused, direct or over Tor
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+// Idle connections kept per host. A mint gets a handful of requests in a row, not a flood.
+const MAX_IDLE_PER_HOST: usize = 2;
 const MAX_RESPONSE_SIZE: usize = 10 * 1024 * 1024; // 10MB limit
 
 /// Make a GET request using normal TCP with TLS.
 ///
 /// This is the standard way to make HTTPS requests. It:
-/// - Uses a connection pool for better performance
+/// - Shares a connection pool, so requests to the same host reuse a kept-alive connection
 /// - Handles redirects automatically (up to `MAX_REDIRECTS`)
 /// - Enforces a response size limit
 /// - Returns deserialized JSON
@@ -57,7 +77,25 @@ pub async fn make_get_request_direct<T>(url: &str) -> anyhow::Result<T>
 where
     T: DeserializeOwned + Send + 'static,
 {
-    make_get_request_direct_internal::<T>(url.to_string(), 0).await
+    let body = make_get_request_direct_internal(url.to_string(), 0, MAX_RESPONSE_SIZE).await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Make a GET request and return the raw response body instead of JSON.
+///
+/// The request goes over Tor when `tor_enabled` is set, same as the JSON requests, and
+/// gives up as soon as the body grows past `max_size` bytes.
+pub async fn make_get_request_bytes(
+    url: &str,
+    max_size: usize,
+    tor_enabled: bool,
+    cancel_handle: Arc<AtomicBool>,
+) -> anyhow::Result<Bytes> {
+    if tor_enabled {
+        make_tor_request_bytes::<()>(url, None, cancel_handle, max_size).await
+    } else {
+        make_get_request_direct_internal(url.to_string(), 0, max_size).await
+    }
 }
 
 /// Helper function to monitor cancellation
@@ -73,7 +111,7 @@ async fn check_cancel(cancel_handle: Arc<AtomicBool>) {
 /// - Routing all traffic through the Tor network
 /// - Supporting .onion addresses
 /// - Enforcing HTTPS-only connections
-/// - Using fresh circuits for each request
+/// - Using a fresh circuit for each host, kept open for follow-up requests to it
 ///
 /// The request can be cancelled at any time using the `cancel_handle`.
 ///
@@ -91,7 +129,7 @@ where
 /// - Routing all traffic through the Tor network
 /// - Supporting .onion addresses
 /// - Enforcing HTTPS-only connections
-/// - Using fresh circuits for each request
+/// - Using a fresh circuit for each host, kept open for follow-up requests to it
 ///
 /// The request can be cancelled at any time using the `cancel_handle`.
 ///
@@ -104,6 +142,19 @@ pub async fn make_tor_request<T, P>(
 where
     P: Serialize + Sized,
     T: DeserializeOwned + Send + 'static,
+{
+    let body = make_tor_request_bytes(url, payload, cancel_handle, MAX_RESPONSE_SIZE).await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+async fn make_tor_request_bytes<P>(
+    url: &str,
+    payload: Option<P>,
+    cancel_handle: Arc<AtomicBool>,
+    max_size: usize,
+) -> anyhow::Result<Bytes>
+where
+    P: Serialize + Sized,
 {
     log::debug!("Making get request to tor: {}", url);
 
@@ -112,6 +163,105 @@ where
         return Err(anyhow!("Only HTTPS is supported"));
     }
 
+    let host = safe_url
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("Expected host str"))?
+        .to_string();
+    let port = safe_url
+        .port_or_known_default()
+        .ok_or_else(|| anyhow::anyhow!("Expected port number"))?;
+
+    // Parse the URL properly
+    let parsed_url = Url::parse(url)?;
+    // Get the path and query string
+    let path = if let Some(query) = parsed_url.query() {
+        format!("{}?{}", parsed_url.path(), query)
+    } else {
+        parsed_url.path().to_string()
+    };
+
+    // For single connections, we need to use relative paths and set the Host header. Built
+    // as needed since a request can't be sent twice.
+    let body = payload
+        .map(|payload| serde_json::to_string(&payload))
+        .transpose()?;
+    let build_request = || match &body {
+        None => build_get_request(&path, Some(host.clone())),
+        Some(body) => build_post_request(&path, Some(host.clone()), body.clone()),
+    };
+
+    // Check if cancelled before sending request
+    if cancel_handle.load(Ordering::Relaxed) {
+        return Err(anyhow!("Request cancelled"));
+    }
+
+    // A connection left open by an earlier request to the same host saves building a circuit
+    // and redoing the TLS handshake. The host may have closed it since, in which case a new
+    // one is opened, but only if the request never went out or is a GET, so a POST like a
+    // swap is never sent twice.
+    let reused = match TOR_CONNECTIONS.take(&host, port, Instant::now()) {
+        Some(mut sender) => sender.ready().await.ok().map(|()| sender),
+        None => None,
+    };
+    if let Some(mut sender) = reused {
+        log::debug!("Reusing Tor connection to {}:{}", &host, port);
+        match sender.try_send_request(build_request()?).await {
+            Ok(response) => {
+                return finish_tor_request(response, sender, &host, port, max_size).await;
+            }
+            Err(mut e) => {
+                if e.take_message().is_none() && body.is_some() {
+                    return Err(anyhow!(
+                        "Connection lost while sending request: {}",
+                        e.into_error()
+                    ));
+                }
+                log::debug!("Reused Tor connection to {host} failed, opening a new one");
+            }
+        }
+    }
+
+    let mut sender = connect_tor(&safe_url, &host, port, cancel_handle).await?;
+    let request = build_request()?;
+    // Log the full request for debugging
+    log::debug!(
+        "Sending request: {} {} {:?}",
+        request.method(),
+        request.uri(),
+        request.headers()
+    );
+    let response = sender.send_request(request).await?;
+    finish_tor_request(response, sender, &host, port, max_size).await
+}
+
+// Reads the response, then hands the connection back for the next request to the same host
+async fn finish_tor_request(
+    response: hyper::Response<hyper::body::Incoming>,
+    sender: PooledSender,
+    host: &str,
+    port: u16,
+    max_size: usize,
+) -> anyhow::Result<Bytes> {
+    log::debug!(
+        "Got response: {} {:?}",
+        response.status(),
+        response.headers()
+    );
+    let body = handle_response(response, 0, None, max_size).await;
+    // Only once the body has been read in full is the connection free for another request
+    if body.is_ok() {
+        TOR_CONNECTIONS.put(host, port, sender, Instant::now());
+    }
+    body
+}
+
+// Opens a connection to `host` on a new circuit and does the TLS and HTTP/1.1 handshakes
+async fn connect_tor(
+    safe_url: &SafeUrl,
+    host: &str,
+    port: u16,
+    cancel_handle: Arc<AtomicBool>,
+) -> anyhow::Result<PooledSender> {
     // Get a reference to the global TorClient
     let tor_client = get_tor_client()?;
 
@@ -141,31 +291,14 @@ where
     }
 
     let tor_client = tor_client.isolated_client();
-
-    let host = safe_url
-        .host_str()
-        .ok_or_else(|| anyhow::anyhow!("Expected host str"))?
-        .to_string();
-    let port = safe_url
-        .port_or_known_default()
-        .ok_or_else(|| anyhow::anyhow!("Expected port number"))?;
-
-    // Parse the URL properly
-    let parsed_url = Url::parse(url)?;
-    // Get the path and query string
-    let path = if let Some(query) = parsed_url.query() {
-        format!("{}?{}", parsed_url.path(), query)
-    } else {
-        parsed_url.path().to_string()
-    };
     let is_onion = safe_url.is_onion_address();
 
-    let tor_addr = TorAddr::from((host.as_str(), port))
-        .map_err(|e| anyhow::anyhow!("Invalid endpoint addr: {:?}: {e:#}", (&host, port)))?;
+    let tor_addr = TorAddr::from((host, port))
+        .map_err(|e| anyhow::anyhow!("Invalid endpoint addr: {:?}: {e:#}", (host, port)))?;
 
-    log::debug!("Attempting to connect to {}:{} via Tor", &host, port);
+    log::debug!("Attempting to connect to {}:{} via Tor", host, port);
 
-    let connect_timeout = Duration::from_secs(30);
+    let connect_timeout = connect_timeout();
     let stream = if is_onion {
         let mut stream_prefs = arti_client::StreamPrefs::default();
         stream_prefs.connect_to_onion_services(arti_client::config::BoolOrAuto::Explicit(true));
@@ -221,20 +354,13 @@ where
     };
 
     // After getting the stream, wrap it in TLS
-    let mut root_store = RootCertStore::empty();
-    root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
-
-    let config = tokio_rustls::rustls::ClientConfig::builder()
-        .with_root_certificates(root_store)
-        .with_no_client_auth();
-
-    let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
-    let server_name = rustls_pki_types::ServerName::try_from(host.as_str())
+    let connector = tokio_rustls::TlsConnector::from(TLS_CONFIG.clone());
+    let server_name = rustls_pki_types::ServerName::try_from(host)
         .map_err(|_| anyhow!("Invalid DNS name: {}", host))?
         .to_owned();
 
     log::debug!("Starting TLS handshake with {}", host);
-    let tls_timeout = Duration::from_secs(30);
+    let tls_timeout = connect_timeout;
 
     // Use select! to handle cancellation during TLS handshake
     let tls_result = tokio::select! {
@@ -259,107 +385,153 @@ where
         }
     };
 
-    make_request_tor(host, path, payload, tls_stream, cancel_handle).await
+    http1_connection(tls_stream).await
 }
 
-async fn make_request_tor<T, S, P>(
-    host: String,
-    path: String,
-    payload: Option<P>,
-    stream: S,
-    cancel_handle: Arc<AtomicBool>,
-) -> anyhow::Result<T>
+// Does the HTTP/1.1 handshake over an open stream, leaving a task to drive the connection
+async fn http1_connection<S>(stream: S) -> anyhow::Result<PooledSender>
 where
-    P: Serialize + Sized,
-    T: DeserializeOwned + Send + 'static,
     S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
 {
-    // Check if cancelled before sending request
-    if cancel_handle.load(Ordering::Relaxed) {
-        return Err(anyhow!("Request cancelled"));
-    }
-
-    // Create a Hyper connection using the TLS-wrapped Tor stream
-    log::debug!("Creating TokioIo wrapper for stream");
+    log::debug!("Starting HTTP/1.1 handshake");
     let io = hyper_util::rt::TokioIo::new(stream);
+    let (sender, conn) = hyper::client::conn::http1::handshake(io).await?;
+    log::debug!("HTTP/1.1 handshake successful");
 
-    // For single connections, we need to use relative paths and set the Host header
-    log::debug!("Building request for path: {} with host: {}", path, host);
-    match payload {
-        None => {
-            log::debug!("Starting HTTP/1.1 handshake");
-            let (sender, conn) = hyper::client::conn::http1::handshake(io).await?;
-            log::debug!("HTTP/1.1 handshake successful");
-
-            // Spawn the connection driver task
-            tokio::spawn(async move {
-                if let Err(err) = conn.await {
-                    log::error!("Connection driver failed: {:?}", err);
-                }
-            });
+    // Spawn the connection driver task
+    tokio::spawn(async move {
+        if let Err(err) = conn.await {
+            log::error!("Connection driver failed: {:?}", err);
+        }
+    });
+
+    Ok(sender)
+}
 
-            let request = build_get_request(path, Some(host))?;
-            // Log the full request for debugging
-            log::debug!(
-                "Sending request: {} {} {:?}",
-                request.method(),
-                request.uri(),
-                request.headers()
-            );
+type PooledSender = SendRequest<String>;
 
-            handle_http_request(request, sender).await
+// The connections kept open for each host and port, with when each was last put back
+type IdleConnections = HashMap<(String, u16), Vec<(PooledSender, Instant)>>;
+
+/// Idle HTTP/1.1 connections by host and port, for requests that don't go through a hyper
+/// `Client` and so don't get its pool, like the ones over Tor
+struct ConnectionPool {
+    idle: Mutex<IdleConnections>,
+}
+
+impl ConnectionPool {
+    fn new() -> Self {
+        Self {
+            idle: Mutex::new(HashMap::new()),
         }
-        Some(payload) => {
-            log::debug!("Starting HTTP/1.1 handshake");
-            let (sender, conn) = hyper::client::conn::http1::handshake(io).await?;
-            log::debug!("HTTP/1.1 handshake successful");
-
-            // Spawn the connection driver task
-            tokio::spawn(async move {
-                if let Err(err) = conn.await {
-                    log::error!("Connection driver failed: {:?}", err);
-                }
+    }
+
+    /// An open connection to `host` that hasn't been idle too long, if there is one
+    fn take(&self, host: &str, port: u16, now: Instant) -> Option<PooledSender> {
+        let mut idle = self.idle.lock().ok()?;
+        let connections = idle.get_mut(&(host.to_string(), port))?;
+        while let Some((sender, idle_since)) = connections.pop() {
+            if !sender.is_closed() && now.duration_since(idle_since) < POOL_IDLE_TIMEOUT {
+                return Some(sender);
+            }
+        }
+        None
+    }
+
+    /// Keeps a connection for the next request to `host`, unless there are enough already.
+    /// Connections to any host that have been idle too long are dropped along the way.
+    fn put(&self, host: &str, port: u16, sender: PooledSender, now: Instant) {
+        if sender.is_closed() {
+            return;
+        }
+        let Ok(mut idle) = self.idle.lock() else {
+            return;
+        };
+        for connections in idle.values_mut() {
+            connections.retain(|(sender, idle_since)| {
+                !sender.is_closed() && now.duration_since(*idle_since) < POOL_IDLE_TIMEOUT
             });
+        }
+        idle.retain(|_, connections| !connections.is_empty());
+
+        let connections = idle.entry((host.to_string(), port)).or_default();
+        if connections.len() < MAX_IDLE_PER_HOST {
+            connections.push((sender, now));
+        }
+    }
+}
 
-            let request = build_post_request(path, Some(host), payload)?;
-            // Log the full request for debugging
-            log::debug!(
-                "Sending request: {} {} {:?}",
-                request.method(),
-                request.uri(),
-                request.headers()
-            );
+// Connections over Tor, each on its own circuit. Reusing one only links requests to the host
+// that already sees them come from the same wallet.
+static TOR_CONNECTIONS: Lazy<ConnectionPool> = Lazy::new(ConnectionPool::new);
 
-            handle_http_request(request, sender).await
+// Rustls' defaults with the webpki roots, so TLS 1.2 or later with certificates checked
+static TLS_CONFIG: Lazy<Arc<tokio_rustls::rustls::ClientConfig>> = Lazy::new(|| {
+    let mut root_store = RootCertStore::empty();
+    root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    Arc::new(
+        tokio_rustls::rustls::ClientConfig::builder()
+            .with_root_certificates(root_store)
+            .with_no_client_auth(),
+    )
+});
+
+type HttpsClient = Client<HttpsConnector<HttpConnector>, Empty<Bytes>>;
+
+// The client direct requests share, so connections to the same host are kept alive between
+// them, along with the connect timeout it was built with
+static DIRECT_CLIENT: Mutex<Option<(Duration, HttpsClient)>> = Mutex::new(None);
+
+// The shared client, built again when the connect timeout has changed since
+fn direct_client() -> HttpsClient {
+    let timeout = connect_timeout();
+    let Ok(mut cached) = DIRECT_CLIENT.lock() else {
+        return create_https_client(timeout);
+    };
+    match &*cached {
+        Some((built_with, client)) if *built_with == timeout => client.clone(),
+        _ => {
+            let client = create_https_client(timeout);
+            *cached = Some((timeout, client.clone()));
+            client
         }
     }
 }
 
 // Create a new Hyper client with TLS support and reasonable defaults
-fn create_https_client() -> Client<
-    hyper_rustls::HttpsConnector<hyper_util::client::legacy::connect::HttpConnector>,
-    Empty<Bytes>,
-> {
+fn create_https_client(connect_timeout: Duration) -> HttpsClient {
+    let mut http = HttpConnector::new();
+    http.enforce_http(false);
+    http.set_connect_timeout(Some(connect_timeout));
     let https = HttpsConnectorBuilder::new()
         .with_webpki_roots()
         .https_only() // Enforce HTTPS for all connections
         .enable_http1()
-        .build();
+        .wrap_connector(http);
+
+    pooled_client(https)
+}
 
+// Keep-alive and per host limits for a hyper client, the same as `TOR_CONNECTIONS` uses
+fn pooled_client<C>(connector: C) -> Client<C, Empty<Bytes>>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
     Client::builder(TokioExecutor::new())
-        .pool_idle_timeout(Duration::from_secs(30))
-        .pool_max_idle_per_host(1)
-        .build(https)
+        .pool_timer(TokioTimer::new())
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .pool_max_idle_per_host(MAX_IDLE_PER_HOST)
+        .build(connector)
 }
 
 /// Common response handling logic
-async fn handle_response<T, B, E>(
+async fn handle_response<B, E>(
     response: hyper::Response<B>,
     redirect_count: u8,
     original_url: Option<&str>,
-) -> anyhow::Result<T>
+    max_size: usize,
+) -> anyhow::Result<Bytes>
 where
-    T: DeserializeOwned + Send + 'static,
     E: std::error::Error + Send + Sync + 'static,
     B: http_body_util::BodyExt<Data = Bytes, Error = E>,
 {
@@ -390,7 +562,8 @@ where
             };
 
             log::debug!("Following redirect to: {}", redirect_url);
-            return make_get_request_direct_internal::<T>(redirect_url, redirect_count + 1).await;
+            return make_get_request_direct_internal(redirect_url, redirect_count + 1, max_size)
+                .await;
         }
         return Err(anyhow!("Redirect response missing Location header"));
     }
@@ -410,50 +583,30 @@ where
         return Err(anyhow!("HTTP request failed with status: {}", status));
     }
 
-    // Read the response body with size limit
-    let body_bytes = http_body_util::BodyExt::collect(response.into_body())
-        .await?
+    // Read the response body, stopping as soon as it goes over the size limit
+    let body = http_body_util::Limited::new(response.into_body(), max_size);
+    let body_bytes = http_body_util::BodyExt::collect(body)
+        .await
+        .map_err(|e| {
+            if e.is::<http_body_util::LengthLimitError>() {
+                anyhow!("Response too large, exceeded {} bytes", max_size)
+            } else {
+                anyhow!("Failed to read response body: {}", e)
+            }
+        })?
         .to_bytes();
 
-    if body_bytes.len() > MAX_RESPONSE_SIZE {
-        return Err(anyhow!(
-            "Response too large, exceeded {} bytes",
-            MAX_RESPONSE_SIZE
-        ));
-    }
-
-    // Parse the JSON response
-    let parsed = serde_json::from_slice(&body_bytes)?;
-    Ok(parsed)
-}
-
-/// Common HTTP request/response handling logic for single connections
-async fn handle_http_request<T, P>(
-    request: Request<P>,
-    mut sender: hyper::client::conn::http1::SendRequest<P>,
-) -> anyhow::Result<T>
-where
-    P: Body + 'static,
-    T: DeserializeOwned + Send + 'static,
-{
-    log::debug!("Sending request to server");
-    let response = sender.send_request(request).await?;
-    log::debug!(
-        "Got response: {} {:?}",
-        response.status(),
-        response.headers()
-    );
-    handle_response(response, 0, None).await
+    Ok(body_bytes)
 }
 
 /// Use what Chrome puts for User Agent for better privacy, copied from: `https://www.whatismybrowser.com/guides/the-latest-user-agent/chrome`
 const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/134.0.0.0 Safari/537.36";
 
-/// Build a GET request with common headers
-fn build_get_request(
+/// Build a GET request with common headers, the empty body being whatever the connection takes
+fn build_get_request<B: Default>(
     uri: impl AsRef<str>,
     host: Option<String>,
-) -> anyhow::Result<Request<Empty<Bytes>>> {
+) -> anyhow::Result<Request<B>> {
     let uri_str = uri.as_ref();
     let mut builder = Request::builder()
         .uri(uri_str)
@@ -467,15 +620,15 @@ fn build_get_request(
     }
 
     builder
-        .body(Empty::<Bytes>::new())
+        .body(B::default())
         .map_err(|e| anyhow!("Failed to build request: {}", e))
 }
 
 /// Build a POST request with common headers
-fn build_post_request<P: Serialize + Sized>(
+fn build_post_request(
     uri: impl AsRef<str>,
     host: Option<String>,
-    payload: P,
+    body: String,
 ) -> anyhow::Result<Request<String>> {
     let uri_str = uri.as_ref();
     let mut builder = Request::builder()
@@ -491,19 +644,16 @@ fn build_post_request<P: Serialize + Sized>(
         }
     }
 
-    let body = serde_json::to_string(&payload)?;
     builder
         .body(body)
         .map_err(|e| anyhow!("Failed to build request: {}", e))
 }
 
-fn make_get_request_direct_internal<T>(
+fn make_get_request_direct_internal(
     url: String,
     redirect_count: u8,
-) -> std::pin::Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send>>
-where
-    T: DeserializeOwned + Send + 'static,
-{
+    max_size: usize,
+) -> std::pin::Pin<Box<dyn Future<Output = anyhow::Result<Bytes>> + Send>> {
     Box::pin(async move {
         if redirect_count >= MAX_REDIRECTS {
             return Err(anyhow!("Too many redirects (max {})", MAX_REDIRECTS));
@@ -517,7 +667,7 @@ where
 
         log::debug!("Making direct get request to: {}", url);
 
-        let client = create_https_client();
+        let client = direct_client();
         let uri: Uri = url
             .parse()
             .map_err(|e| anyhow!("Invalid URL '{}': {}", url, e))?;
@@ -532,7 +682,7 @@ where
             }
         })?;
 
-        handle_response(response, redirect_count, Some(&url)).await
+        handle_response(response, redirect_count, Some(&url), max_size).await
     })
 }
 
@@ -545,6 +695,89 @@ mod tests {
         let _ = env_logger::builder().is_test(true).try_init();
     }
 
+    // A plain HTTP server that answers every request with `{}` and keeps the connection open,
+    // counting the connections it accepts
+    async fn keep_alive_server() -> (std::net::SocketAddr, Arc<AtomicU64>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepted = Arc::new(AtomicU64::new(0));
+        let counter = accepted.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::Relaxed);
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let mut received = Vec::new();
+                    while let Ok(n) = stream.read(&mut buf).await {
+                        if n == 0 {
+                            break;
+                        }
+                        received.extend_from_slice(&buf[..n]);
+                        // The requests here have no body, so each one ends at the blank line
+                        while let Some(end) = received.windows(4).position(|w| w == b"\r\n\r\n") {
+                            received.drain(..end + 4);
+                            let response = b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}";
+                            if stream.write_all(response).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        (addr, accepted)
+    }
+
+    #[tokio::test]
+    async fn test_connection_reuse() {
+        init();
+
+        // Sequential requests through a client with the shared pool settings use one connection
+        let (addr, accepted) = keep_alive_server().await;
+        let client = pooled_client(HttpConnector::new());
+        for _ in 0..3 {
+            let request = build_get_request(format!("http://{addr}/v1/info"), None).unwrap();
+            let response = client.request(request).await.unwrap();
+            let body = handle_response(response, 0, None, MAX_RESPONSE_SIZE)
+                .await
+                .unwrap();
+            assert_eq!(&body[..], b"{}");
+        }
+        assert_eq!(accepted.load(Ordering::Relaxed), 1);
+
+        // As do connections handed back to a pool by hand, like the ones over Tor
+        let (addr, accepted) = keep_alive_server().await;
+        let pool = ConnectionPool::new();
+        let host = addr.ip().to_string();
+        for _ in 0..3 {
+            let mut sender = match pool.take(&host, addr.port(), Instant::now()) {
+                Some(sender) => sender,
+                None => {
+                    let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+                    http1_connection(stream).await.unwrap()
+                }
+            };
+            sender.ready().await.unwrap();
+            let request = build_get_request("/v1/info", Some(host.clone())).unwrap();
+            let response = sender.send_request(request).await.unwrap();
+            handle_response(response, 0, None, MAX_RESPONSE_SIZE)
+                .await
+                .unwrap();
+            pool.put(&host, addr.port(), sender, Instant::now());
+        }
+        assert_eq!(accepted.load(Ordering::Relaxed), 1);
+
+        // Other hosts don't get it, and nothing is handed out once it's been idle too long
+        assert!(
+            pool.take("example.com", addr.port(), Instant::now())
+                .is_none()
+        );
+        let later = Instant::now() + POOL_IDLE_TIMEOUT;
+        assert!(pool.take(&host, addr.port(), later).is_none());
+    }
+
     #[tokio::test]
     async fn test_fetch_metadata() {
         init();
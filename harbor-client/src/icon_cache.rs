@@ -0,0 +1,330 @@
+use crate::http::make_get_request_bytes;
+use anyhow::anyhow;
+use bitcoin::hashes::{Hash, sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, SystemTime};
+
+/// Icons are shown at a few dozen pixels, anything bigger than this isn't worth downloading
+pub const MAX_ICON_SIZE: usize = 512 * 1024;
+
+/// The widest or tallest icon that's kept. A small file can still claim a huge canvas, and the
+/// UI decodes it at full size.
+pub const MAX_ICON_DIMENSION: u32 = 512;
+
+/// How long a downloaded icon is used before it's fetched again
+pub const ICON_REFRESH_INTERVAL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+const ICON_DIR: &str = "icons";
+
+/// The image formats the UI knows how to draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Svg,
+}
+
+const FORMATS: [IconFormat; 5] = [
+    IconFormat::Png,
+    IconFormat::Jpeg,
+    IconFormat::Gif,
+    IconFormat::Webp,
+    IconFormat::Svg,
+];
+
+impl IconFormat {
+    /// Works out the format from the file's contents rather than trusting the URL or headers
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            return Some(Self::Png);
+        }
+        if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+            return Some(Self::Jpeg);
+        }
+        if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            return Some(Self::Gif);
+        }
+        if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            return Some(Self::Webp);
+        }
+
+        // SVGs are text, so look for the root element near the start, after any XML prolog. The
+        // cut can land inside a multi-byte character, so that's read lossily.
+        let start = &bytes[..bytes.len().min(1024)];
+        String::from_utf8_lossy(start)
+            .contains("<svg")
+            .then_some(Self::Svg)
+    }
+
+    /// The width and height from the image's header, `None` for SVGs or headers that can't be
+    /// read
+    pub fn dimensions(self, bytes: &[u8]) -> Option<(u32, u32)> {
+        let be32 = |at: usize| Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+        let le16 = |at: usize| {
+            Some(u32::from(u16::from_le_bytes(
+                bytes.get(at..at + 2)?.try_into().ok()?,
+            )))
+        };
+        let le24 = |at: usize| {
+            let b = bytes.get(at..at + 3)?;
+            Some(u32::from_le_bytes([b[0], b[1], b[2], 0]))
+        };
+
+        match self {
+            // IHDR is always the first chunk
+            Self::Png => {
+                if bytes.get(12..16)? != b"IHDR" {
+                    return None;
+                }
+                Some((be32(16)?, be32(20)?))
+            }
+            // The logical screen every frame is drawn into
+            Self::Gif => Some((le16(6)?, le16(8)?)),
+            Self::Webp => match bytes.get(12..16)? {
+                // Lossy, the key frame header follows its start code
+                b"VP8 " => {
+                    if bytes.get(23..26)? != [0x9d, 0x01, 0x2a] {
+                        return None;
+                    }
+                    Some((le16(26)? & 0x3fff, le16(28)? & 0x3fff))
+                }
+                // Lossless, 14 bits each for width and height less one
+                b"VP8L" => {
+                    if *bytes.get(20)? != 0x2f {
+                        return None;
+                    }
+                    let bits = u32::from_le_bytes(bytes.get(21..25)?.try_into().ok()?);
+                    Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+                }
+                // Extended, the canvas size less one in 24 bits each
+                b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+                _ => None,
+            },
+            Self::Jpeg => jpeg_dimensions(bytes),
+            Self::Svg => None,
+        }
+    }
+
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::Gif => "gif",
+            Self::Webp => "webp",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+// JPEGs keep their size in the start of frame segment, which comes after any metadata segments
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?));
+
+    // Skip the start of image marker
+    let mut at = 2;
+    loop {
+        if *bytes.get(at)? != 0xff {
+            return None;
+        }
+        let marker = *bytes.get(at + 1)?;
+        match marker {
+            // Fill bytes before a marker
+            0xff => at += 1,
+            // Markers that stand alone, without a length
+            0x01 | 0xd0..=0xd7 => at += 2,
+            // Every SOFn except DHT, JPG and DAC, which share the range
+            0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                return Some((u32::from(be16(at + 7)?), u32::from(be16(at + 5)?)));
+            }
+            // Reached the image data or its end without a frame
+            0xd9 | 0xda => return None,
+            _ => at += 2 + usize::from(be16(at + 2)?),
+        }
+    }
+}
+
+// Files are named after the URL so a federation changing its icon gets the new one
+fn icon_stem(url: &str) -> String {
+    let hash = sha256::Hash::hash(url.as_bytes()).to_string();
+    hash[..32].to_string()
+}
+
+/// The downloaded icon for `url`, if there is one. This never touches the network.
+pub fn cached_icon(data_dir: &Path, url: &str) -> Option<PathBuf> {
+    let dir = data_dir.join(ICON_DIR);
+    let stem = icon_stem(url);
+    FORMATS
+        .iter()
+        .map(|format| dir.join(format!("{stem}.{}", format.extension())))
+        .find(|path| path.is_file())
+}
+
+fn is_stale(path: &Path) -> bool {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified());
+    match modified {
+        Ok(modified) => SystemTime::now()
+            .duration_since(modified)
+            .is_ok_and(|age| age > ICON_REFRESH_INTERVAL),
+        Err(_) => true,
+    }
+}
+
+/// Saves the icon to the cache, replacing any older copy for the same URL
+pub fn store_icon(data_dir: &Path, url: &str, bytes: &[u8]) -> anyhow::Result<PathBuf> {
+    if bytes.len() > MAX_ICON_SIZE {
+        return Err(anyhow!("Icon is larger than {MAX_ICON_SIZE} bytes"));
+    }
+    let format = IconFormat::detect(bytes).ok_or_else(|| anyhow!("Icon is not a known format"))?;
+
+    // SVGs are drawn at whatever size they're shown, only raster images carry a pixel size
+    if format != IconFormat::Svg {
+        let (width, height) = format
+            .dimensions(bytes)
+            .ok_or_else(|| anyhow!("Icon has no readable size"))?;
+        if width > MAX_ICON_DIMENSION || height > MAX_ICON_DIMENSION {
+            return Err(anyhow!(
+                "Icon is {width}x{height}, more than {MAX_ICON_DIMENSION} pixels a side"
+            ));
+        }
+    }
+
+    let dir = data_dir.join(ICON_DIR);
+    std::fs::create_dir_all(&dir)?;
+
+    if let Some(old) = cached_icon(data_dir, url) {
+        std::fs::remove_file(old)?;
+    }
+
+    // Write somewhere else first so the UI never loads a half written file
+    let path = dir.join(format!("{}.{}", icon_stem(url), format.extension()));
+    let partial = path.with_extension("partial");
+    std::fs::write(&partial, bytes)?;
+    std::fs::rename(&partial, &path)?;
+
+    Ok(path)
+}
+
+/// Whether the icon for `url` is missing from disk or old enough to fetch again
+pub fn needs_fetch(data_dir: &Path, url: &str) -> bool {
+    cached_icon(data_dir, url).is_none_or(|path| is_stale(&path))
+}
+
+/// Downloads the icon for `url` unless there's already a recent enough copy on disk
+pub async fn fetch_icon(
+    data_dir: &Path,
+    url: &str,
+    tor_enabled: bool,
+    cancel_handle: Arc<AtomicBool>,
+) -> anyhow::Result<PathBuf> {
+    if let Some(path) = cached_icon(data_dir, url).filter(|path| !is_stale(path)) {
+        return Ok(path);
+    }
+
+    let bytes = make_get_request_bytes(url, MAX_ICON_SIZE, tor_enabled, cancel_handle).await?;
+    store_icon(data_dir, url, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempdir::TempDir;
+
+    // The signature and header of a 64x64 image
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x40\0\0\0\x40\x08\x06\0\0\0";
+    const SVG: &[u8] = br#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg"></svg>"#;
+
+    #[test]
+    fn test_detect_icon_format() {
+        assert_eq!(IconFormat::detect(PNG), Some(IconFormat::Png));
+        assert_eq!(
+            IconFormat::detect(&[0xff, 0xd8, 0xff, 0xe0]),
+            Some(IconFormat::Jpeg)
+        );
+        assert_eq!(IconFormat::detect(b"GIF89a..."), Some(IconFormat::Gif));
+        assert_eq!(
+            IconFormat::detect(b"RIFF\0\0\0\0WEBPVP8 "),
+            Some(IconFormat::Webp)
+        );
+        assert_eq!(IconFormat::detect(SVG), Some(IconFormat::Svg));
+
+        // Error pages and other junk servers hand back instead of an image
+        assert_eq!(
+            IconFormat::detect(b"<html><body>Not found</body></html>"),
+            None
+        );
+        assert_eq!(IconFormat::detect(&[0, 1, 2, 3]), None);
+        assert_eq!(IconFormat::detect(b""), None);
+
+        // A multi-byte character cut off at the end of what's looked at doesn't hide the root
+        let mut svg = b"<svg ".to_vec();
+        svg.extend("é".repeat(600).as_bytes());
+        assert_eq!(IconFormat::detect(&svg), Some(IconFormat::Svg));
+    }
+
+    #[test]
+    fn test_icon_dimensions() {
+        assert_eq!(IconFormat::Png.dimensions(PNG), Some((64, 64)));
+        assert_eq!(
+            IconFormat::Gif.dimensions(b"GIF89a\x20\0\x10\0"),
+            Some((32, 16))
+        );
+
+        let vp8 = b"RIFF\0\0\0\0WEBPVP8 \0\0\0\0\0\0\0\x9d\x01\x2a\x40\0\x30\0";
+        assert_eq!(IconFormat::Webp.dimensions(vp8), Some((64, 48)));
+        let vp8l = b"RIFF\0\0\0\0WEBPVP8L\0\0\0\0\x2f\x63\x40\x0c\0";
+        assert_eq!(IconFormat::Webp.dimensions(vp8l), Some((100, 50)));
+        let vp8x = b"RIFF\0\0\0\0WEBPVP8X\0\0\0\0\0\0\0\0\xe7\x03\0\x57\x02\0";
+        assert_eq!(IconFormat::Webp.dimensions(vp8x), Some((1000, 600)));
+
+        // The frame comes after an APP0 segment, which is skipped over
+        let mut jpeg = vec![0xff, 0xd8, 0xff, 0xe0, 0x00, 0x10];
+        jpeg.extend([0; 14]);
+        jpeg.extend([0xff, 0xc0, 0x00, 0x11, 0x08, 0x00, 0x20, 0x00, 0x40, 0x03]);
+        assert_eq!(IconFormat::Jpeg.dimensions(&jpeg), Some((64, 32)));
+
+        // Cut short before the size, or with no frame at all
+        assert_eq!(IconFormat::Png.dimensions(&PNG[..20]), None);
+        assert_eq!(IconFormat::Webp.dimensions(&vp8[..27]), None);
+        assert_eq!(IconFormat::Jpeg.dimensions(&jpeg[..22]), None);
+        assert_eq!(IconFormat::Jpeg.dimensions(&[0xff, 0xd8, 0xff, 0xd9]), None);
+        assert_eq!(IconFormat::Svg.dimensions(SVG), None);
+    }
+
+    #[test]
+    fn test_icon_cache() {
+        let dir = TempDir::new("harbor-icons").unwrap();
+        let url = "https://example.com/icon.png";
+        assert_eq!(cached_icon(dir.path(), url), None);
+        assert!(needs_fetch(dir.path(), url));
+
+        let path = store_icon(dir.path(), url, PNG).unwrap();
+        assert_eq!(path.extension().unwrap(), "png");
+        assert_eq!(cached_icon(dir.path(), url), Some(path));
+        assert!(!needs_fetch(dir.path(), url));
+
+        // A new image at the same URL replaces the old one, even in a different format
+        let path = store_icon(dir.path(), url, SVG).unwrap();
+        assert_eq!(path.extension().unwrap(), "svg");
+        assert_eq!(cached_icon(dir.path(), url), Some(path));
+        assert_eq!(
+            cached_icon(dir.path(), "https://example.com/other.png"),
+            None
+        );
+
+        // Anything that isn't an image, is too big or claims too large a canvas is left out of the
+        // cache
+        assert!(store_icon(dir.path(), url, b"<html></html>").is_err());
+        let mut huge = PNG.to_vec();
+        huge.resize(MAX_ICON_SIZE + 1, 0);
+        assert!(store_icon(dir.path(), url, &huge).is_err());
+        let mut wide = PNG.to_vec();
+        wide[16..20].copy_from_slice(&4096u32.to_be_bytes());
+        assert!(store_icon(dir.path(), url, &wide).is_err());
+        assert!(store_icon(dir.path(), url, &PNG[..20]).is_err());
+        assert!(cached_icon(dir.path(), url).is_some());
+    }
+}
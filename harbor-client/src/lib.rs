@@ -22,53 +22,113 @@
     clippy::too_many_lines
 )]
 
+use crate::background::{BackgroundGate, BackgroundTask};
 use crate::cashu_client::{
     TorMintConnector, spawn_lightning_payment_thread, spawn_lightning_receive_thread,
 };
+use crate::connectivity::{CONNECTIVITY_CHECK_EVERY, Connectivity};
 use crate::db::DBConnection;
-use crate::db_models::MintItem;
-use crate::db_models::transaction_item::TransactionItem;
+use crate::db_models::operation_record::{OperationOutcome, OperationRecord};
+use crate::db_models::transaction_item::{FeeSummary, TransactionItem};
+use crate::db_models::{
+    FederationInitState, FederationVersions, FeeSchedule, GatewayInfo, GuardianHealth, MintFees,
+    MintItem, ModuleVersion, PendingAmounts, RecoveryState, SendFeeEstimate, SessionProgress,
+};
+use crate::diagnostics::{
+    DIAGNOSTICS_VERSION, DiagnosticGuardian, DiagnosticModule, MAX_LOG_LINES, MintDiagnostics,
+    PendingOperation, recent_errors, redact_url,
+};
+use crate::exchange_rate::{ExchangeRate, ExchangeRates, FiatCurrency};
 use crate::fedimint_client::{
     FederationInviteOrId, FedimintClient, select_gateway, spawn_internal_payment_subscription,
     spawn_invoice_payment_subscription, spawn_invoice_receive_subscription,
     spawn_onchain_payment_subscription, spawn_onchain_receive_subscription,
 };
+use crate::http::make_get_request_bytes;
 use crate::metadata::{CACHE, FederationData, FederationMeta, get_federation_metadata};
+use crate::mint_error::{AddMintError, AddMintErrorKind};
+use crate::mint_export::{
+    ExportedMint, FetchedMintList, MintImportSummary, MintListExport, signature_url,
+    verify_list_signature,
+};
+use crate::nostr::{NostrIdentity, NostrKeySource, NostrPublicIdentity};
+use crate::operations::{
+    Aborted, CancellableOperations, OperationKind, OperationTimeouts, StatusUpdates,
+};
+use crate::startup_timings::{StartupTimings, TimingSpan, short_federation_id};
 use ::fedimint_client::ClientHandleArc;
 use anyhow::anyhow;
 use bip39::Mnemonic;
 use bitcoin::address::NetworkUnchecked;
+use bitcoin::hashes::{Hash, sha256};
+use bitcoin::secp256k1::PublicKey;
 use bitcoin::{Address, Network, Txid};
+use cdk::amount::SplitTarget;
 use cdk::cdk_database::WalletDatabase;
 use cdk::mint_url::MintUrl;
-use cdk::nuts::{CurrencyUnit, MintInfo};
-use cdk::wallet::WalletBuilder;
+use cdk::nuts::{CurrencyUnit, MeltQuoteState, MintInfo, MintQuoteState};
+use cdk::wallet::{MeltQuote, WalletBuilder};
 use cdk_sqlite::WalletSqliteDatabase;
-use fedimint_client::{spawn_lnv2_payment_subscription, spawn_lnv2_receive_subscription};
-use fedimint_core::Amount;
-use fedimint_core::config::{ClientConfig, FederationId};
+use fedimint_api_client::api::FederationApiExt;
+use fedimint_client::{
+    lnv2_send_fee, spawn_lnv2_payment_subscription, spawn_lnv2_receive_subscription,
+};
+use fedimint_core::config::{ClientConfig, FederationId, PeerUrl};
 use fedimint_core::core::{ModuleKind, OperationId};
+use fedimint_core::encoding::{Decodable, Encodable};
+use fedimint_core::endpoint_constants::VERSION_ENDPOINT;
 use fedimint_core::invite_code::InviteCode;
+use fedimint_core::module::registry::ModuleDecoderRegistry;
+use fedimint_core::module::{ApiRequestErased, CoreConsensusVersion};
+use fedimint_core::util::SafeUrl;
+use fedimint_core::{Amount, PeerId};
 use fedimint_ln_client::{LightningClientModule, PayType};
-use fedimint_ln_common::config::FeeToAmount;
+use fedimint_ln_common::LightningGateway;
+use fedimint_ln_common::config::{FeeToAmount, LightningClientConfig};
 use fedimint_ln_common::lightning_invoice::{Bolt11InvoiceDescription, Description};
 use fedimint_wallet_client::WalletClientModule;
-use futures::{SinkExt, channel::mpsc::Sender};
+use fedimint_wallet_common::config::WalletClientConfig;
+use futures::{SinkExt, StreamExt, channel::mpsc::Sender};
 use lightning_address::make_lnurl_request;
 use lnurl::lnurl::LnUrl;
 use log::{error, trace};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::spawn;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+const GUARDIAN_HEALTH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+// How long each mint gets to quote a fee when picking the cheapest one to pay from
+const SEND_FEE_ESTIMATE_TIMEOUT: Duration = Duration::from_secs(10);
+// A melt quote this close to expiring isn't reused, the send could reach the mint too late
+const MELT_QUOTE_REUSE_MARGIN: Duration = Duration::from_secs(60);
+const METADATA_REFRESH_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+// How many federation clients are opened at once after unlock
+const FEDERATION_INIT_CONCURRENCY: usize = 4;
+// Opening a client can wait on guardians, past this it's reported as failed so it can be retried
+const FEDERATION_INIT_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+/// How long a previewed federation that was never joined is remembered after it was last shown
+pub const PREVIEW_CACHE_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+// A published mint list and its signature are small, anything much bigger isn't one
+const MAX_MINT_LIST_SIZE: usize = 1024 * 1024;
+const MAX_SIGNATURE_SIZE: usize = 1024;
+
+/// Notes are meant as a short reminder, not a document
+pub const MAX_MINT_NOTES_LEN: usize = 2048;
+
+/// The fedimint client library Harbor is built with, a test checks it against Cargo.toml
+pub const FEDIMINT_CLIENT_VERSION: &str = "0.7.1";
+// The newest core consensus version the fedimint client library above knows about
+const SUPPORTED_CORE_CONSENSUS_VERSION: CoreConsensusVersion = CoreConsensusVersion::new(2, 1);
+
 /// The directory where all application data is stored
 /// Defaults to ~/.harbor as the root directory
 /// Network-specific data goes in ~/.harbor/<network>
@@ -89,13 +149,28 @@ pub fn data_dir(network: Option<Network>) -> PathBuf {
     }
 }
 
+pub mod background;
 pub mod cashu_client;
+pub mod connectivity;
 pub mod db;
 pub mod db_models;
+pub mod diagnostics;
+pub mod error_report;
+pub mod exchange_rate;
+pub mod external_link;
 pub mod fedimint_client;
+pub mod health;
 mod http;
+pub mod icon_cache;
 pub mod lightning_address;
 pub mod metadata;
+pub mod mint_error;
+pub mod mint_export;
+pub mod nostr;
+pub mod operations;
+pub mod share_bundle;
+pub mod startup_timings;
+pub mod transfer;
 
 pub use bip39;
 pub use bitcoin;
@@ -133,18 +208,437 @@ pub enum MintConnectionInfo {
     Fedimint(InviteCode),
 }
 
+/// Which protocol a mint speaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum MintKind {
+    Cashu,
+    Fedimint,
+}
+
+impl std::fmt::Display for MintKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cashu => write!(f, "Cashu mint"),
+            Self::Fedimint => write!(f, "Fedimint federation"),
+        }
+    }
+}
+
+impl MintConnectionInfo {
+    pub const fn kind(&self) -> MintKind {
+        match self {
+            Self::Cashu(_) => MintKind::Cashu,
+            Self::Fedimint(_) => MintKind::Fedimint,
+        }
+    }
+
+    pub const fn is_fedimint(&self) -> bool {
+        matches!(self, Self::Fedimint(_))
+    }
+
+    pub const fn is_cashu(&self) -> bool {
+        matches!(self, Self::Cashu(_))
+    }
+
+    pub fn federation_id(&self) -> Option<FederationId> {
+        match self {
+            Self::Fedimint(invite_code) => Some(invite_code.federation_id()),
+            Self::Cashu(_) => None,
+        }
+    }
+
+    pub fn mint_url(&self) -> Option<MintUrl> {
+        match self {
+            Self::Cashu(url) => Some(url.clone()),
+            Self::Fedimint(_) => None,
+        }
+    }
+
+    /// Whether the mint can only be reached through onion services. A federation with at least
+    /// one clearnet guardian in the invite code can still be reached without Tor.
+    pub fn requires_tor(&self) -> bool {
+        match self {
+            Self::Cashu(url) => {
+                SafeUrl::parse(&url.to_string()).is_ok_and(|u| u.is_onion_address())
+            }
+            Self::Fedimint(invite_code) => {
+                let peers = invite_code.peers();
+                !peers.is_empty() && peers.values().all(SafeUrl::is_onion_address)
+            }
+        }
+    }
+}
+
+/// Why some text couldn't be turned into a [`MintConnectionInfo`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MintConnectionError {
+    /// Not a URL, and not shaped like bech32 either
+    NotBech32,
+    /// Shaped like an invite code but the checksum doesn't match, usually a typo
+    BadChecksum,
+    /// Valid bech32 for something other than an invite code, like an address or invoice
+    WrongHrp(String),
+    /// An invite code too short to hold even a federation ID, so part of it wasn't copied
+    TruncatedInvite,
+    /// A well formed invite code in a format this version of Harbor can't read yet
+    UnsupportedInviteVersion,
+    /// Several invite codes that aren't all for the same federation
+    MixedFederations,
+    /// Looked like a mint URL but didn't parse as one
+    InvalidUrl(url::ParseError),
+    /// A URL, but not one a Cashu mint is served over
+    UnsupportedScheme(String),
+}
+
+impl std::fmt::Display for MintConnectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotBech32 => write!(f, "Not an invite code or mint URL"),
+            Self::BadChecksum => write!(f, "Invite code checksum doesn't match"),
+            Self::WrongHrp(hrp) => write!(f, "Expected an invite code, found a {hrp} code"),
+            Self::TruncatedInvite => write!(f, "Invite code is incomplete"),
+            Self::UnsupportedInviteVersion => {
+                write!(f, "This invite code requires a newer version of Harbor")
+            }
+            Self::MixedFederations => write!(
+                f,
+                "These invite codes are for different federations, paste one federation at a time"
+            ),
+            Self::InvalidUrl(e) => write!(f, "Invalid mint URL: {e}"),
+            Self::UnsupportedScheme(scheme) => {
+                write!(
+                    f,
+                    "Mint URLs have to start with https:// or http://, not {scheme}://"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MintConnectionError {}
+
+// A federation ID alone is 32 bytes, which is 52 characters of bech32. With the "fed1" prefix,
+// the separator and the checksum no real invite code is this short.
+const MIN_INVITE_CODE_LEN: usize = 63;
+
+impl MintConnectionInfo {
+    /// Parses a mint URL, an invite code, or several invite codes for the same federation
+    /// separated by whitespace, saying what was wrong if it's none of those
+    pub fn parse(s: &str) -> Result<Self, MintConnectionError> {
+        let s = s.trim();
+        if s.split_whitespace().nth(1).is_some() {
+            let codes = s
+                .split_whitespace()
+                .map(parse_invite_code)
+                .collect::<Result<Vec<_>, _>>()?;
+            // There are at least two codes here, so the only way merging fails is a mix
+            merge_invite_codes(&codes)
+                .map(Self::Fedimint)
+                .map_err(|_| MintConnectionError::MixedFederations)
+        } else if s.chars().all(|c| c.is_ascii_alphanumeric()) {
+            // Bech32 is only letters and digits, anything with punctuation is meant as a URL
+            parse_invite_code(s).map(Self::Fedimint)
+        } else {
+            parse_mint_url(s).map(Self::Cashu)
+        }
+    }
+}
+
 impl FromStr for MintConnectionInfo {
-    type Err = anyhow::Error;
+    type Err = MintConnectionError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Ok(url) = MintUrl::from_str(s) {
-            Ok(Self::Cashu(url))
-        } else if let Ok(invite_code) = InviteCode::from_str(s) {
-            Ok(Self::Fedimint(invite_code))
+        Self::parse(s)
+    }
+}
+
+fn parse_invite_code(s: &str) -> Result<InviteCode, MintConnectionError> {
+    use bitcoin::bech32::primitives::decode::UncheckedHrpstring;
+    use bitcoin::bech32::{Bech32, Bech32m};
+
+    if let Ok(invite_code) = InviteCode::from_str(s) {
+        return Ok(invite_code);
+    }
+
+    let unchecked = UncheckedHrpstring::new(s).map_err(|_| MintConnectionError::NotBech32)?;
+    let hrp = unchecked.hrp().to_lowercase();
+    let checksum_ok =
+        unchecked.has_valid_checksum::<Bech32m>() || unchecked.has_valid_checksum::<Bech32>();
+
+    if !hrp.starts_with("fed") {
+        return Err(if checksum_ok {
+            MintConnectionError::WrongHrp(hrp)
         } else {
-            Err(anyhow::anyhow!("Invalid mint connection info: {}", s))
+            MintConnectionError::NotBech32
+        });
+    }
+    if hrp == "fed1" && s.len() <= MIN_INVITE_CODE_LEN {
+        return Err(MintConnectionError::TruncatedInvite);
+    }
+    if !checksum_ok {
+        return Err(MintConnectionError::BadChecksum);
+    }
+
+    // The checksum says nothing was mistyped, so a fedimint prefix we don't know or a payload we
+    // can't decode was made by something newer
+    Err(MintConnectionError::UnsupportedInviteVersion)
+}
+
+fn parse_mint_url(url: &str) -> Result<MintUrl, MintConnectionError> {
+    let mut parsed = url::Url::parse(url.trim()).map_err(MintConnectionError::InvalidUrl)?;
+    if !matches!(parsed.scheme(), "https" | "http") {
+        return Err(MintConnectionError::UnsupportedScheme(
+            parsed.scheme().to_string(),
+        ));
+    }
+
+    // Parsing already took care of the host's case and the scheme's default port
+    parsed.set_fragment(None);
+    let path = parsed.path().trim_end_matches('/').to_string();
+    parsed.set_path(&path);
+
+    // cdk parses the string with url again, which can't fail after it parsed above
+    MintUrl::from_str(parsed.as_str().trim_end_matches('/'))
+        .map_err(|_| MintConnectionError::InvalidUrl(url::ParseError::EmptyHost))
+}
+
+/// Puts a Cashu mint URL in one canonical form so the same mint typed differently isn't joined
+/// twice.
+///
+/// The host is lowercased, default ports and fragments are dropped, and trailing slashes are
+/// removed. Paths are kept as they are since mints can treat them case sensitively.
+pub fn normalize_mint_url(url: &str) -> anyhow::Result<MintUrl> {
+    Ok(parse_mint_url(url)?)
+}
+
+/// Whether two Cashu URLs are the same mint. Mints joined before URLs were normalized can be
+/// stored in another form, so both sides are compared normalized.
+pub fn same_mint_url(a: &MintUrl, b: &MintUrl) -> bool {
+    match (
+        normalize_mint_url(&a.to_string()),
+        normalize_mint_url(&b.to_string()),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Combines invite codes for the same federation into a single code holding every endpoint.
+///
+/// Some operators publish one code per guardian. Connecting with the merged code tries all of
+/// them, so one guardian being down doesn't stop the join.
+pub fn merge_invite_codes(codes: &[InviteCode]) -> anyhow::Result<InviteCode> {
+    let first = codes.first().ok_or(anyhow!("No invite codes to merge"))?;
+    let federation_id = first.federation_id();
+    if codes
+        .iter()
+        .any(|code| code.federation_id() != federation_id)
+    {
+        return Err(anyhow!(
+            "These invite codes are for different federations, paste one federation at a time"
+        ));
+    }
+
+    // Earlier codes win if two of them disagree on a guardian's address
+    let mut peers = BTreeMap::new();
+    for code in codes {
+        for (peer, url) in code.peers() {
+            peers.entry(peer).or_insert(url);
+        }
+    }
+    let api_secret = codes.iter().find_map(InviteCode::api_secret);
+
+    invite_code_with_peers(&peers, federation_id, api_secret)
+}
+
+// `InviteCode::from_map` keeps only as many guardians as it takes to be sure of reaching an
+// honest one, so the code is encoded from its parts directly instead: one per guardian, then
+// the federation id and the secret once each.
+fn invite_code_with_peers(
+    peers: &BTreeMap<PeerId, SafeUrl>,
+    federation_id: FederationId,
+    api_secret: Option<String>,
+) -> anyhow::Result<InviteCode> {
+    let parts: Vec<InviteCodePart> = peers
+        .iter()
+        .map(|(peer, url)| InviteCodePart::Api {
+            url: url.clone(),
+            peer: *peer,
+        })
+        .chain([InviteCodePart::FederationId(federation_id)])
+        .chain(api_secret.map(InviteCodePart::ApiSecret))
+        .collect();
+
+    // An invite code encodes as nothing but its list of parts
+    Ok(InviteCode::consensus_decode_whole(
+        &parts.consensus_encode_to_vec(),
+        &ModuleDecoderRegistry::default(),
+    )?)
+}
+
+// fedimint keeps the parts of an invite code private, this encodes the same way since
+// variants encode by their position
+#[derive(Encodable)]
+enum InviteCodePart {
+    Api { url: SafeUrl, peer: PeerId },
+    FederationId(FederationId),
+    ApiSecret(String),
+}
+
+/// The canonical form, a bech32 invite code or the normalized mint URL, which parses back
+/// to the same value
+impl std::fmt::Display for MintConnectionInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cashu(url) => write!(f, "{url}"),
+            Self::Fedimint(invite_code) => write!(f, "{invite_code}"),
+        }
+    }
+}
+
+// Picks the guardian an invite code should point at. Without any health checks yet there's
+// nothing to go on, so the first guardian is as good as any.
+fn invite_code_for_guardian(
+    federation_id: FederationId,
+    endpoints: &BTreeMap<PeerId, PeerUrl>,
+    health: Option<&[GuardianHealth]>,
+    preferred_guardian: Option<PeerId>,
+    api_secret: Option<String>,
+) -> anyhow::Result<InviteCode> {
+    let peer = match (preferred_guardian, health) {
+        (Some(peer), _) => peer,
+        (None, Some(health)) => health
+            .iter()
+            .find(|g| g.online)
+            .map(|g| g.peer_id)
+            .ok_or(anyhow!("None of the guardians are answering right now"))?,
+        (None, None) => *endpoints
+            .keys()
+            .next()
+            .ok_or(anyhow!("Federation has no guardians"))?,
+    };
+
+    let endpoint = endpoints
+        .get(&peer)
+        .ok_or(anyhow!("Not a guardian of this federation"))?;
+
+    Ok(InviteCode::new(
+        endpoint.url.clone(),
+        peer,
+        federation_id,
+        api_secret,
+    ))
+}
+
+// What a join warns about when some guardians didn't answer. It goes ahead anyway, since it
+// only needed enough of them to agree.
+fn unreachable_guardians_warning(health: &[GuardianHealth]) -> Option<String> {
+    let offline = health.iter().filter(|guardian| !guardian.online).count();
+    (offline > 0).then(|| {
+        format!(
+            "{offline} of {} guardians unreachable, proceeding",
+            health.len()
+        )
+    })
+}
+
+// Asks every mint for its fee and balance at once through `quote`, giving each `timeout` to
+// answer. A mint that doesn't gets an error instead of a fee.
+async fn probe_send_fees<F, Fut>(
+    mints: Vec<MintIdentifier>,
+    amount: Amount,
+    timeout: Duration,
+    quote: F,
+) -> Vec<SendFeeEstimate>
+where
+    F: Fn(MintIdentifier) -> Fut,
+    Fut: Future<Output = anyhow::Result<(Amount, Amount)>>,
+{
+    let estimates = mints.into_iter().map(|mint| {
+        let quote = quote(mint.clone());
+        async move {
+            let estimate = tokio::time::timeout(timeout, quote)
+                .await
+                .unwrap_or_else(|_| Err(anyhow!("No quote in time")));
+
+            match estimate {
+                Ok((fee, balance)) => SendFeeEstimate {
+                    mint,
+                    fee: Ok(fee),
+                    sufficient_balance: amount + fee <= balance,
+                },
+                Err(e) => {
+                    log::warn!("Could not estimate the fee to pay from {mint:?}: {e}");
+                    SendFeeEstimate {
+                        mint,
+                        fee: Err(e.to_string()),
+                        sufficient_balance: false,
+                    }
+                }
+            }
+        }
+    });
+    futures::future::join_all(estimates).await
+}
+
+// The bitcoin network a federation runs on, from its wallet module. Federations without one
+// don't touch the chain, so there's nothing to compare.
+fn federation_network(config: &ClientConfig) -> Option<Network> {
+    config
+        .modules
+        .values()
+        .find(|module| module.kind() == &fedimint_wallet_common::KIND)
+        .and_then(|module| module.cast::<WalletClientConfig>().ok())
+        .map(|wallet| wallet.network.0)
+}
+
+// Runs the part of a join that writes the federation to the database. If it fails, whatever it
+// wrote is undone so the federation isn't left half joined: a new one is deleted and a rejoined
+// one is archived again.
+async fn with_join_rollback<T>(
+    storage: &(dyn DBConnection + Send + Sync),
+    id: FederationId,
+    join: impl Future<Output = anyhow::Result<T>>,
+) -> anyhow::Result<T> {
+    let before = storage.get_fedimint(id)?;
+    let e = match join.await {
+        Ok(joined) => return Ok(joined),
+        Err(e) => e,
+    };
+
+    let rolled_back = match before {
+        None => storage.delete_federation(id),
+        Some(archived) if archived.active == 0 => {
+            storage.get_fedimint(id).and_then(|now| match now {
+                Some(now) if now.active == 1 => storage.remove_federation(id),
+                _ => Ok(()),
+            })
         }
+        Some(_) => Ok(()),
+    };
+    if let Err(rollback_error) = rolled_back {
+        // Storage that just failed may well fail again, the user needs to know either way
+        log::error!("Could not undo the failed join of {id}: {rollback_error}");
+    }
+
+    let error = AddMintError::classify(&e);
+    if error.kind == AddMintErrorKind::Database {
+        return Err(e.context(AddMintError {
+            message: format!("Could not save the mint: {}", error.message),
+            ..error
+        }));
     }
+    Err(e)
+}
+
+/// How much a status update needs the user's attention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatusSeverity {
+    /// Progress as expected
+    #[default]
+    Info,
+    /// Something worth knowing about, the operation keeps going regardless
+    Warning,
 }
 
 #[derive(Debug, Clone)]
@@ -181,6 +675,8 @@ pub enum UICoreMsg {
         from: MintIdentifier,
         amount: Amount,
     },
+    /// Claims what a transfer paid to this mint but couldn't issue
+    RetryTransferClaim(MintIdentifier),
     GetFederationInfo(InviteCode),
     GetCashuMintInfo(MintUrl),
     AddFederation(InviteCode),
@@ -196,14 +692,75 @@ pub enum UICoreMsg {
     GetSeedWords,
     SetOnchainReceiveEnabled(bool),
     SetTorEnabled(bool),
+    /// Saves the user's nostr key, `None` removes it
+    SetNostrIdentity(Option<NostrIdentity>),
+    /// Switches the nostr key to the one derived from the wallet seed
+    DeriveNostrIdentity,
+    SetMintNickname {
+        mint: MintIdentifier,
+        nickname: Option<String>,
+    },
+    SetMintNotes {
+        mint: MintIdentifier,
+        notes: Option<String>,
+    },
+    SetMintMaxBalance {
+        mint: MintIdentifier,
+        max_balance: Option<Amount>,
+    },
+    /// Fetches a mint list from `url` to preview, checking its signature when `signer` is given
+    FetchMintList {
+        url: String,
+        signer: Option<String>,
+    },
+    ImportMints(Vec<ExportedMint>),
+    RemoveMints(Vec<MintIdentifier>),
+    GetFederationVersions(FederationId),
+    GetGateways(FederationId),
+    GetMintHistory(MintIdentifier),
+    GetSessionProgress(FederationId),
+    GetMintFees(MintIdentifier),
+    /// Asks every mint what paying this invoice would cost, see [`HarborCore::estimate_send_fees`]
+    EstimateSendFees(Bolt11Invoice),
+    PingGuardians(FederationId),
+    RetryFederationInit(FederationId),
+    RegenerateInviteCode {
+        federation_id: FederationId,
+        preferred_guardian: Option<PeerId>,
+    },
+    ResyncMint(MintIdentifier),
+    /// See [`HarborCore::refresh_mint_balance`]
+    RefreshMintBalance(MintIdentifier),
+    GetMintDiagnostics(MintIdentifier),
+    GetStartupTimings,
+    GetOperationHistory,
+    GetExchangeRate(FiatCurrency),
+    GetPendingAmounts,
+    /// Stops a preview, join or resync started under this id
+    CancelOperation(Uuid),
+    /// Time limits for previews, joins and resyncs started from now on
+    SetOperationTimeouts(OperationTimeouts),
+    /// Holds back the network work Harbor does on its own, see `BackgroundGate`
+    SetReduceBackgroundData(bool),
+    SetPinnedGateway {
+        federation_id: FederationId,
+        gateway_id: Option<String>,
+    },
     TestStatusUpdates,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum SendSuccessMsg {
-    Lightning { preimage: [u8; 32] },
-    Onchain { txid: Txid },
-    Transfer,
+    Lightning {
+        preimage: [u8; 32],
+    },
+    Onchain {
+        txid: Txid,
+    },
+    /// The source's half of a transfer, with what it paid in fees if that's known
+    Transfer {
+        fee: Option<Amount>,
+    },
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -231,11 +788,15 @@ pub enum CoreUIMsg {
     ReceiveFailed(String),
     TransferFailure(String),
     TransactionHistoryUpdated(Vec<TransactionItem>),
+    MintHistoryUpdated {
+        mint: MintIdentifier,
+        history: Vec<TransactionItem>,
+    },
     MintBalanceUpdated {
         id: MintIdentifier,
         balance: Amount,
     },
-    AddMintFailed(String),
+    AddMintFailed(AddMintError),
     RemoveFederationFailed(String),
     MintInfo {
         id: MintIdentifier,
@@ -257,15 +818,80 @@ pub enum CoreUIMsg {
     SeedWords(String),
     OnchainReceiveEnabled(bool),
     TorEnabled(bool),
+    /// The user's nostr key as the UI sees it, `None` once it's removed
+    NostrIdentity(Option<NostrPublicIdentity>),
     InitialProfile {
         seed_words: String,
         onchain_receive_enabled: bool,
         tor_enabled: bool,
+        nostr_identity: Option<NostrPublicIdentity>,
     },
     StatusUpdate {
         message: String,
         operation_id: Option<Uuid>,
+        /// The mint the update is about, when there is one
+        target: Option<MintIdentifier>,
+        severity: StatusSeverity,
+    },
+    MintListFetched(FetchedMintList),
+    MintListFetchFailed(String),
+    MintImportFinished(MintImportSummary),
+    RemoveMintsFinished {
+        removed: usize,
+        failed: Vec<(MintIdentifier, String)>,
+    },
+    FederationVersions {
+        id: FederationId,
+        versions: FederationVersions,
+    },
+    GatewayList {
+        id: FederationId,
+        gateways: Vec<GatewayInfo>,
+        pinned: Option<String>,
+    },
+    SetPinnedGatewayFailed(String),
+    SessionProgress {
+        id: FederationId,
+        progress: SessionProgress,
+    },
+    RecoveryProgress {
+        id: FederationId,
+        state: RecoveryState,
+    },
+    FederationInit {
+        id: FederationId,
+        state: FederationInitState,
+    },
+    MintFees {
+        mint: MintIdentifier,
+        fees: MintFees,
     },
+    SendFeeEstimates(Vec<SendFeeEstimate>),
+    GuardiansPinged(FederationId),
+    InviteCodeRegenerated {
+        federation_id: FederationId,
+        invite_code: InviteCode,
+    },
+    RegenerateInviteCodeFailed(String),
+    ResyncFinished(MintIdentifier),
+    /// A balance refresh finished, with why it didn't work if it didn't
+    MintBalanceRefreshed {
+        mint: MintIdentifier,
+        error: Option<String>,
+    },
+    MintDiagnostics(Box<MintDiagnostics>),
+    MintDiagnosticsFailed(String),
+    StartupTimings(Vec<TimingSpan>),
+    OperationHistory(Vec<OperationRecord>),
+    /// The latest price of bitcoin, `None` when one couldn't be fetched and there's no
+    /// earlier one to fall back to
+    ExchangeRate(Option<ExchangeRate>),
+    /// What's in flight for each mint, mints with nothing pending are left out
+    PendingAmountsUpdated(HashMap<MintIdentifier, PendingAmounts>),
+    /// The operation sent under this message's id was stopped before it finished
+    OperationCancelled,
+    /// Whether the machine is online, sent when that changes
+    Connectivity(bool),
 }
 
 #[derive(Clone)]
@@ -282,6 +908,28 @@ pub struct HarborCore {
     pub stop: Arc<AtomicBool>,
     pub tor_enabled: Arc<AtomicBool>,
     pub metadata_fetch_cancel: Arc<AtomicBool>,
+    /// Mints whose balance isn't settled yet, like a cashu mint that is still restoring
+    pub syncing_mints: Arc<RwLock<HashSet<MintIdentifier>>>,
+    pub guardian_health: Arc<RwLock<HashMap<FederationId, Vec<GuardianHealth>>>>,
+    /// What we've seen of each federation's session count so far
+    pub session_progress: Arc<RwLock<HashMap<FederationId, SessionProgress>>>,
+    /// Federations restoring their ecash from a backup, kept after finishing so failures stick
+    pub recoveries: Arc<RwLock<HashMap<FederationId, RecoveryState>>>,
+    /// Icon URLs already downloaded or tried this session, so a broken one isn't retried on
+    /// every list refresh
+    pub icon_fetches: Arc<RwLock<HashSet<String>>>,
+    /// Active federations whose client hasn't opened yet, or failed to
+    pub federation_init: Arc<RwLock<HashMap<FederationId, FederationInitState>>>,
+    /// How long each step of opening the wallet took, for the settings screen
+    pub startup_timings: StartupTimings,
+    /// The melt quote last asked of each cashu mint for an invoice, so estimating the fee and
+    /// then paying it doesn't leave the mint with a quote per step
+    pub melt_quotes: Arc<RwLock<HashMap<(MintUrl, String), String>>>,
+    pub operations: CancellableOperations,
+    pub status_updates: StatusUpdates,
+    pub exchange_rates: ExchangeRates,
+    pub connectivity: Connectivity,
+    pub background: BackgroundGate,
 }
 
 impl HarborCore {
@@ -297,20 +945,70 @@ impl HarborCore {
         cashu_storage: Arc<WalletSqliteDatabase>,
         stop: Arc<AtomicBool>,
         tor_enabled: Arc<AtomicBool>,
+        startup_timings: StartupTimings,
     ) -> anyhow::Result<Self> {
         // start subscription to pending events
+        Self::resubscribe_pending(
+            &tx,
+            &storage,
+            &*clients.read().await,
+            &*cashu_clients.read().await,
+            None,
+        )
+        .await?;
+
+        Ok(Self {
+            network,
+            mnemonic,
+            data_dir,
+            tx,
+            clients,
+            cashu_clients,
+            storage,
+            cashu_storage,
+            stop,
+            tor_enabled,
+            metadata_fetch_cancel: Arc::new(AtomicBool::new(false)),
+            syncing_mints: Arc::new(RwLock::new(HashSet::new())),
+            guardian_health: Arc::new(RwLock::new(HashMap::new())),
+            session_progress: Arc::new(RwLock::new(HashMap::new())),
+            recoveries: Arc::new(RwLock::new(HashMap::new())),
+            icon_fetches: Arc::new(RwLock::new(HashSet::new())),
+            federation_init: Arc::new(RwLock::new(HashMap::new())),
+            startup_timings,
+            melt_quotes: Arc::new(RwLock::new(HashMap::new())),
+            operations: CancellableOperations::default(),
+            status_updates: StatusUpdates::default(),
+            exchange_rates: ExchangeRates::default(),
+            connectivity: Connectivity::default(),
+            background: BackgroundGate::default(),
+        })
+    }
+
+    // Picks the pending operations back up, either all of them at startup or just the ones
+    // for `only` when resyncing a mint. Returns how many it went through.
+    async fn resubscribe_pending(
+        tx: &Sender<CoreUIMsgPacket>,
+        storage: &Arc<dyn DBConnection + Send + Sync>,
+        fed_clients: &HashMap<FederationId, FedimintClient>,
+        cashus: &HashMap<MintUrl, cdk::Wallet>,
+        only: Option<&MintIdentifier>,
+    ) -> anyhow::Result<usize> {
         let pending_onchain_recv = storage.get_pending_onchain_receives()?;
         let pending_onchain_payments = storage.get_pending_onchain_payments()?;
         let pending_lightning_recv = storage.get_pending_lightning_receives()?;
         let pending_lightning_payments = storage.get_pending_lightning_payments()?;
 
-        let fed = clients.clone();
-        let fed_clients = fed.read().await;
-
-        let cashu = cashu_clients.clone();
-        let cashus = cashu.read().await;
+        // Everything is picked up when there's no `only`
+        let wanted =
+            |mint: Option<MintIdentifier>| only.is_none_or(|only| mint.as_ref() == Some(only));
+        let mut resubscribed = 0;
 
         for item in pending_onchain_recv {
+            if !wanted(item.fedimint_id().map(MintIdentifier::Fedimint)) {
+                continue;
+            }
+            resubscribed += 1;
             if let Some(federation_id) = item.fedimint_id() {
                 if let Some(client) = fed_clients.get(&federation_id) {
                     let onchain = client
@@ -335,6 +1033,10 @@ impl HarborCore {
         }
 
         for item in pending_onchain_payments {
+            if !wanted(item.fedimint_id().map(MintIdentifier::Fedimint)) {
+                continue;
+            }
+            resubscribed += 1;
             if let Some(federation_id) = item.fedimint_id() {
                 if let Some(client) = fed_clients.get(&federation_id) {
                     let onchain = client
@@ -359,6 +1061,10 @@ impl HarborCore {
         }
 
         for item in pending_lightning_recv {
+            if !wanted(Some(item.mint_identifier())) {
+                continue;
+            }
+            resubscribed += 1;
             match item.mint_identifier() {
                 MintIdentifier::Fedimint(federation_id) => {
                     if let Some(client) = fed_clients.get(&federation_id) {
@@ -411,6 +1117,10 @@ impl HarborCore {
         }
 
         for item in pending_lightning_payments {
+            if !wanted(Some(item.mint_identifier())) {
+                continue;
+            }
+            resubscribed += 1;
             match item.mint_identifier() {
                 MintIdentifier::Fedimint(federation_id) => {
                     if let Some(client) = fed_clients.get(&federation_id) {
@@ -474,19 +1184,7 @@ impl HarborCore {
             }
         }
 
-        Ok(Self {
-            network,
-            mnemonic,
-            data_dir,
-            tx,
-            clients,
-            cashu_clients,
-            storage,
-            cashu_storage,
-            stop,
-            tor_enabled,
-            metadata_fetch_cancel: Arc::new(AtomicBool::new(false)),
-        })
+        Ok(resubscribed)
     }
 
     // Initial setup messages that don't have an id
@@ -509,77 +1207,770 @@ impl HarborCore {
             .expect("Could not communicate with the UI");
     }
 
-    // Convenience method for sending status updates
-    pub async fn status_update(&self, id: Uuid, message: &str) {
+    async fn send_status(
+        &self,
+        id: Uuid,
+        target: Option<&MintIdentifier>,
+        severity: StatusSeverity,
+        message: &str,
+    ) {
+        if !self
+            .status_updates
+            .should_send(id, message, severity, target)
+        {
+            return;
+        }
         self.msg(
             id,
             CoreUIMsg::StatusUpdate {
                 message: message.to_string(),
                 operation_id: Some(id),
+                target: target.cloned(),
+                severity,
             },
         )
         .await;
     }
 
-    // Sends updates to the UI to reflect the initial state
-    pub async fn init_ui_state(&self) -> anyhow::Result<()> {
-        let federation_items = self.get_mint_items().await?;
-        self.send_system_msg(CoreUIMsg::MintListUpdated(federation_items))
-            .await;
-
-        for client in self.clients.read().await.values() {
-            let fed_balance = client.fedimint_client.get_balance().await;
-            self.send_system_msg(CoreUIMsg::MintBalanceUpdated {
-                id: MintIdentifier::Fedimint(client.fedimint_client.federation_id()),
-                balance: fed_balance,
-            })
-            .await;
-        }
-
-        for client in self.cashu_clients.read().await.values() {
-            let bal: u64 = client.total_balance().await?.into();
-            self.send_system_msg(CoreUIMsg::MintBalanceUpdated {
-                id: MintIdentifier::Cashu(client.mint_url.clone()),
-                balance: Amount::from_sats(bal),
-            })
+    // Convenience method for sending status updates
+    pub async fn status_update(&self, id: Uuid, message: &str) {
+        self.send_status(id, None, StatusSeverity::Info, message)
             .await;
-        }
+    }
 
-        let history = self.storage.get_transaction_history()?;
-        self.send_system_msg(CoreUIMsg::TransactionHistoryUpdated(history))
+    // A status update about one mint, so the UI can say which when several are busy at once
+    pub async fn mint_status_update(&self, id: Uuid, mint: &MintIdentifier, message: &str) {
+        self.send_status(id, Some(mint), StatusSeverity::Info, message)
             .await;
+    }
 
-        let profile = self.storage.get_profile()?;
-        if let Some(profile) = profile {
-            // Send all profile settings in one message
-            self.send_system_msg(CoreUIMsg::InitialProfile {
-                seed_words: profile.seed_words.clone(),
-                onchain_receive_enabled: profile.onchain_receive_enabled(),
-                tor_enabled: profile.tor_enabled(),
-            })
+    // Tells the user about something that went wrong along the way without failing the operation
+    pub async fn mint_status_warning(&self, id: Uuid, mint: &MintIdentifier, message: &str) {
+        self.send_status(id, Some(mint), StatusSeverity::Warning, message)
             .await;
-        }
+    }
 
-        Ok(())
+    // Probes every guardian of every active federation in the background until the core stops
+    pub fn spawn_guardian_health_checks(&self) {
+        let core = self.clone();
+        spawn(async move {
+            while !core.stop.load(Ordering::Relaxed) {
+                if core.background.allow(BackgroundTask::GuardianHealthChecks) {
+                    core.check_guardian_health().await;
+                }
+                tokio::time::sleep(GUARDIAN_HEALTH_INTERVAL).await;
+            }
+        });
     }
 
-    async fn get_client(&self, federation_id: FederationId) -> FedimintClient {
-        let clients = self.clients.read().await;
-        clients
-            .get(&federation_id)
-            .expect("No client found for federation")
-            .clone()
+    /// Keeps checking the internet connection, telling the UI whenever it comes or goes
+    pub fn spawn_connectivity_checks(&self) {
+        let core = self.clone();
+        spawn(async move {
+            while !core.stop.load(Ordering::Relaxed) {
+                if let Some(online) = core.connectivity.check().await {
+                    core.send_system_msg(CoreUIMsg::Connectivity(online)).await;
+                }
+                tokio::time::sleep(CONNECTIVITY_CHECK_EVERY).await;
+            }
+        });
     }
 
-    async fn get_cashu_client(&self, mint_url: &MintUrl) -> cdk::Wallet {
-        let clients = self.cashu_clients.read().await;
-        clients
-            .get(mint_url)
-            .expect("No client found for mint url")
-            .clone()
+    // Picks up any recoveries left over from the last run
+    pub fn spawn_recovery_watchers(&self) {
+        let core = self.clone();
+        spawn(async move {
+            let recovering: Vec<ClientHandleArc> = core
+                .clients
+                .read()
+                .await
+                .values()
+                .filter(|c| c.is_recovering())
+                .map(|c| c.fedimint_client.clone())
+                .collect();
+            for client in recovering {
+                core.watch_recovery(client);
+            }
+        });
     }
 
-    async fn send_lnv2(
+    // Reports a federation's recovery progress to the UI until it's done, then reopens the client
+    // since its modules only start once every recovery has finished
+    fn watch_recovery(&self, client: ClientHandleArc) {
+        let core = self.clone();
+        spawn(async move {
+            let federation_id = client.federation_id();
+            core.set_recovery_state(federation_id, RecoveryState::NotStarted)
+                .await;
+
+            let progress_core = core.clone();
+            let mut updates = client.subscribe_to_recovery_progress();
+            let progress = spawn(async move {
+                let mut modules = HashMap::new();
+                while let Some((module_id, progress)) = updates.next().await {
+                    modules.insert(module_id, (progress.complete, progress.total));
+                    let (complete, total) = modules
+                        .values()
+                        .fold((0, 0), |(c, t), (module_c, module_t)| {
+                            (c + module_c, t + module_t)
+                        });
+                    progress_core
+                        .set_recovery_state(
+                            federation_id,
+                            RecoveryState::Scanning { complete, total },
+                        )
+                        .await;
+                }
+            });
+
+            let result = client.wait_for_all_recoveries().await;
+            progress.abort();
+
+            let state = match result {
+                Ok(()) => match FedimintClient::new(
+                    core.storage.clone(),
+                    FederationInviteOrId::Id(federation_id),
+                    &core.mnemonic,
+                    core.network,
+                    core.stop.clone(),
+                    core.tx.clone(),
+                    None,
+                )
+                .await
+                {
+                    Ok(reopened) => {
+                        log::info!("Federation {federation_id} successfully recovered");
+                        core.clients.write().await.insert(federation_id, reopened);
+                        RecoveryState::Complete
+                    }
+                    Err(e) => {
+                        error!("Could not reopen federation {federation_id} after recovery: {e}");
+                        RecoveryState::Failed(e.to_string())
+                    }
+                },
+                Err(e) => {
+                    error!("Could not recover federation {federation_id}: {e}");
+                    RecoveryState::Failed(e.to_string())
+                }
+            };
+            core.set_recovery_state(federation_id, state).await;
+
+            // The balance and modules are known now
+            core.send_system_msg(CoreUIMsg::FederationListNeedsUpdate)
+                .await;
+        });
+    }
+
+    /// Opens the clients of the given active federations in the background, a few at a time.
+    /// Until each one is open it's listed from its saved metadata, so a slow or unreachable
+    /// federation holds up neither the others nor the UI.
+    pub async fn open_federations(&self, federation_ids: Vec<FederationId>) {
+        let mut init = self.federation_init.write().await;
+        for id in &federation_ids {
+            init.insert(*id, FederationInitState::Opening);
+        }
+        drop(init);
+
+        // Tell the UI about every one up front, so the list shows a placeholder even for
+        // those still waiting on the concurrency limit
+        for id in &federation_ids {
+            self.send_system_msg(CoreUIMsg::FederationInit {
+                id: *id,
+                state: FederationInitState::Opening,
+            })
+            .await;
+        }
+
+        let core = self.clone();
+        spawn(async move {
+            let started = Instant::now();
+            futures::stream::iter(federation_ids)
+                .for_each_concurrent(FEDERATION_INIT_CONCURRENCY, |id| core.open_federation(id))
+                .await;
+            core.startup_timings.record("All federations", started);
+        });
+    }
+
+    /// Tries again to open a federation whose client failed to open after unlock
+    pub async fn retry_federation_init(&self, federation_id: FederationId) -> anyhow::Result<()> {
+        let mut init = self.federation_init.write().await;
+        if !matches!(
+            init.get(&federation_id),
+            Some(FederationInitState::Failed(_))
+        ) {
+            return Err(anyhow!("This mint isn't waiting to be retried"));
+        }
+        init.insert(federation_id, FederationInitState::Opening);
+        drop(init);
+
+        self.send_system_msg(CoreUIMsg::FederationInit {
+            id: federation_id,
+            state: FederationInitState::Opening,
+        })
+        .await;
+
+        let core = self.clone();
+        spawn(async move { core.open_federation(federation_id).await });
+        Ok(())
+    }
+
+    async fn open_federation(&self, federation_id: FederationId) {
+        let started = Instant::now();
+        let opened = tokio::time::timeout(
+            FEDERATION_INIT_TIMEOUT,
+            FedimintClient::new(
+                self.storage.clone(),
+                FederationInviteOrId::Id(federation_id),
+                &self.mnemonic,
+                self.network,
+                self.stop.clone(),
+                self.tx.clone(),
+                None,
+            ),
+        )
+        .await;
+        let span = format!("Federation {}", short_federation_id(&federation_id));
+
+        let state = match opened {
+            Ok(Ok(client)) => {
+                self.startup_timings.record(span, started);
+                log::info!(
+                    "Opened federation {federation_id} in {}ms",
+                    started.elapsed().as_millis()
+                );
+                if client.is_recovering() {
+                    self.watch_recovery(client.fedimint_client.clone());
+                }
+                self.clients.write().await.insert(federation_id, client);
+
+                // Operations left in flight by the last run couldn't be picked up without it
+                if let Err(e) = Self::resubscribe_pending(
+                    &self.tx,
+                    &self.storage,
+                    &*self.clients.read().await,
+                    &*self.cashu_clients.read().await,
+                    Some(&MintIdentifier::Fedimint(federation_id)),
+                )
+                .await
+                {
+                    error!("Could not pick up pending operations for {federation_id}: {e}");
+                }
+                FederationInitState::Ready
+            }
+            Ok(Err(e)) => {
+                self.startup_timings
+                    .record(format!("{span} (failed)"), started);
+                error!("Could not open federation {federation_id}: {e}");
+                FederationInitState::Failed(e.to_string())
+            }
+            Err(_) => {
+                self.startup_timings
+                    .record(format!("{span} (timed out)"), started);
+                error!(
+                    "Opening federation {federation_id} timed out after {}s",
+                    FEDERATION_INIT_TIMEOUT.as_secs()
+                );
+                FederationInitState::Failed("Timed out connecting to the federation".to_string())
+            }
+        };
+
+        let mut init = self.federation_init.write().await;
+        if state == FederationInitState::Ready {
+            init.remove(&federation_id);
+        } else {
+            init.insert(federation_id, state.clone());
+        }
+        drop(init);
+
+        self.send_system_msg(CoreUIMsg::FederationInit {
+            id: federation_id,
+            state,
+        })
+        .await;
+        // The balance, guardians and modules are known now
+        self.send_system_msg(CoreUIMsg::FederationListNeedsUpdate)
+            .await;
+    }
+
+    async fn set_recovery_state(&self, federation_id: FederationId, state: RecoveryState) {
+        self.recoveries
+            .write()
+            .await
+            .insert(federation_id, state.clone());
+        self.send_system_msg(CoreUIMsg::RecoveryProgress {
+            id: federation_id,
+            state,
+        })
+        .await;
+    }
+
+    // Nothing can be paid or received through a federation before its client is open, or while
+    // it is recovering
+    async fn ensure_ready(&self, federation_id: FederationId) -> anyhow::Result<()> {
+        match self.federation_init.read().await.get(&federation_id) {
+            Some(FederationInitState::Failed(e)) => {
+                return Err(anyhow!("This mint couldn't be opened: {e}"));
+            }
+            Some(FederationInitState::Opening) => {
+                return Err(anyhow!(
+                    "This mint is still connecting, try again in a moment"
+                ));
+            }
+            Some(FederationInitState::Ready) | None => {}
+        }
+
+        match self.recoveries.read().await.get(&federation_id) {
+            Some(RecoveryState::Failed(e)) => Err(anyhow!("Recovering this mint failed: {e}")),
+            Some(state) if state.blocks_sending() => Err(anyhow!(
+                "This mint is still recovering, try again once it has finished"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    // Re-fetches federation meta every so often so changes like a shutdown notice get picked up
+    pub fn spawn_metadata_refresh(&self) {
+        let core = self.clone();
+        spawn(async move {
+            loop {
+                // Startup already fetches anything missing, so wait before the first refresh
+                tokio::time::sleep(METADATA_REFRESH_INTERVAL).await;
+                if core.stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                if !core.background.allow(BackgroundTask::MetadataRefresh) {
+                    continue;
+                }
+
+                // Lets the next list refresh re-download old icons and retry ones that failed
+                core.icon_fetches.write().await.clear();
+
+                let clients: Vec<ClientHandleArc> = core
+                    .clients
+                    .read()
+                    .await
+                    .values()
+                    .map(|c| c.fedimint_client.clone())
+                    .collect();
+                if clients.is_empty() {
+                    continue;
+                }
+
+                Self::update_mint_metadata(
+                    clients,
+                    core.metadata_fetch_cancel.clone(),
+                    core.tor_enabled.load(Ordering::Relaxed),
+                    core.storage.clone(),
+                    core.tx.clone(),
+                )
+                .await;
+            }
+        });
+    }
+
+    async fn check_guardian_health(&self) {
+        // Snapshot the clients so we aren't holding the lock while waiting on guardians
+        let clients: Vec<ClientHandleArc> = self
+            .clients
+            .read()
+            .await
+            .values()
+            .map(|c| c.fedimint_client.clone())
+            .collect();
+
+        if clients.is_empty() {
+            return;
+        }
+
+        // Probes run one at a time to keep the background traffic down
+        for client in clients {
+            self.probe_guardians(&client).await;
+        }
+
+        self.send_system_msg(CoreUIMsg::FederationListNeedsUpdate)
+            .await;
+    }
+
+    // Re-probes one federation's guardians right away, for when the user asks
+    pub async fn ping_guardians(&self, federation_id: FederationId) -> anyhow::Result<()> {
+        let client = self
+            .clients
+            .read()
+            .await
+            .get(&federation_id)
+            .map(|c| c.fedimint_client.clone())
+            .ok_or(anyhow!("Federation not found"))?;
+
+        self.probe_guardians(&client).await;
+        Ok(())
+    }
+
+    /// A fresh invite code for a joined federation, for when the stored one points at a
+    /// guardian that has gone away. It targets `preferred_guardian` if given, otherwise the
+    /// first guardian that answered the last health check.
+    pub async fn regenerate_invite_code(
+        &self,
+        federation_id: FederationId,
+        preferred_guardian: Option<PeerId>,
+    ) -> anyhow::Result<InviteCode> {
+        let client = self
+            .clients
+            .read()
+            .await
+            .get(&federation_id)
+            .map(|c| c.fedimint_client.clone())
+            .ok_or(anyhow!("Federation not found"))?;
+
+        let config = client.config().await;
+        let health = self
+            .guardian_health
+            .read()
+            .await
+            .get(&federation_id)
+            .cloned();
+
+        // Only the original invite code knows about an API secret, so carry it over
+        let api_secret = self
+            .storage
+            .get_fedimint(federation_id)?
+            .and_then(|r| InviteCode::from_str(&r.invite_code).ok())
+            .and_then(|code| code.api_secret());
+
+        invite_code_for_guardian(
+            federation_id,
+            &config.global.api_endpoints,
+            health.as_deref(),
+            preferred_guardian,
+            api_secret,
+        )
+    }
+
+    async fn probe_guardians(&self, client: &ClientHandleArc) {
+        let federation_id = client.federation_id();
+        let previous = self
+            .guardian_health
+            .read()
+            .await
+            .get(&federation_id)
+            .cloned()
+            .unwrap_or_default();
+
+        // Guardians are probed together, so a join checking them waits on the slowest one
+        // rather than all of them in turn
+        let config = client.config().await;
+        let request_timeout = self.operations.timeouts().guardian_request;
+        let previous = &previous;
+        let probes = config
+            .global
+            .api_endpoints
+            .iter()
+            .map(|(peer_id, endpoint)| async move {
+                let probe = client.api().request_single_peer::<serde_json::Value>(
+                    VERSION_ENDPOINT.to_string(),
+                    ApiRequestErased::default(),
+                    *peer_id,
+                );
+                let last_error = match tokio::time::timeout(request_timeout, probe).await {
+                    Ok(Ok(_)) => None,
+                    Ok(Err(e)) => Some(e.to_string()),
+                    Err(_) => Some("Timed out".to_string()),
+                };
+                let online = last_error.is_none();
+
+                // Older guardians don't answer this, a failure here doesn't mean they're down
+                let version = if online {
+                    tokio::time::timeout(request_timeout, client.api().fedimintd_version(*peer_id))
+                        .await
+                        .ok()
+                        .and_then(Result::ok)
+                } else {
+                    None
+                };
+
+                let previous = previous.iter().find(|g| g.name == endpoint.name);
+                let last_contact = if online {
+                    SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .ok()
+                        .map(|d| d.as_secs())
+                } else {
+                    previous.and_then(|g| g.last_contact)
+                };
+
+                let endpoint_host = endpoint.url.host_str().unwrap_or_default().to_string();
+                GuardianHealth {
+                    peer_id: *peer_id,
+                    name: endpoint.name.clone(),
+                    online,
+                    onion: endpoint_host
+                        .rsplit_once('.')
+                        .is_some_and(|(_, tld)| tld.eq_ignore_ascii_case("onion")),
+                    endpoint_host,
+                    last_contact,
+                    version: version.or_else(|| previous.and_then(|g| g.version.clone())),
+                    last_error,
+                }
+            });
+        let health = futures::future::join_all(probes).await;
+
+        self.guardian_health
+            .write()
+            .await
+            .insert(federation_id, health);
+    }
+
+    // Sends updates to the UI to reflect the initial state
+    pub async fn init_ui_state(&self) -> anyhow::Result<()> {
+        let federation_items = self.get_mint_items().await?;
+        self.send_system_msg(CoreUIMsg::MintListUpdated(federation_items))
+            .await;
+
+        for client in self.clients.read().await.values() {
+            // The balance isn't known until recovery finishes
+            if client.is_recovering() {
+                continue;
+            }
+            let fed_balance = client.fedimint_client.get_balance().await;
+            self.send_system_msg(CoreUIMsg::MintBalanceUpdated {
+                id: MintIdentifier::Fedimint(client.fedimint_client.federation_id()),
+                balance: fed_balance,
+            })
+            .await;
+        }
+
+        for client in self.cashu_clients.read().await.values() {
+            let bal: u64 = client.total_balance().await?.into();
+            self.send_system_msg(CoreUIMsg::MintBalanceUpdated {
+                id: MintIdentifier::Cashu(client.mint_url.clone()),
+                balance: Amount::from_sats(bal),
+            })
+            .await;
+        }
+
+        let history = self.storage.get_transaction_history(None)?;
+        self.send_system_msg(CoreUIMsg::TransactionHistoryUpdated(history))
+            .await;
+
+        let profile = self.storage.get_profile()?;
+        if let Some(profile) = profile {
+            // Send all profile settings in one message
+            self.send_system_msg(CoreUIMsg::InitialProfile {
+                seed_words: profile.seed_words.clone(),
+                onchain_receive_enabled: profile.onchain_receive_enabled(),
+                tor_enabled: profile.tor_enabled(),
+                nostr_identity: profile.nostr_public_identity(),
+            })
+            .await;
+        }
+
+        Ok(())
+    }
+
+    async fn get_client(&self, federation_id: FederationId) -> FedimintClient {
+        let clients = self.clients.read().await;
+        clients
+            .get(&federation_id)
+            .expect("No client found for federation")
+            .clone()
+    }
+
+    // The user's pinned gateway for this federation, if it's still registered.
+    // Falls back to None, with a notice on `msg_id` if there is one, so the caller can pick
+    // one automatically.
+    async fn pinned_gateway(
+        &self,
+        msg_id: Option<Uuid>,
+        client: &ClientHandleArc,
+    ) -> Option<LightningGateway> {
+        let target = MintIdentifier::Fedimint(client.federation_id());
+        let pinned = self
+            .storage
+            .get_fedimint(client.federation_id())
+            .ok()
+            .flatten()?
+            .pinned_gateway?;
+
+        let gateway = match (
+            PublicKey::from_str(&pinned),
+            client.get_first_module::<LightningClientModule>(),
+        ) {
+            (Ok(gateway_id), Ok(ln)) => ln.select_gateway(&gateway_id).await,
+            _ => None,
+        };
+
+        if gateway.is_none() {
+            log::warn!("Pinned gateway {pinned} is no longer available");
+            if let Some(msg_id) = msg_id {
+                self.mint_status_warning(
+                    msg_id,
+                    &target,
+                    "Pinned gateway is unavailable, choosing one automatically",
+                )
+                .await;
+            }
+        }
+
+        gateway
+    }
+
+    /// What paying `invoice` would cost from each joined mint. The mints are asked all at
+    /// once, and one that doesn't answer in time gets an error instead of a fee.
+    ///
+    /// Cashu mints only tell the fee through a melt quote, which they store until it expires.
+    /// Sending the invoice from one of them afterwards pays with that same quote.
+    pub async fn estimate_send_fees(
+        &self,
+        invoice: &Bolt11Invoice,
+    ) -> anyhow::Result<Vec<SendFeeEstimate>> {
+        let amount = Amount::from_msats(
+            invoice
+                .amount_milli_satoshis()
+                .ok_or(anyhow!("Invoice must have an amount"))?,
+        );
+
+        let mut mints: Vec<MintIdentifier> = self
+            .clients
+            .read()
+            .await
+            .keys()
+            .map(|id| MintIdentifier::Fedimint(*id))
+            .collect();
+        mints.extend(
+            self.cashu_clients
+                .read()
+                .await
+                .keys()
+                .map(|url| MintIdentifier::Cashu(url.clone())),
+        );
+
+        let estimates = probe_send_fees(
+            mints,
+            amount,
+            SEND_FEE_ESTIMATE_TIMEOUT,
+            |mint| async move { self.estimate_send_fee(&mint, invoice, amount).await },
+        )
+        .await;
+        Ok(estimates)
+    }
+
+    // The fee for paying `invoice` from `mint` the way a send would, and the mint's balance
+    async fn estimate_send_fee(
+        &self,
+        mint: &MintIdentifier,
+        invoice: &Bolt11Invoice,
+        amount: Amount,
+    ) -> anyhow::Result<(Amount, Amount)> {
+        match mint {
+            MintIdentifier::Fedimint(federation_id) => {
+                self.ensure_ready(*federation_id).await?;
+                let client = self.get_client(*federation_id).await.fedimint_client;
+                let balance = client.get_balance().await;
+
+                // Same order as a send: LNv2 first, LNv1 for pinned gateways or if LNv2 can't
+                let pinned_gateway = self.pinned_gateway(None, &client).await;
+                if pinned_gateway.is_none() {
+                    match lnv2_send_fee(&client, invoice, amount).await {
+                        Ok(fee) => return Ok((fee, balance)),
+                        Err(e) => log::debug!("No LNv2 quote, quoting LNv1 instead. {e}"),
+                    }
+                }
+                let gateway = match pinned_gateway {
+                    Some(gateway) => gateway,
+                    None => select_gateway(&client)
+                        .await
+                        .ok_or(anyhow!("No gateway found for federation"))?,
+                };
+                Ok((gateway.fees.to_amount(&amount), balance))
+            }
+            MintIdentifier::Cashu(mint_url) => {
+                let client = self.get_cashu_client(mint_url).await;
+                let quote = self.cashu_melt_quote(mint_url, &client, invoice).await?;
+                let balance: u64 = client.total_balance().await?.into();
+                Ok((
+                    Amount::from_sats(quote.fee_reserve.into()),
+                    Amount::from_sats(balance),
+                ))
+            }
+        }
+    }
+
+    // A melt quote for paying `invoice` from the cashu mint at `mint_url`. Each quote asked for
+    // is kept by the mint, so the one from an earlier estimate is reused while it's still open.
+    async fn cashu_melt_quote(
+        &self,
+        mint_url: &MintUrl,
+        client: &cdk::Wallet,
+        invoice: &Bolt11Invoice,
+    ) -> anyhow::Result<MeltQuote> {
+        let key = (mint_url.clone(), invoice.to_string());
+        let previous = self.melt_quotes.read().await.get(&key).cloned();
+        if let Some(id) = previous {
+            let reuse_until =
+                SystemTime::now().duration_since(UNIX_EPOCH)? + MELT_QUOTE_REUSE_MARGIN;
+            if let Some(quote) = client.localstore.get_melt_quote(&id).await? {
+                if quote.state == MeltQuoteState::Unpaid && quote.expiry > reuse_until.as_secs() {
+                    return Ok(quote);
+                }
+            }
+        }
+
+        let quote = client.melt_quote(key.1.clone(), None).await?;
+        self.melt_quotes.write().await.insert(key, quote.id.clone());
+        Ok(quote)
+    }
+
+    pub async fn get_gateways(
+        &self,
+        federation_id: FederationId,
+    ) -> anyhow::Result<(Vec<GatewayInfo>, Option<String>)> {
+        self.ensure_ready(federation_id).await?;
+        let client = self.get_client(federation_id).await.fedimint_client;
+        let ln = client.get_first_module::<LightningClientModule>()?;
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let gateways = ln
+            .list_gateways()
+            .await
+            .into_iter()
+            .map(|gateway| GatewayInfo {
+                gateway_id: gateway.info.gateway_id.to_string(),
+                alias: gateway.info.lightning_alias.clone(),
+                base_msat: gateway.info.fees.base_msat,
+                proportional_millionths: gateway.info.fees.proportional_millionths,
+                vetted: gateway.vetted,
+                supports_private_payments: gateway.info.supports_private_payments,
+                registered_until: (now + gateway.ttl).as_secs(),
+            })
+            .collect();
+
+        let pinned = self
+            .storage
+            .get_fedimint(federation_id)?
+            .and_then(|r| r.pinned_gateway);
+
+        Ok((gateways, pinned))
+    }
+
+    pub fn set_pinned_gateway(
+        &self,
+        federation_id: FederationId,
+        gateway_id: Option<String>,
+    ) -> anyhow::Result<()> {
+        if let Some(gateway_id) = &gateway_id {
+            PublicKey::from_str(gateway_id)?;
+        }
+        self.storage.set_pinned_gateway(federation_id, gateway_id)
+    }
+
+    async fn get_cashu_client(&self, mint_url: &MintUrl) -> cdk::Wallet {
+        let clients = self.cashu_clients.read().await;
+        clients
+            .get(mint_url)
+            .expect("No client found for mint url")
+            .clone()
+    }
+
+    async fn send_lnv2(
         &self,
         client: &ClientHandleArc,
         msg_id: Uuid,
@@ -632,18 +2023,20 @@ impl HarborCore {
         invoice: Bolt11Invoice,
         is_transfer: bool,
     ) -> anyhow::Result<()> {
+        let target = MintIdentifier::Cashu(mint_url.clone());
         log::info!("Paying lightning invoice: {invoice} from cashu mint: {mint_url}");
         let amount = Amount::from_msats(invoice.amount_milli_satoshis().expect("must have amount"));
 
         let client = self.get_cashu_client(&mint_url).await;
 
-        self.status_update(msg_id, "Getting quote").await;
+        self.mint_status_update(msg_id, &target, "Getting quote")
+            .await;
 
-        let quote = client.melt_quote(invoice.to_string(), None).await?;
+        let quote = self.cashu_melt_quote(&mint_url, &client, &invoice).await?;
 
         log::info!("Sending lightning invoice: {invoice}");
 
-        self.status_update(msg_id, "Creating payment transaction")
+        self.mint_status_update(msg_id, &target, "Creating payment transaction")
             .await;
 
         self.storage.create_lightning_payment(
@@ -664,7 +2057,7 @@ impl HarborCore {
             is_transfer,
         );
 
-        self.status_update(msg_id, "Waiting for payment confirmation")
+        self.mint_status_update(msg_id, &target, "Waiting for payment confirmation")
             .await;
 
         log::info!("Payment sent");
@@ -679,13 +2072,21 @@ impl HarborCore {
         invoice: Bolt11Invoice,
         is_transfer: bool,
     ) -> anyhow::Result<()> {
+        let target = MintIdentifier::Fedimint(federation_id);
         log::info!("Paying lightning invoice: {invoice} from federation: {federation_id}");
         let amount = Amount::from_msats(invoice.amount_milli_satoshis().expect("must have amount"));
 
+        self.ensure_ready(federation_id).await?;
         let client = self.get_client(federation_id).await.fedimint_client;
+        let pinned_gateway = self.pinned_gateway(Some(msg_id), &client).await;
 
-        // Try sending using LNv2 first, if that doesn't work fall back to using LNv1
-        match self.send_lnv2(&client, msg_id, invoice.clone()).await {
+        // Try sending using LNv2 first, if that doesn't work fall back to using LNv1.
+        // Pinned gateways are LNv1 gateways, so those go straight to LNv1.
+        let lnv2 = match pinned_gateway {
+            Some(_) => Err(anyhow!("Using the pinned gateway")),
+            None => self.send_lnv2(&client, msg_id, invoice.clone()).await,
+        };
+        match lnv2 {
             Ok(operation_id) => {
                 let lnv2_module = client
                     .get_first_module::<fedimint_lnv2_client::LightningClientModule>()
@@ -735,12 +2136,15 @@ impl HarborCore {
                     .get_first_module::<LightningClientModule>()
                     .expect("must have ln module");
 
-                self.status_update(msg_id, "Selecting gateway and calculating fees")
+                self.mint_status_update(msg_id, &target, "Selecting gateway and calculating fees")
                     .await;
 
-                let gateway = select_gateway(&client)
-                    .await
-                    .ok_or(anyhow!("Internal error: No gateway found for federation"))?;
+                let gateway = match pinned_gateway {
+                    Some(gateway) => gateway,
+                    None => select_gateway(&client)
+                        .await
+                        .ok_or(anyhow!("Internal error: No gateway found for federation"))?,
+                };
 
                 let fees = gateway.fees.to_amount(&amount);
                 let total = fees + amount;
@@ -756,14 +2160,14 @@ impl HarborCore {
                 log::info!("Sending lightning invoice: {invoice}, paying fees: {fees}");
 
                 // Send another update
-                self.status_update(msg_id, "Creating payment transaction")
+                self.mint_status_update(msg_id, &target, "Creating payment transaction")
                     .await;
 
                 let outgoing = lightning_module
                     .pay_bolt11_invoice(Some(gateway), invoice.clone(), ())
                     .await?;
 
-                self.status_update(msg_id, "Waiting for payment confirmation")
+                self.mint_status_update(msg_id, &target, "Waiting for payment confirmation")
                     .await;
 
                 self.storage.create_lightning_payment(
@@ -817,19 +2221,21 @@ impl HarborCore {
         lnurl: LnUrl,
         amount_sats: u64,
     ) -> anyhow::Result<()> {
-        self.status_update(msg_id, "Starting LNURL-pay flow").await;
+        let target = mint_identifier.clone();
+        self.mint_status_update(msg_id, &target, "Starting LNURL-pay flow")
+            .await;
 
         log::info!("Sending lnurl pay: {lnurl} from mint: {mint_identifier:?}");
 
         let tor_enabled = self.tor_enabled.load(Ordering::Relaxed);
-        self.status_update(msg_id, "Fetching payment details from recipient")
+        self.mint_status_update(msg_id, &target, "Fetching payment details from recipient")
             .await;
 
         let pay_response =
             make_lnurl_request(&lnurl, tor_enabled, self.metadata_fetch_cancel.clone()).await?;
         log::info!("Pay response: {pay_response:?}");
 
-        self.status_update(msg_id, "Requesting invoice from recipient")
+        self.mint_status_update(msg_id, &target, "Requesting invoice from recipient")
             .await;
 
         let amount_msats = amount_sats * 1000;
@@ -907,13 +2313,22 @@ impl HarborCore {
         amount: Amount,
         is_transfer: bool,
     ) -> anyhow::Result<Bolt11Invoice> {
+        let target = MintIdentifier::Fedimint(federation_id);
         let tor_enabled = self.tor_enabled.load(Ordering::Relaxed);
         log::info!(
             "Creating lightning invoice, amount: {amount} for federation: {federation_id}. Tor enabled: {tor_enabled}"
         );
 
+        self.ensure_ready(federation_id).await?;
         let client = self.get_client(federation_id).await.fedimint_client;
-        match self.receive_lnv2(&client, msg_id, amount).await {
+        let pinned_gateway = self.pinned_gateway(Some(msg_id), &client).await;
+
+        // Pinned gateways are LNv1 gateways, so those skip straight to LNv1
+        let lnv2 = match pinned_gateway {
+            Some(_) => Err(anyhow!("Using the pinned gateway")),
+            None => self.receive_lnv2(&client, msg_id, amount).await,
+        };
+        match lnv2 {
             Ok((invoice, operation_id)) => {
                 let operation = client
                     .operation_log()
@@ -961,21 +2376,27 @@ impl HarborCore {
             }
             Err(err) => {
                 log::warn!("LNv2 invoice generation failed, trying LNv1. {err}");
-                self.status_update(msg_id, "Connecting to mint").await;
+                self.mint_status_update(msg_id, &target, "Connecting to mint")
+                    .await;
 
                 let lightning_module = client
                     .get_first_module::<LightningClientModule>()
                     .expect("must have ln module");
                 log::info!("Lightning module: {:?}", lightning_module.id);
 
-                self.status_update(msg_id, "Selecting gateway").await;
+                self.mint_status_update(msg_id, &target, "Selecting gateway")
+                    .await;
 
-                let gateway = select_gateway(&client)
-                    .await
-                    .ok_or(anyhow!("Internal error: No gateway found for federation"))?;
+                let gateway = match pinned_gateway {
+                    Some(gateway) => gateway,
+                    None => select_gateway(&client)
+                        .await
+                        .ok_or(anyhow!("Internal error: No gateway found for federation"))?,
+                };
                 log::info!("Gateway: {gateway:?}");
 
-                self.status_update(msg_id, "Generating invoice").await;
+                self.mint_status_update(msg_id, &target, "Generating invoice")
+                    .await;
 
                 let desc = Description::new(String::new()).expect("empty string is valid");
                 let (op_id, invoice, _) = lightning_module
@@ -1030,16 +2451,19 @@ impl HarborCore {
         amount: Amount,
         is_transfer: bool,
     ) -> anyhow::Result<Bolt11Invoice> {
+        let target = MintIdentifier::Cashu(mint.clone());
         let tor_enabled = self.tor_enabled.load(Ordering::Relaxed);
         log::info!(
             "Creating lightning invoice, amount: {amount} for mint: {mint}. Tor enabled: {tor_enabled}"
         );
 
-        self.status_update(msg_id, "Connecting to mint").await;
+        self.mint_status_update(msg_id, &target, "Connecting to mint")
+            .await;
 
         let client = self.get_cashu_client(&mint).await;
 
-        self.status_update(msg_id, "Generating invoice").await;
+        self.mint_status_update(msg_id, &target, "Generating invoice")
+            .await;
 
         let quote = client
             .mint_quote(cdk::Amount::from(amount.msats / 1000), None)
@@ -1078,18 +2502,67 @@ impl HarborCore {
     ) -> anyhow::Result<()> {
         log::info!("Transferring {amount} from {from:?} to {to:?}");
 
-        self.status_update(msg_id, "Generating invoice on destination mint")
+        self.mint_status_update(msg_id, &to, "Generating invoice on destination mint")
             .await;
 
         let invoice = self.receive_lightning(msg_id, to, amount, true).await?;
 
-        self.status_update(msg_id, "Paying invoice from source mint")
+        self.mint_status_update(msg_id, &from, "Paying invoice from source mint")
             .await;
+        self.msg(msg_id, CoreUIMsg::Sending).await;
 
+        // Each leg reports how it went under `msg_id`, see `transfer::TransferState`
         self.send_lightning(msg_id, from, invoice, true).await?;
         Ok(())
     }
 
+    /// Issues anything that was paid to a cashu mint but never claimed, like the second half
+    /// of a transfer that failed after the source paid. Returns how much was claimed.
+    pub async fn claim_paid_receives(
+        &self,
+        msg_id: Uuid,
+        mint: MintIdentifier,
+    ) -> anyhow::Result<Amount> {
+        let MintIdentifier::Cashu(mint_url) = &mint else {
+            return Err(anyhow!(
+                "Federations claim their receives on their own, there's nothing to retry"
+            ));
+        };
+        let client = self.get_cashu_client(mint_url).await;
+
+        self.mint_status_update(msg_id, &mint, "Checking for paid invoices")
+            .await;
+
+        let before: u64 = client.total_balance().await?.into();
+        let quotes = client.localstore.get_mint_quotes().await?;
+        for quote in quotes.into_iter().filter(|q| &q.mint_url == mint_url) {
+            let state = client.mint_quote_state(&quote.id).await?;
+            if state.state != MintQuoteState::Paid {
+                continue;
+            }
+
+            self.mint_status_update(msg_id, &mint, "Claiming paid invoice")
+                .await;
+            client.mint(&quote.id, SplitTarget::default(), None).await?;
+
+            if let Err(e) = self.storage.mark_ln_receive_as_success(quote.id) {
+                log::error!("Could not mark lightning receive as success: {e}");
+            }
+        }
+
+        let balance: u64 = client.total_balance().await?.into();
+        self.msg(
+            msg_id,
+            CoreUIMsg::MintBalanceUpdated {
+                id: mint,
+                balance: Amount::from_sats(balance),
+            },
+        )
+        .await;
+
+        Ok(Amount::from_sats(balance.saturating_sub(before)))
+    }
+
     /// Sends a given amount of sats to a given address, if the amount is None, send all funds
     pub async fn send_onchain(
         &self,
@@ -1105,6 +2578,7 @@ impl HarborCore {
         log::info!(
             "Sending onchain payment to address: {address} from federation: {federation_id}",
         );
+        self.ensure_ready(federation_id).await?;
         let client = self.get_client(federation_id).await.fedimint_client;
         let onchain = client
             .get_first_module::<WalletClientModule>()
@@ -1183,6 +2657,7 @@ impl HarborCore {
         msg_id: Uuid,
         federation_id: FederationId,
     ) -> anyhow::Result<Address> {
+        let target = MintIdentifier::Fedimint(federation_id);
         // check if on-chain receive is enabled
         let profile = self.storage.get_profile()?;
         if profile.is_none() || !profile.unwrap().onchain_receive_enabled() {
@@ -1191,14 +2666,17 @@ impl HarborCore {
 
         log::info!("Generating address for federation: {federation_id}");
 
-        self.status_update(msg_id, "Connecting to mint").await;
+        self.mint_status_update(msg_id, &target, "Connecting to mint")
+            .await;
 
+        self.ensure_ready(federation_id).await?;
         let client = self.get_client(federation_id).await.fedimint_client;
         let onchain = client
             .get_first_module::<WalletClientModule>()
             .expect("must have wallet module");
 
-        self.status_update(msg_id, "Generating address").await;
+        self.mint_status_update(msg_id, &target, "Generating address")
+            .await;
 
         let (op_id, address, _) = onchain.allocate_deposit_address_expert_only(()).await?;
 
@@ -1224,14 +2702,37 @@ impl HarborCore {
         Ok(address)
     }
 
+    // Nothing can be reached while offline, and onion services can't be reached at all without
+    // Tor, so don't bother trying
+    fn check_reachable(&self, info: &MintConnectionInfo) -> anyhow::Result<()> {
+        if !self.connectivity.is_online() {
+            return Err(AddMintError::new(
+                AddMintErrorKind::Offline,
+                "You appear to be offline, check your internet connection",
+            )
+            .into());
+        }
+        if info.requires_tor() && !self.tor_enabled.load(Ordering::Relaxed) {
+            return Err(AddMintError::new(
+                AddMintErrorKind::NetworkUnreachable,
+                "This mint is only reachable over Tor, turn on Tor in Settings",
+            )
+            .into());
+        }
+        Ok(())
+    }
+
     pub async fn get_cashu_mint_info(
         &self,
         msg_id: Uuid,
         mint_url: MintUrl,
     ) -> anyhow::Result<Option<MintInfo>> {
+        let target = MintIdentifier::Cashu(mint_url.clone());
         log::info!("Getting cashu mint info for: {mint_url}");
+        self.check_reachable(&MintConnectionInfo::Cashu(mint_url.clone()))?;
 
-        self.status_update(msg_id, "Connecting to mint").await;
+        self.mint_status_update(msg_id, &target, "Connecting to mint")
+            .await;
 
         let seed = self.mnemonic.to_seed_normalized("");
 
@@ -1252,11 +2753,13 @@ impl HarborCore {
 
         let wallet = builder.build()?;
 
-        self.status_update(msg_id, "Retrieving mint metadata").await;
+        self.mint_status_update(msg_id, &target, "Retrieving mint metadata")
+            .await;
 
         let info = wallet.get_mint_info().await?;
 
-        self.status_update(msg_id, "Checking mint network").await;
+        self.mint_status_update(msg_id, &target, "Checking mint network")
+            .await;
 
         let quote = wallet.mint_quote(cdk::Amount::ONE, None).await?;
         let invoice = Bolt11Invoice::from_str(&quote.request)?;
@@ -1283,8 +2786,79 @@ impl HarborCore {
         invite_code: InviteCode,
     ) -> anyhow::Result<(ClientConfig, FederationMeta)> {
         log::info!("Getting federation info for invite code: {invite_code}");
+        self.check_reachable(&MintConnectionInfo::Fedimint(invite_code.clone()))?;
+
+        // A federation previewed before shows up right away and is refreshed behind the preview
+        let federation_id = invite_code.federation_id();
+        if let Some((config, metadata)) = self.cached_federation_info(federation_id) {
+            self.check_federation_network(&config)?;
+
+            let core = self.clone();
+            spawn(async move {
+                match core.download_federation_info(msg_id, &invite_code).await {
+                    Ok((config, metadata)) => {
+                        core.msg(
+                            msg_id,
+                            CoreUIMsg::MintInfo {
+                                id: MintIdentifier::Fedimint(federation_id),
+                                config: Some(config),
+                                metadata,
+                            },
+                        )
+                        .await;
+                    }
+                    Err(e) => {
+                        log::warn!("Could not refresh preview of federation {federation_id}: {e}");
+                    }
+                }
+            });
+
+            return Ok((config, metadata));
+        }
+
+        self.download_federation_info(msg_id, &invite_code).await
+    }
+
+    // The config and metadata saved the last time this federation was previewed
+    fn cached_federation_info(
+        &self,
+        federation_id: FederationId,
+    ) -> Option<(ClientConfig, FederationMeta)> {
+        let preview = match self.storage.get_federation_preview(federation_id) {
+            Ok(preview) => preview?,
+            Err(e) => {
+                log::warn!("Could not read preview cache: {e}");
+                return None;
+            }
+        };
 
-        self.status_update(msg_id, "Connecting to mint").await;
+        let config = ClientConfig::consensus_decode_whole(
+            &preview.config,
+            &ModuleDecoderRegistry::default(),
+        )
+        .inspect_err(|e| log::warn!("Could not decode cached config of {federation_id}: {e}"))
+        .ok()
+        // The ID is derived from the config, so a mismatch means the row is corrupt
+        .filter(|config| config.calculate_federation_id() == federation_id)?;
+
+        let metadata = self
+            .storage
+            .get_federation_metadata(federation_id)
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+
+        Some((config, metadata))
+    }
+
+    async fn download_federation_info(
+        &self,
+        msg_id: Uuid,
+        invite_code: &InviteCode,
+    ) -> anyhow::Result<(ClientConfig, FederationMeta)> {
+        let target = MintIdentifier::Fedimint(invite_code.federation_id());
+        self.mint_status_update(msg_id, &target, "Connecting to mint")
+            .await;
 
         let tor_enabled = self.tor_enabled.load(Ordering::Relaxed);
         let download = Instant::now();
@@ -1295,7 +2869,7 @@ impl HarborCore {
                 fedimint_api_client::api::net::Connector::Tcp
             };
             connector
-                .download_from_invite_code(&invite_code)
+                .download_from_invite_code(invite_code)
                 .await
                 .map_err(|e| {
                     error!("Could not download federation info: {e}");
@@ -1307,24 +2881,515 @@ impl HarborCore {
             download.elapsed().as_millis()
         );
 
-        self.status_update(msg_id, "Retrieving mint metadata").await;
+        // Invite codes don't say which network a federation is on, so this is the first chance
+        // to catch a mismatch, well before joining
+        self.mint_status_update(msg_id, &target, "Checking mint network")
+            .await;
+        self.check_federation_network(&config)?;
+
+        self.mint_status_update(msg_id, &target, "Retrieving mint metadata")
+            .await;
 
-        let mut cache = CACHE.write().await;
-        let metadata = match cache.get(&invite_code.federation_id()).cloned() {
-            None => {
-                let m = get_federation_metadata(
-                    FederationData::Config(&config),
-                    tor_enabled,
+        let federation_id = invite_code.federation_id();
+        let metadata = get_federation_metadata(
+            FederationData::Config(&config),
+            tor_enabled,
+            self.metadata_fetch_cancel.clone(),
+        )
+        .await;
+        CACHE.write().await.insert(federation_id, metadata.clone());
+
+        // Failing to cache only costs a slower preview next time
+        if let Err(e) = self
+            .storage
+            .upsert_federation_preview(federation_id, config.consensus_encode_to_vec())
+            .and_then(|()| {
+                self.storage
+                    .upsert_federation_metadata(federation_id, metadata.clone())
+            })
+        {
+            log::warn!("Could not cache preview of federation {federation_id}: {e}");
+        }
+
+        Ok((config, metadata))
+    }
+
+    fn check_federation_network(&self, config: &ClientConfig) -> anyhow::Result<()> {
+        if let Some(network) = federation_network(config) {
+            if network != self.network {
+                error!(
+                    "Fedimint on different network {network}, expected: {}",
+                    self.network
+                );
+
+                return Err(AddMintError::new(
+                    AddMintErrorKind::WrongNetwork,
+                    format!("Network mismatch, expected: {}", self.network),
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs an operation through [`CancellableOperations::run`], keeping a record of how it
+    /// went for the activity log. `target` is what it was started with, so it can be tried
+    /// again, and `mint` is what it's shown as.
+    pub async fn run_recorded<T>(
+        &self,
+        id: Uuid,
+        kind: OperationKind,
+        target: String,
+        mint: String,
+        operation: impl Future<Output = anyhow::Result<T>>,
+    ) -> Result<anyhow::Result<T>, Aborted> {
+        // The operation matters more than its record, so it goes ahead either way
+        if let Err(e) = self.storage.start_operation_record(id, kind, target, mint) {
+            log::warn!("Could not record operation {id}: {e}");
+        }
+
+        let result = self.operations.run(id, kind, operation).await;
+
+        let (outcome, error_kind) = match &result {
+            Err(Aborted) => (OperationOutcome::Cancelled, None),
+            Ok(Err(e)) => (
+                OperationOutcome::Failed,
+                Some(AddMintError::classify(e).kind),
+            ),
+            Ok(Ok(_)) => (OperationOutcome::Succeeded, None),
+        };
+        if let Err(e) = self
+            .storage
+            .finish_operation_record(id, outcome, error_kind)
+        {
+            log::warn!("Could not record how operation {id} ended: {e}");
+        }
+        result
+    }
+
+    /// Anything the activity log still has as running was cut short when Harbor last closed
+    pub fn interrupt_unfinished_operations(&self) {
+        match self.storage.interrupt_running_operations() {
+            Ok(0) => {}
+            Ok(interrupted) => {
+                log::info!("Marked {interrupted} unfinished operations as interrupted");
+            }
+            Err(e) => log::warn!("Could not mark unfinished operations as interrupted: {e}"),
+        }
+    }
+
+    /// Forgets previews that haven't been looked at in [`PREVIEW_CACHE_TTL`]
+    pub fn prune_preview_cache(&self) {
+        match self.storage.prune_federation_previews(PREVIEW_CACHE_TTL) {
+            Ok(0) => {}
+            Ok(pruned) => log::info!("Pruned {pruned} old federation previews"),
+            Err(e) => log::warn!("Could not prune federation previews: {e}"),
+        }
+    }
+
+    pub async fn get_federation_versions(
+        &self,
+        federation_id: FederationId,
+    ) -> anyhow::Result<FederationVersions> {
+        let client = self
+            .clients
+            .read()
+            .await
+            .get(&federation_id)
+            .map(|c| c.fedimint_client.clone())
+            .ok_or(anyhow!("Federation not found"))?;
+
+        // Ask the guardians again so an upgrade since joining shows up,
+        // falling back to the config we saved when we joined
+        let downloaded = self.download_federation_config(federation_id).await?;
+        let fresh = downloaded.is_some();
+        let config = match downloaded {
+            Some(config) => config,
+            None => client.config().await,
+        };
+
+        let consensus = config.global.consensus_version;
+        let modules = config
+            .modules
+            .into_values()
+            .map(|module| ModuleVersion {
+                kind: module.kind().to_owned(),
+                version: format!("{}.{}", module.version.major, module.version.minor),
+            })
+            .collect();
+
+        Ok(FederationVersions {
+            consensus_version: format!("{}.{}", consensus.major, consensus.minor),
+            modules,
+            consensus_unsupported: (consensus.major, consensus.minor)
+                > (
+                    SUPPORTED_CORE_CONSENSUS_VERSION.major,
+                    SUPPORTED_CORE_CONSENSUS_VERSION.minor,
+                ),
+            fresh,
+        })
+    }
+
+    // The federation's current config straight from its guardians, `None` if they can't be reached
+    async fn download_federation_config(
+        &self,
+        federation_id: FederationId,
+    ) -> anyhow::Result<Option<ClientConfig>> {
+        let Some(invite_code) = self
+            .storage
+            .get_fedimint(federation_id)?
+            .and_then(|r| InviteCode::from_str(&r.invite_code).ok())
+        else {
+            return Ok(None);
+        };
+
+        let connector = if self.tor_enabled.load(Ordering::Relaxed) {
+            fedimint_api_client::api::net::Connector::Tor
+        } else {
+            fedimint_api_client::api::net::Connector::Tcp
+        };
+        Ok(connector
+            .download_from_invite_code(&invite_code)
+            .await
+            .map_err(|e| error!("Could not download federation config: {e}"))
+            .ok())
+    }
+
+    // Knocks a wedged mint client back into shape by refetching what it knows about the mint
+    // and picking its pending operations back up. Only this mint is touched.
+    pub async fn resync_mint(&self, msg_id: Uuid, mint: MintIdentifier) -> anyhow::Result<()> {
+        match &mint {
+            MintIdentifier::Fedimint(federation_id) => {
+                self.ensure_ready(*federation_id).await?;
+                let client = self
+                    .clients
+                    .read()
+                    .await
+                    .get(federation_id)
+                    .map(|c| c.fedimint_client.clone())
+                    .ok_or(anyhow!("Federation not found"))?;
+
+                self.mint_status_update(msg_id, &mint, "Fetching federation config")
+                    .await;
+                match self.download_federation_config(*federation_id).await? {
+                    Some(config) if config != client.config().await => {
+                        log::warn!("Federation {federation_id} config changed since joining");
+                        self.mint_status_warning(
+                            msg_id,
+                            &mint,
+                            "The federation's config has changed since you joined",
+                        )
+                        .await;
+                    }
+                    Some(_) => {}
+                    None => {
+                        self.mint_status_warning(
+                            msg_id,
+                            &mint,
+                            "Couldn't reach the guardians for the config, carrying on",
+                        )
+                        .await;
+                    }
+                }
+
+                self.mint_status_update(msg_id, &mint, "Refreshing federation metadata")
+                    .await;
+                Self::update_mint_metadata(
+                    vec![client.clone()],
                     self.metadata_fetch_cancel.clone(),
+                    self.tor_enabled.load(Ordering::Relaxed),
+                    self.storage.clone(),
+                    self.tx.clone(),
                 )
                 .await;
-                cache.insert(invite_code.federation_id(), m.clone());
-                m
+
+                self.mint_status_update(msg_id, &mint, "Registering with gateways")
+                    .await;
+                client
+                    .get_first_module::<LightningClientModule>()?
+                    .update_gateway_cache()
+                    .await?;
+            }
+            MintIdentifier::Cashu(mint_url) => {
+                let client = self.get_cashu_client(mint_url).await;
+
+                self.mint_status_update(msg_id, &mint, "Fetching mint info")
+                    .await;
+                client.get_mint_info().await?;
+                client.get_active_mint_keyset().await?;
+
+                self.mint_status_update(msg_id, &mint, "Checking pending ecash")
+                    .await;
+                client.check_all_pending_proofs().await?;
             }
-            Some(metadata) => metadata,
+        }
+
+        self.mint_status_update(msg_id, &mint, "Checking pending operations")
+            .await;
+        let resubscribed = Self::resubscribe_pending(
+            &self.tx,
+            &self.storage,
+            &*self.clients.read().await,
+            &*self.cashu_clients.read().await,
+            Some(&mint),
+        )
+        .await?;
+
+        log::info!("Resynced {mint:?}, {resubscribed} pending operations picked back up");
+        let done = match resubscribed {
+            0 => "Resync complete".to_string(),
+            1 => "Resync complete, picked 1 pending operation back up".to_string(),
+            n => format!("Resync complete, picked {n} pending operations back up"),
         };
+        self.mint_status_update(msg_id, &mint, &done).await;
 
-        Ok((config, metadata))
+        Ok(())
+    }
+
+    /// Rereads a mint's balance and picks its pending operations back up, for when the
+    /// balance looks out of date, say after receiving on another device. Lighter than
+    /// [`Self::resync_mint`], nothing about the mint itself is refetched.
+    pub async fn refresh_mint_balance(
+        &self,
+        msg_id: Uuid,
+        mint: MintIdentifier,
+    ) -> anyhow::Result<Amount> {
+        let balance = match &mint {
+            MintIdentifier::Fedimint(federation_id) => {
+                self.ensure_ready(*federation_id).await?;
+                let client = self
+                    .clients
+                    .read()
+                    .await
+                    .get(federation_id)
+                    .map(|c| c.fedimint_client.clone())
+                    .ok_or(anyhow!("Federation not found"))?;
+                client.get_balance().await
+            }
+            MintIdentifier::Cashu(mint_url) => {
+                let client = self.get_cashu_client(mint_url).await;
+                client.check_all_pending_proofs().await?;
+                let balance: u64 = client.total_balance().await?.into();
+                Amount::from_sats(balance)
+            }
+        };
+
+        Self::resubscribe_pending(
+            &self.tx,
+            &self.storage,
+            &*self.clients.read().await,
+            &*self.cashu_clients.read().await,
+            Some(&mint),
+        )
+        .await?;
+
+        self.msg(msg_id, CoreUIMsg::MintBalanceUpdated { id: mint, balance })
+            .await;
+        Ok(balance)
+    }
+
+    // Puts together what a mint's operators need to look into a problem, see `MintDiagnostics`
+    // for what is left out. `log` is the contents of Harbor's log file.
+    pub async fn get_mint_diagnostics(
+        &self,
+        mint: MintIdentifier,
+        log: &str,
+    ) -> anyhow::Result<MintDiagnostics> {
+        let history = self.storage.get_transaction_history(Some(&mint))?;
+        let mut diagnostics = MintDiagnostics {
+            version: DIAGNOSTICS_VERSION,
+            generated_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            harbor_version: env!("CARGO_PKG_VERSION").to_string(),
+            fedimint_client_version: FEDIMINT_CLIENT_VERSION.to_string(),
+            network: self.network,
+            mint: String::new(),
+            mint_name: "Unknown".to_string(),
+            config_hash: None,
+            consensus_version: None,
+            modules: vec![],
+            guardians: vec![],
+            recent_errors: vec![],
+            pending_operations: PendingOperation::from_history(&history, &mint),
+            skipped_background: self.background.skipped(),
+        };
+
+        match &mint {
+            MintIdentifier::Fedimint(federation_id) => {
+                let client = self
+                    .clients
+                    .read()
+                    .await
+                    .get(federation_id)
+                    .map(|c| c.fedimint_client.clone())
+                    .ok_or(anyhow!("Federation not found"))?;
+                let config = client.config().await;
+
+                diagnostics.mint = federation_id.to_string();
+                if let Some(name) = client.get_config_meta("federation_name") {
+                    diagnostics.mint_name = name;
+                }
+                diagnostics.config_hash =
+                    Some(sha256::Hash::hash(&serde_json::to_vec(&config)?).to_string());
+
+                // Falls back to the saved config if the guardians can't be reached
+                let versions = self.get_federation_versions(*federation_id).await?;
+                diagnostics.consensus_version = Some(versions.consensus_version);
+                diagnostics.modules = versions
+                    .modules
+                    .into_iter()
+                    .map(|m| DiagnosticModule {
+                        kind: m.kind.to_string(),
+                        version: m.version,
+                    })
+                    .collect();
+
+                let health = self
+                    .guardian_health
+                    .read()
+                    .await
+                    .get(federation_id)
+                    .cloned()
+                    .unwrap_or_default();
+                diagnostics.guardians = config
+                    .global
+                    .api_endpoints
+                    .values()
+                    .map(|endpoint| {
+                        let health = health.iter().find(|g| g.name == endpoint.name);
+                        DiagnosticGuardian {
+                            name: endpoint.name.clone(),
+                            endpoint: redact_url(&endpoint.url.to_string()),
+                            online: health.map(|g| g.online),
+                            last_contact: health.and_then(|g| g.last_contact),
+                            version: health.and_then(|g| g.version.clone()),
+                            last_error: health.and_then(|g| g.last_error.clone()),
+                        }
+                    })
+                    .collect();
+            }
+            MintIdentifier::Cashu(mint_url) => {
+                let client = self.get_cashu_client(mint_url).await;
+                let info = client.localstore.get_mint(mint_url.clone()).await?;
+
+                diagnostics.mint = mint_url.to_string();
+                let name = info.as_ref().and_then(|i| i.name.clone());
+                if let Some(name) = name {
+                    diagnostics.mint_name = name;
+                }
+                diagnostics.guardians = vec![DiagnosticGuardian {
+                    name: diagnostics.mint_name.clone(),
+                    endpoint: redact_url(&mint_url.to_string()),
+                    online: None,
+                    last_contact: None,
+                    version: info
+                        .and_then(|i| i.version)
+                        .map(|v| format!("{} {}", v.name, v.version)),
+                    last_error: None,
+                }];
+            }
+        }
+
+        diagnostics.recent_errors =
+            recent_errors(log, std::slice::from_ref(&diagnostics.mint), MAX_LOG_LINES);
+
+        Ok(diagnostics)
+    }
+
+    // Asks the federation how many sessions it has completed and notes when that last changed
+    pub async fn get_session_progress(
+        &self,
+        federation_id: FederationId,
+    ) -> anyhow::Result<SessionProgress> {
+        let client = self
+            .clients
+            .read()
+            .await
+            .get(&federation_id)
+            .map(|c| c.fedimint_client.clone())
+            .ok_or(anyhow!("Federation not found"))?;
+
+        let session_count = client.api().session_count().await?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let mut observations = self.session_progress.write().await;
+        let progress = observations
+            .entry(federation_id)
+            .or_insert(SessionProgress {
+                session_count,
+                last_progress_at: None,
+                watching_since: now,
+            });
+        if progress.session_count != session_count {
+            progress.session_count = session_count;
+            progress.last_progress_at = Some(now);
+        }
+
+        Ok(progress.clone())
+    }
+
+    // The mint's own fee config alongside what it has actually cost according to our history
+    pub async fn get_mint_fees(&self, mint: MintIdentifier) -> anyhow::Result<MintFees> {
+        let schedule = match &mint {
+            MintIdentifier::Fedimint(federation_id) => {
+                self.get_federation_fee_schedule(*federation_id).await
+            }
+            MintIdentifier::Cashu(mint_url) => self.get_cashu_fee_schedule(mint_url).await,
+        };
+
+        let history = self.storage.get_transaction_history(Some(&mint))?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let paid = FeeSummary::from_history(&history, &mint, now);
+
+        Ok(MintFees { schedule, paid })
+    }
+
+    async fn get_federation_fee_schedule(
+        &self,
+        federation_id: FederationId,
+    ) -> Option<FeeSchedule> {
+        let client = self
+            .clients
+            .read()
+            .await
+            .get(&federation_id)?
+            .fedimint_client
+            .clone();
+        let config = client.config().await;
+
+        let mut schedule = FeeSchedule::default();
+        for module in config.modules.values() {
+            if module.kind() == &fedimint_wallet_common::KIND {
+                let wallet = module.cast::<WalletClientConfig>().ok()?;
+                schedule.peg_in_msats = Some(wallet.fee_consensus.peg_in_abs.msats);
+                schedule.peg_out_msats = Some(wallet.fee_consensus.peg_out_abs.msats);
+            } else if module.kind() == &fedimint_ln_common::KIND {
+                let ln = module.cast::<LightningClientConfig>().ok()?;
+                schedule.lightning_contract_input_msats =
+                    Some(ln.fee_consensus.contract_input.msats);
+                schedule.lightning_contract_output_msats =
+                    Some(ln.fee_consensus.contract_output.msats);
+            }
+        }
+
+        Some(schedule)
+    }
+
+    async fn get_cashu_fee_schedule(&self, mint_url: &MintUrl) -> Option<FeeSchedule> {
+        let client = self.cashu_clients.read().await.get(mint_url)?.clone();
+        let keysets = client
+            .localstore
+            .get_mint_keysets(mint_url.clone())
+            .await
+            .ok()
+            .flatten()?;
+        let active = keysets
+            .iter()
+            .find(|k| k.active && k.unit == CurrencyUnit::Sat)?;
+
+        Some(FeeSchedule {
+            input_fee_ppk: Some(active.input_fee_ppk),
+            ..FeeSchedule::default()
+        })
     }
 
     pub async fn add_federation(
@@ -1332,33 +3397,65 @@ impl HarborCore {
         msg_id: Uuid,
         invite_code: InviteCode,
     ) -> anyhow::Result<()> {
+        let target = MintIdentifier::Fedimint(invite_code.federation_id());
         log::info!("Adding federation with invite code: {invite_code}");
+        self.check_reachable(&MintConnectionInfo::Fedimint(invite_code.clone()))?;
         let id = invite_code.federation_id();
 
-        self.status_update(msg_id, "Starting mint setup").await;
+        self.mint_status_update(msg_id, &target, "Starting mint setup")
+            .await;
 
         let mut clients = self.clients.write().await;
         if clients.get(&id).is_some() {
-            return Err(anyhow!("Federation already added"));
+            return Err(AddMintError::new(
+                AddMintErrorKind::AlreadyJoined,
+                "Federation already added",
+            )
+            .into());
         }
 
-        self.status_update(msg_id, "Initializing mint connection")
+        self.mint_status_update(msg_id, &target, "Initializing mint connection")
             .await;
 
-        let client = FedimintClient::new(
-            self.storage.clone(),
-            FederationInviteOrId::Invite(invite_code.clone()),
-            &self.mnemonic,
-            self.network,
-            self.stop.clone(),
-            self.tx.clone(),
-            Some(msg_id),
+        let client = with_join_rollback(
+            self.storage.as_ref(),
+            id,
+            FedimintClient::new(
+                self.storage.clone(),
+                FederationInviteOrId::Invite(invite_code.clone()),
+                &self.mnemonic,
+                self.network,
+                self.stop.clone(),
+                self.tx.clone(),
+                Some(msg_id),
+            ),
         )
         .await?;
 
-        self.status_update(msg_id, "Registering with mint").await;
+        self.mint_status_update(msg_id, &target, "Registering with mint")
+            .await;
 
+        if client.is_recovering() {
+            self.watch_recovery(client.fedimint_client.clone());
+        }
         clients.insert(id, client.clone());
+        drop(clients);
+
+        // The join only needed enough guardians to agree, any that didn't answer are worth
+        // knowing about before relying on the mint
+        self.mint_status_update(msg_id, &target, "Checking guardians")
+            .await;
+        self.probe_guardians(&client.fedimint_client).await;
+        let warning = self
+            .guardian_health
+            .read()
+            .await
+            .get(&id)
+            .map(Vec::as_slice)
+            .and_then(unreachable_guardians_warning);
+        if let Some(warning) = warning {
+            self.mint_status_warning(msg_id, &target, &warning).await;
+        }
 
         let tx = self.tx.clone();
         let tor_enabled = self.tor_enabled.load(Ordering::Relaxed);
@@ -1375,23 +3472,34 @@ impl HarborCore {
             .await;
         });
 
-        self.status_update(msg_id, "Mint setup complete!").await;
+        // The metadata row stays on as the joined federation's, the preview isn't needed anymore
+        if let Err(e) = self.storage.remove_federation_preview(id) {
+            log::warn!("Could not remove preview of federation {id}: {e}");
+        }
+
+        self.mint_status_update(msg_id, &target, "Mint setup complete!")
+            .await;
 
         Ok(())
     }
 
     pub async fn add_cashu_mint(&self, msg_id: Uuid, mint_url: MintUrl) -> anyhow::Result<()> {
+        let target = MintIdentifier::Cashu(mint_url.clone());
         log::info!("Adding cashu mint: {mint_url}");
+        self.check_reachable(&MintConnectionInfo::Cashu(mint_url.clone()))?;
         let url = mint_url.to_string();
 
-        self.status_update(msg_id, "Starting mint setup").await;
+        self.mint_status_update(msg_id, &target, "Starting mint setup")
+            .await;
 
         let mut clients = self.cashu_clients.write().await;
-        if clients.get(&mint_url).is_some() {
-            return Err(anyhow!("Mint already added"));
+        if clients.keys().any(|known| same_mint_url(known, &mint_url)) {
+            return Err(
+                AddMintError::new(AddMintErrorKind::AlreadyJoined, "Mint already added").into(),
+            );
         }
 
-        self.status_update(msg_id, "Initializing mint connection")
+        self.mint_status_update(msg_id, &target, "Initializing mint connection")
             .await;
 
         let seed = self.mnemonic.to_seed_normalized("");
@@ -1416,6 +3524,9 @@ impl HarborCore {
         // start background task to attempt to restore
         let w = wallet.clone();
         let mut tx = self.tx.clone();
+        let syncing_mints = self.syncing_mints.clone();
+        let syncing_id = MintIdentifier::Cashu(mint_url.clone());
+        syncing_mints.write().await.insert(syncing_id.clone());
         spawn(async move {
             match w.restore().await {
                 Err(e) => log::error!("Failed to restore cashu mint: {e}"),
@@ -1441,17 +3552,24 @@ impl HarborCore {
                     }
                 }
             }
+
+            // the balance is settled now, so have the UI refresh the list
+            syncing_mints.write().await.remove(&syncing_id);
+            Self::send_msg(&mut tx, None, CoreUIMsg::FederationListNeedsUpdate).await;
         });
 
-        self.status_update(msg_id, "Registering with mint").await;
+        self.mint_status_update(msg_id, &target, "Registering with mint")
+            .await;
 
         clients.insert(mint_url, wallet);
 
-        self.status_update(msg_id, "Saving to database").await;
+        self.mint_status_update(msg_id, &target, "Saving to database")
+            .await;
 
         self.storage.insert_new_cashu_mint(url)?;
 
-        self.status_update(msg_id, "Mint setup complete!").await;
+        self.mint_status_update(msg_id, &target, "Mint setup complete!")
+            .await;
 
         Ok(())
     }
@@ -1467,8 +3585,18 @@ impl HarborCore {
 
         let mut clients = self.clients.write().await;
 
+        // One that failed to open can still be archived, it just has no client to drop
+        let failed_to_open = {
+            let mut init = self.federation_init.write().await;
+            let failed = matches!(init.get(&id), Some(FederationInitState::Failed(_)));
+            if failed {
+                init.remove(&id);
+            }
+            failed
+        };
+
         // Check if federation exists before attempting removal
-        if !clients.contains_key(&id) {
+        if !clients.contains_key(&id) && !failed_to_open {
             return Err(anyhow!("Federation doesn't exist"));
         }
 
@@ -1516,18 +3644,97 @@ impl HarborCore {
         Ok(())
     }
 
+    // A federation's list item from what's saved about it, for when there's no client to ask
+    fn saved_federation_item(
+        &self,
+        id: FederationId,
+        metadata: FederationMeta,
+        active: bool,
+    ) -> anyhow::Result<MintItem> {
+        let record = self.storage.get_fedimint(id)?;
+        Ok(MintItem {
+            id: MintIdentifier::Fedimint(id),
+            name: metadata
+                .federation_name
+                .clone()
+                .unwrap_or("Unknown".to_string()),
+            nickname: record.as_ref().and_then(|r| r.nickname.clone()),
+            joined_at: record
+                .as_ref()
+                .and_then(|r| r.joined_at)
+                .map(|t| t.and_utc().timestamp() as u64),
+            last_used_at: record
+                .as_ref()
+                .and_then(|r| r.last_used_at)
+                .map(|t| t.and_utc().timestamp() as u64),
+            notes: record.as_ref().and_then(|r| r.notes.clone()),
+            max_balance_override: record
+                .as_ref()
+                .and_then(|r| r.max_balance_msats)
+                .and_then(|max| u64::try_from(max).ok())
+                .map(Amount::from_msats),
+            invite_code: record.and_then(|r| InviteCode::from_str(&r.invite_code).ok()),
+            icon: metadata
+                .federation_icon_url
+                .as_deref()
+                .and_then(|url| icon_cache::cached_icon(&self.data_dir, url)),
+            balance: 0,
+            guardians: None,
+            guardian_health: None,
+            module_kinds: None,
+            metadata,
+            on_chain_supported: false,
+            active,
+            syncing: false,
+            pending: PendingAmounts::default(),
+        })
+    }
+
+    /// What's in flight for each mint, from the payments and deposits that haven't finished
+    pub fn get_pending_amounts(&self) -> anyhow::Result<HashMap<MintIdentifier, PendingAmounts>> {
+        let mut items: Vec<TransactionItem> = vec![];
+        items.extend(
+            self.storage
+                .get_pending_onchain_receives()?
+                .into_iter()
+                .map(TransactionItem::from),
+        );
+        items.extend(
+            self.storage
+                .get_pending_onchain_payments()?
+                .into_iter()
+                .map(TransactionItem::from),
+        );
+        items.extend(
+            self.storage
+                .get_pending_lightning_payments()?
+                .into_iter()
+                .map(TransactionItem::from),
+        );
+        Ok(PendingAmounts::tally(&items))
+    }
+
     pub async fn get_mint_items(&self) -> anyhow::Result<Vec<MintItem>> {
+        let pending = self.get_pending_amounts()?;
         let clients = self.clients.read().await;
         let cashu_clients = self.cashu_clients.read().await;
 
         let metadata_cache = CACHE.read().await;
+        let syncing_mints = self.syncing_mints.read().await;
+        let guardian_health = self.guardian_health.read().await;
 
         let mut needs_metadata = vec![];
+        let mut needs_icons = vec![];
 
         // Tell the UI about any clients we have
         let mut res = Vec::with_capacity(clients.len() + cashu_clients.len());
         for c in clients.values() {
-            let balance = c.fedimint_client.get_balance().await;
+            let recovering = c.is_recovering();
+            let balance = if recovering {
+                Amount::ZERO
+            } else {
+                c.fedimint_client.get_balance().await
+            };
             let config = c.fedimint_client.config().await;
 
             let guardians: Vec<String> = config
@@ -1562,18 +3769,51 @@ impl HarborCore {
                     Err(_) => false,
                 };
 
+            let record = self
+                .storage
+                .get_fedimint(c.fedimint_client.federation_id())?;
+
             res.push(MintItem {
                 id: MintIdentifier::Fedimint(c.fedimint_client.federation_id()),
                 name: c
                     .fedimint_client
                     .get_config_meta("federation_name")
                     .unwrap_or("Unknown".to_string()),
+                nickname: record.as_ref().and_then(|r| r.nickname.clone()),
+                joined_at: record
+                    .as_ref()
+                    .and_then(|r| r.joined_at)
+                    .map(|t| t.and_utc().timestamp() as u64),
+                last_used_at: record
+                    .as_ref()
+                    .and_then(|r| r.last_used_at)
+                    .map(|t| t.and_utc().timestamp() as u64),
+                notes: record.as_ref().and_then(|r| r.notes.clone()),
+                max_balance_override: record
+                    .as_ref()
+                    .and_then(|r| r.max_balance_msats)
+                    .and_then(|max| u64::try_from(max).ok())
+                    .map(Amount::from_msats),
+                invite_code: record.and_then(|r| InviteCode::from_str(&r.invite_code).ok()),
+                icon: metadata
+                    .as_ref()
+                    .and_then(|m| self.mint_icon(m, &mut needs_icons)),
                 balance: balance.sats_round_down(),
                 guardians: Some(guardians),
+                guardian_health: guardian_health
+                    .get(&c.fedimint_client.federation_id())
+                    .cloned(),
                 module_kinds: Some(module_kinds),
                 metadata: metadata.unwrap_or_default(),
                 on_chain_supported,
                 active: true,
+                syncing: recovering
+                    || syncing_mints
+                        .contains(&MintIdentifier::Fedimint(c.fedimint_client.federation_id())),
+                pending: pending
+                    .get(&MintIdentifier::Fedimint(c.fedimint_client.federation_id()))
+                    .copied()
+                    .unwrap_or_default(),
             });
         }
 
@@ -1591,31 +3831,62 @@ impl HarborCore {
                 welcome_message: None,
                 vetted_gateways: None,
                 federation_icon_url: info.as_ref().and_then(|i| i.icon_url.clone()),
+                federation_site_url: None,
                 meta_external_url: None,
                 preview_message: info.as_ref().and_then(|i| i.description.clone()),
                 popup_end_timestamp: None,
                 popup_countdown_message: None,
+                max_balance_msats: None,
             };
 
+            let record = self.storage.get_cashu_mint(&c.mint_url)?;
+
             res.push(MintItem {
                 id: MintIdentifier::Cashu(c.mint_url.clone()),
                 name: metadata
                     .federation_name
                     .clone()
                     .unwrap_or("Unknown".to_string()),
+                nickname: record.as_ref().and_then(|r| r.nickname.clone()),
+                joined_at: record
+                    .as_ref()
+                    .and_then(|r| r.joined_at)
+                    .map(|t| t.and_utc().timestamp() as u64),
+                last_used_at: record
+                    .as_ref()
+                    .and_then(|r| r.last_used_at)
+                    .map(|t| t.and_utc().timestamp() as u64),
+                notes: record.as_ref().and_then(|r| r.notes.clone()),
+                max_balance_override: record
+                    .as_ref()
+                    .and_then(|r| r.max_balance_msats)
+                    .and_then(|max| u64::try_from(max).ok())
+                    .map(Amount::from_msats),
+                invite_code: None,
+                icon: self.mint_icon(&metadata, &mut needs_icons),
                 balance,
                 guardians: None,
+                guardian_health: None,
                 module_kinds: None,
                 metadata,
                 on_chain_supported: false,
                 active: true,
+                syncing: syncing_mints.contains(&MintIdentifier::Cashu(c.mint_url.clone())),
+                pending: pending
+                    .get(&MintIdentifier::Cashu(c.mint_url.clone()))
+                    .copied()
+                    .unwrap_or_default(),
             });
         }
 
         drop(metadata_cache);
+        drop(syncing_mints);
+        drop(guardian_health);
+
+        self.fetch_icons(needs_icons);
 
         // if we're missing metadata for federations, start background task to populate it
-        if !needs_metadata.is_empty() {
+        if !needs_metadata.is_empty() && self.background.allow(BackgroundTask::MetadataRefresh) {
             let tx = self.tx.clone();
             let tor_enabled = self.tor_enabled.load(Ordering::Relaxed);
             let metadata_fetch_cancel = self.metadata_fetch_cancel.clone();
@@ -1632,38 +3903,71 @@ impl HarborCore {
             });
         }
 
+        // Federations still opening, or that failed to, are listed from what's saved about them
+        let opening: Vec<(FederationId, FederationInitState)> = self
+            .federation_init
+            .read()
+            .await
+            .iter()
+            .map(|(id, state)| (*id, state.clone()))
+            .collect();
+        for (id, state) in opening {
+            let metadata = self
+                .storage
+                .get_federation_metadata(id)?
+                .unwrap_or_default();
+            let mut item = self.saved_federation_item(id, metadata, true)?;
+            item.syncing = state == FederationInitState::Opening;
+            res.push(item);
+        }
+
         // get archived fedimints
         let archived = self.storage.get_archived_fedimints()?;
         for m in archived {
-            let item = MintItem {
-                id: MintIdentifier::Fedimint(FederationId::from_str(&m.id)?),
-                name: m.name.clone().unwrap_or("Unknown".to_string()),
-                balance: 0,
-                guardians: None,
-                module_kinds: None,
-                metadata: m.into(),
-                on_chain_supported: false,
-                active: false,
-            };
-            res.push(item);
+            let id = FederationId::from_str(&m.id)?;
+            res.push(self.saved_federation_item(id, m.into(), false)?);
         }
 
         // get archived cashu mints
         let archived = self.storage.list_archived_cashu_mints()?;
         for mint_url in archived {
             let info = self.cashu_storage.get_mint(mint_url.clone()).await?;
+            let record = self.storage.get_cashu_mint(&mint_url)?;
             let item = MintItem {
                 id: MintIdentifier::Cashu(mint_url.clone()),
                 name: info
                     .as_ref()
                     .and_then(|i| i.name.clone())
                     .unwrap_or(mint_url.to_string()),
+                nickname: record.as_ref().and_then(|r| r.nickname.clone()),
+                joined_at: record
+                    .as_ref()
+                    .and_then(|r| r.joined_at)
+                    .map(|t| t.and_utc().timestamp() as u64),
+                last_used_at: record
+                    .as_ref()
+                    .and_then(|r| r.last_used_at)
+                    .map(|t| t.and_utc().timestamp() as u64),
+                notes: record.as_ref().and_then(|r| r.notes.clone()),
+                max_balance_override: record
+                    .as_ref()
+                    .and_then(|r| r.max_balance_msats)
+                    .and_then(|max| u64::try_from(max).ok())
+                    .map(Amount::from_msats),
+                invite_code: None,
+                icon: info
+                    .as_ref()
+                    .and_then(|i| i.icon_url.as_deref())
+                    .and_then(|url| icon_cache::cached_icon(&self.data_dir, url)),
                 balance: 0,
                 guardians: None,
+                guardian_health: None,
                 module_kinds: None,
                 metadata: info.into(),
                 on_chain_supported: false,
                 active: false,
+                syncing: false,
+                pending: PendingAmounts::default(),
             };
             res.push(item);
         }
@@ -1671,6 +3975,57 @@ impl HarborCore {
         Ok(res)
     }
 
+    // The icon on disk for a mint, queueing a download when it's missing or old. Archived mints
+    // just use whatever is already on disk.
+    fn mint_icon(
+        &self,
+        metadata: &FederationMeta,
+        needs_icons: &mut Vec<String>,
+    ) -> Option<PathBuf> {
+        let url = metadata.federation_icon_url.as_ref()?;
+        if icon_cache::needs_fetch(&self.data_dir, url) {
+            needs_icons.push(url.clone());
+        }
+        icon_cache::cached_icon(&self.data_dir, url)
+    }
+
+    // Downloads icons in the background, redrawing the list once any of them arrive
+    fn fetch_icons(&self, urls: Vec<String>) {
+        if urls.is_empty() || !self.background.allow(BackgroundTask::IconDownloads) {
+            return;
+        }
+
+        let core = self.clone();
+        spawn(async move {
+            let mut fetched = false;
+            for url in urls {
+                if core.metadata_fetch_cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+                if !core.icon_fetches.write().await.insert(url.clone()) {
+                    continue;
+                }
+
+                match icon_cache::fetch_icon(
+                    &core.data_dir,
+                    &url,
+                    core.tor_enabled.load(Ordering::Relaxed),
+                    core.metadata_fetch_cancel.clone(),
+                )
+                .await
+                {
+                    Ok(_) => fetched = true,
+                    Err(e) => log::warn!("Could not fetch mint icon from {url}: {e}"),
+                }
+            }
+
+            if fetched {
+                core.send_system_msg(CoreUIMsg::FederationListNeedsUpdate)
+                    .await;
+            }
+        });
+    }
+
     async fn update_mint_metadata(
         needs_metadata: Vec<ClientHandleArc>,
         metadata_fetch_cancel: Arc<AtomicBool>,
@@ -1704,6 +4059,206 @@ impl HarborCore {
         }
     }
 
+    pub fn set_mint_nickname(
+        &self,
+        mint: MintIdentifier,
+        nickname: Option<String>,
+    ) -> anyhow::Result<()> {
+        // Treat an empty name as clearing the nickname
+        let nickname = nickname
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty());
+        log::info!("Setting nickname for {mint:?} to: {nickname:?}");
+        match mint {
+            MintIdentifier::Fedimint(id) => self.storage.set_federation_nickname(id, nickname),
+            MintIdentifier::Cashu(url) => self.storage.set_cashu_mint_nickname(&url, nickname),
+        }
+    }
+
+    pub fn set_mint_notes(
+        &self,
+        mint: MintIdentifier,
+        notes: Option<String>,
+    ) -> anyhow::Result<()> {
+        let notes = notes.filter(|n| !n.trim().is_empty());
+        if notes.as_ref().is_some_and(|n| n.len() > MAX_MINT_NOTES_LEN) {
+            return Err(anyhow!("Notes can be at most {MAX_MINT_NOTES_LEN} bytes"));
+        }
+        match mint {
+            MintIdentifier::Fedimint(id) => self.storage.set_federation_notes(id, notes),
+            MintIdentifier::Cashu(url) => self.storage.set_cashu_mint_notes(&url, notes),
+        }
+    }
+
+    /// Uses the user's time limits from here on, brought into range first
+    pub fn set_operation_timeouts(&self, timeouts: OperationTimeouts) {
+        let timeouts = timeouts.clamped();
+        self.operations.set_timeouts(timeouts);
+        http::set_connect_timeout(timeouts.connect);
+    }
+
+    /// The price of bitcoin in `currency`, over Tor when that's on. See
+    /// [`ExchangeRates::get`] for how it's cached.
+    pub async fn get_exchange_rate(&self, currency: FiatCurrency) -> Option<ExchangeRate> {
+        self.exchange_rates
+            .get(
+                currency,
+                self.tor_enabled.load(Ordering::Relaxed),
+                self.metadata_fetch_cancel.clone(),
+            )
+            .await
+    }
+
+    /// Sets the user's own cap on how much to hold in a mint, `None` goes back to what the
+    /// mint recommends
+    pub fn set_mint_max_balance(
+        &self,
+        mint: MintIdentifier,
+        max_balance: Option<Amount>,
+    ) -> anyhow::Result<()> {
+        match mint {
+            MintIdentifier::Fedimint(id) => {
+                self.storage.set_federation_max_balance(id, max_balance)
+            }
+            MintIdentifier::Cashu(url) => {
+                self.storage.set_cashu_mint_max_balance(&url, max_balance)
+            }
+        }
+    }
+
+    /// Fetches a published mint list. With a `signer` the detached signature next to it has to
+    /// check out against that key, otherwise the list is refused.
+    pub async fn fetch_mint_list(
+        &self,
+        url: &str,
+        signer: Option<&str>,
+    ) -> anyhow::Result<FetchedMintList> {
+        // Pasted URLs often come with whitespace around them
+        let url = url.trim();
+        if !self.connectivity.is_online() {
+            return Err(anyhow!("You're offline"));
+        }
+        let signer = signer.map(nostr::parse_public_key).transpose()?;
+        let tor_enabled = self.tor_enabled.load(Ordering::Relaxed);
+        let cancel_handle = Arc::new(AtomicBool::new(false));
+
+        let payload =
+            make_get_request_bytes(url, MAX_MINT_LIST_SIZE, tor_enabled, cancel_handle.clone())
+                .await
+                .map_err(|e| anyhow!("Could not fetch the mint list: {e}"))?;
+        let json =
+            std::str::from_utf8(&payload).map_err(|_| anyhow!("Not a Harbor mint list file"))?;
+        let export = MintListExport::from_json(json)?;
+
+        let verified_by = match signer {
+            Some(signer) => {
+                let signature_url = signature_url(url)?;
+                let signature = make_get_request_bytes(
+                    &signature_url,
+                    MAX_SIGNATURE_SIZE,
+                    tor_enabled,
+                    cancel_handle,
+                )
+                .await
+                .map_err(|e| anyhow!("Could not fetch the signature from {signature_url}: {e}"))?;
+                verify_list_signature(&payload, &String::from_utf8_lossy(&signature), &signer)?;
+                Some(nostr::npub(&signer))
+            }
+            None => None,
+        };
+
+        Ok(FetchedMintList {
+            url: url.to_string(),
+            export,
+            verified_by,
+        })
+    }
+
+    pub async fn import_mints(&self, msg_id: Uuid, mints: Vec<ExportedMint>) -> MintImportSummary {
+        let mut summary = MintImportSummary::default();
+        let total = mints.len();
+
+        // Join one at a time, a mint that fails is recorded and the rest carry on
+        for (i, mint) in mints.into_iter().enumerate() {
+            let label = mint.label();
+            self.status_update(msg_id, &format!("Joining {} of {total}: {label}", i + 1))
+                .await;
+
+            match self.import_mint(&mint).await {
+                Ok(true) => summary.joined += 1,
+                Ok(false) => summary.skipped += 1,
+                Err(e) => {
+                    log::error!("Failed to import mint {label}: {e}");
+                    summary.failed.push((label, e.to_string()));
+                }
+            }
+        }
+
+        self.status_update(msg_id, "Import complete").await;
+        summary
+    }
+
+    // Joins a single mint from an imported list, returns false if it was already in the wallet
+    async fn import_mint(&self, mint: &ExportedMint) -> anyhow::Result<bool> {
+        // Progress for the whole import is reported on the import's id,
+        // so the individual joins get their own to avoid clobbering it
+        let join_id = Uuid::new_v4();
+
+        let id = match mint {
+            ExportedMint::Fedimint {
+                federation_id,
+                invite_codes,
+                ..
+            } => {
+                let federation_id = FederationId::from_str(federation_id)?;
+                if self.storage.get_fedimint(federation_id)?.is_some() {
+                    return Ok(false);
+                }
+
+                let invite_code = invite_codes
+                    .iter()
+                    .find_map(|code| InviteCode::from_str(code).ok())
+                    .ok_or(anyhow!("No valid invite code"))?;
+                if invite_code.federation_id() != federation_id {
+                    return Err(anyhow!("Invite code is for a different federation"));
+                }
+
+                self.add_federation(join_id, invite_code).await?;
+                MintIdentifier::Fedimint(federation_id)
+            }
+            ExportedMint::Cashu { mint_url, .. } => {
+                let mint_url = normalize_mint_url(mint_url)?;
+                if self.storage.get_cashu_mint(&mint_url)?.is_some() {
+                    return Ok(false);
+                }
+
+                self.add_cashu_mint(join_id, mint_url.clone()).await?;
+                MintIdentifier::Cashu(mint_url)
+            }
+        };
+
+        if mint.nickname().is_some() {
+            self.set_mint_nickname(id.clone(), mint.nickname())?;
+        }
+
+        if mint.notes().is_some() {
+            self.set_mint_notes(id.clone(), mint.notes())?;
+        }
+
+        if mint.archived() {
+            match &id {
+                MintIdentifier::Fedimint(federation_id) => {
+                    self.remove_federation(join_id, *federation_id).await?;
+                }
+                MintIdentifier::Cashu(mint_url) => {
+                    self.remove_cashu_mint(join_id, mint_url).await?;
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
     pub fn get_seed_words(&self) -> String {
         self.mnemonic.to_string()
     }
@@ -1729,6 +4284,41 @@ impl HarborCore {
         Ok(())
     }
 
+    /// Saves a generated or imported nostr key, or removes it with `None`. Returns what the
+    /// UI should show for what's saved.
+    pub fn set_nostr_identity(
+        &self,
+        identity: Option<NostrIdentity>,
+    ) -> anyhow::Result<Option<NostrPublicIdentity>> {
+        self.save_nostr_identity(identity.map(|identity| (identity, NostrKeySource::Separate)))
+    }
+
+    /// Switches to the nostr key NIP-06 derives from the wallet seed
+    pub fn derive_nostr_identity(&self) -> anyhow::Result<Option<NostrPublicIdentity>> {
+        let identity = NostrIdentity::from_seed(&self.mnemonic)?;
+        self.save_nostr_identity(Some((identity, NostrKeySource::Seed)))
+    }
+
+    fn save_nostr_identity(
+        &self,
+        identity: Option<(NostrIdentity, NostrKeySource)>,
+    ) -> anyhow::Result<Option<NostrPublicIdentity>> {
+        self.storage.set_nostr_identity(
+            identity
+                .as_ref()
+                .map(|(identity, source)| (identity, *source)),
+        )?;
+        let public = identity.map(|(identity, source)| NostrPublicIdentity {
+            npub: identity.npub(),
+            source,
+        });
+        match &public {
+            Some(public) => log::info!("Set nostr identity {} ({})", public.npub, public.source),
+            None => log::info!("Removed nostr identity"),
+        }
+        Ok(public)
+    }
+
     pub async fn test_status_updates(&self, msg_id: Uuid) {
         self.status_update(msg_id, "Starting test sequence").await;
         tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
@@ -1747,3 +4337,732 @@ impl HarborCore {
         self.status_update(msg_id, "Test sequence complete!").await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVITE_CODE: &str = "fed11qgqzc2nhwden5te0vejkg6tdd9h8gepwvejkg6tdd9h8garhduhx6at5d9h8jmn9wshxxmmd9uqqzgxg6s3evnr6m9zdxr6hxkdkukexpcs3mn7mj3g5pc5dfh63l4tj6g9zk4er";
+
+    #[test]
+    fn test_mint_connection_info_round_trip() {
+        let with_secret = InviteCode::new(
+            SafeUrl::parse("wss://guardian.example.com/").unwrap(),
+            PeerId::from(0),
+            FederationId::dummy(),
+            Some("secret".to_string()),
+        );
+        let inputs = [
+            INVITE_CODE.to_string(),
+            with_secret.to_string(),
+            "https://mint.example.com".to_string(),
+            "https://mint.example.com/".to_string(),
+            "https://mint.example.com/Bitcoin".to_string(),
+            "http://localhost:3338".to_string(),
+        ];
+
+        for input in inputs {
+            let info = MintConnectionInfo::from_str(&input).unwrap();
+            let canonical = info.to_string();
+            assert_eq!(
+                MintConnectionInfo::from_str(&canonical).unwrap(),
+                info,
+                "{input} did not survive a round trip"
+            );
+            // The canonical form is a fixed point
+            assert_eq!(
+                MintConnectionInfo::from_str(&canonical)
+                    .unwrap()
+                    .to_string(),
+                canonical
+            );
+        }
+
+        let info = MintConnectionInfo::from_str("https://mint.example.com/").unwrap();
+        assert_eq!(info.to_string(), "https://mint.example.com");
+        assert_eq!(
+            MintConnectionInfo::from_str(INVITE_CODE)
+                .unwrap()
+                .to_string(),
+            INVITE_CODE
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_join_rolls_back() {
+        use crate::db_models::NewFedimint;
+        use diesel::result::{DatabaseErrorKind, Error as DieselError};
+
+        let tmp_dir = tempdir::TempDir::new("harbor").unwrap();
+        let url = format!("sqlite://{}/harbor.sqlite", tmp_dir.path().display());
+        let storage = db::setup_db(&url, "password".to_string()).unwrap();
+        let id = InviteCode::from_str(INVITE_CODE).unwrap().federation_id();
+        let new_federation = || NewFedimint {
+            id: id.to_string(),
+            value: vec![],
+            invite_code: INVITE_CODE.to_string(),
+        };
+        let mint_list = storage.list_federations().unwrap();
+
+        // The federation row goes in, then the disk fills up before the join is done
+        let failing_join = async {
+            storage.insert_new_federation(new_federation())?;
+            Err::<(), _>(anyhow::Error::new(DieselError::DatabaseError(
+                DatabaseErrorKind::Unknown,
+                Box::new("database or disk is full".to_string()),
+            )))
+        };
+        let e = with_join_rollback(storage.as_ref(), id, failing_join)
+            .await
+            .unwrap_err();
+        let error = AddMintError::classify(&e);
+        assert_eq!(error.kind, AddMintErrorKind::Database);
+        assert!(error.message.contains("database or disk is full"));
+        assert_eq!(storage.list_federations().unwrap(), mint_list);
+        assert!(storage.get_fedimint(id).unwrap().is_none());
+
+        // A rejoin that fails leaves the federation archived, as it was
+        storage.insert_new_federation(new_federation()).unwrap();
+        storage.remove_federation(id).unwrap();
+        let failing_rejoin = async {
+            storage.set_federation_active(id)?;
+            Err::<(), _>(anyhow!("disk I/O error"))
+        };
+        assert!(
+            with_join_rollback(storage.as_ref(), id, failing_rejoin)
+                .await
+                .is_err()
+        );
+        assert_eq!(storage.list_federations().unwrap(), mint_list);
+        assert_eq!(storage.get_fedimint(id).unwrap().unwrap().active, 0);
+
+        // A join that works is left alone
+        let rejoin = async { storage.set_federation_active(id) };
+        with_join_rollback(storage.as_ref(), id, rejoin)
+            .await
+            .unwrap();
+        assert_eq!(storage.list_federations().unwrap(), vec![id.to_string()]);
+    }
+
+    #[test]
+    fn test_fedimint_client_version_matches_manifest() {
+        let manifest = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"));
+        let required = manifest
+            .lines()
+            .find_map(|line| line.strip_prefix("fedimint-client = "))
+            .and_then(|dep| dep.split('"').nth(1))
+            .expect("fedimint-client is in Cargo.toml");
+        assert_eq!(FEDIMINT_CLIENT_VERSION, required);
+    }
+
+    #[test]
+    fn test_multiple_invite_codes() {
+        let federation_id = FederationId::dummy();
+        let guardian = |peer: u16| {
+            InviteCode::new(
+                SafeUrl::parse(&format!("wss://guardian{peer}.example.com/")).unwrap(),
+                PeerId::from(peer),
+                federation_id,
+                None,
+            )
+        };
+
+        // One code per guardian, separated however the operator happened to publish them
+        let pasted = format!("{}\n{}  {}\n", guardian(0), guardian(1), guardian(2));
+        let info = MintConnectionInfo::from_str(&pasted).unwrap();
+        let MintConnectionInfo::Fedimint(invite_code) = &info else {
+            panic!("Expected a Fedimint invite code");
+        };
+        assert_eq!(invite_code.federation_id(), federation_id);
+        assert_eq!(invite_code.peers().len(), 3);
+        assert_eq!(
+            MintConnectionInfo::from_str(&info.to_string()).unwrap(),
+            info
+        );
+
+        // Every guardian survives the merge, with the federation id encoded only once
+        let codes: Vec<InviteCode> = (0..4).map(guardian).collect();
+        let merged = merge_invite_codes(&codes).unwrap();
+        let expected: BTreeMap<PeerId, SafeUrl> =
+            codes.iter().flat_map(InviteCode::peers).collect();
+        let parsed = InviteCode::from_str(&merged.to_string()).unwrap();
+        assert_eq!(parsed.peers(), expected);
+        assert_eq!(parsed.federation_id(), federation_id);
+        assert_eq!(parsed.api_secret(), None);
+        let encoded = merged.consensus_encode_to_vec();
+        let parts = u64::consensus_decode_partial(
+            &mut encoded.as_slice(),
+            &ModuleDecoderRegistry::default(),
+        )
+        .unwrap();
+        assert_eq!(parts, 5);
+
+        let with_secret = InviteCode::new(
+            SafeUrl::parse("wss://guardian0.example.com/").unwrap(),
+            PeerId::from(0),
+            federation_id,
+            Some("secret".to_string()),
+        );
+        let merged = merge_invite_codes(&[with_secret, guardian(1)]).unwrap();
+        let parsed = InviteCode::from_str(&merged.to_string()).unwrap();
+        assert_eq!(parsed.peers().len(), 2);
+        assert_eq!(parsed.api_secret(), Some("secret".to_string()));
+
+        // Pasting the same code twice doesn't count it twice
+        let pasted = format!("{} {}", guardian(0), guardian(0));
+        let merged = merge_invite_codes(&[guardian(0), guardian(0)]).unwrap();
+        assert_eq!(merged.peers().len(), 1);
+        assert!(MintConnectionInfo::from_str(&pasted).is_ok());
+
+        let other = InviteCode::from_str(INVITE_CODE).unwrap();
+        let err = MintConnectionInfo::from_str(&format!("{} {other}", guardian(0))).unwrap_err();
+        assert_eq!(err, MintConnectionError::MixedFederations);
+        assert!(err.to_string().contains("one federation at a time"));
+
+        assert!(MintConnectionInfo::from_str(&format!("{} nonsense", guardian(0))).is_err());
+    }
+
+    #[test]
+    fn test_invite_code_for_guardian() {
+        let federation_id = FederationId::dummy();
+        let endpoints: BTreeMap<PeerId, PeerUrl> = (0..4)
+            .map(|peer| {
+                let url = SafeUrl::parse(&format!("wss://guardian{peer}.example.com/")).unwrap();
+                let name = format!("Guardian {peer}");
+                (PeerId::from(peer), PeerUrl { url, name })
+            })
+            .collect();
+        let health: Vec<GuardianHealth> = endpoints
+            .iter()
+            .map(|(peer_id, endpoint)| GuardianHealth {
+                peer_id: *peer_id,
+                name: endpoint.name.clone(),
+                // The first two guardians are down
+                online: peer_id.to_usize() >= 2,
+                endpoint_host: endpoint.url.host_str().unwrap().to_string(),
+                onion: false,
+                last_contact: None,
+                version: None,
+                last_error: None,
+            })
+            .collect();
+
+        let code =
+            invite_code_for_guardian(federation_id, &endpoints, Some(&health), None, None).unwrap();
+        assert_eq!(code.peer(), PeerId::from(2));
+        assert_eq!(code.url(), endpoints[&PeerId::from(2)].url);
+
+        // The new code has to be something other wallets can read back
+        let parsed = InviteCode::from_str(&code.to_string()).unwrap();
+        assert_eq!(parsed, code);
+        assert_eq!(parsed.federation_id(), federation_id);
+
+        let code = invite_code_for_guardian(
+            federation_id,
+            &endpoints,
+            Some(&health),
+            Some(PeerId::from(3)),
+            Some("secret".to_string()),
+        )
+        .unwrap();
+        assert_eq!(code.peer(), PeerId::from(3));
+        assert_eq!(code.api_secret(), Some("secret".to_string()));
+
+        let code = invite_code_for_guardian(federation_id, &endpoints, None, None, None).unwrap();
+        assert_eq!(code.peer(), PeerId::from(0));
+
+        let all_down: Vec<GuardianHealth> = health
+            .into_iter()
+            .map(|g| GuardianHealth { online: false, ..g })
+            .collect();
+        assert!(
+            invite_code_for_guardian(federation_id, &endpoints, Some(&all_down), None, None)
+                .is_err()
+        );
+        assert!(
+            invite_code_for_guardian(federation_id, &endpoints, None, Some(PeerId::from(9)), None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unreachable_guardians_warning() {
+        let guardian = |peer: u16, online: bool| GuardianHealth {
+            peer_id: PeerId::from(peer),
+            name: format!("Guardian {peer}"),
+            online,
+            endpoint_host: format!("guardian{peer}.example.com"),
+            onion: false,
+            last_contact: None,
+            version: None,
+            last_error: None,
+        };
+
+        let all_up: Vec<_> = (0..4).map(|peer| guardian(peer, true)).collect();
+        assert_eq!(unreachable_guardians_warning(&all_up), None);
+
+        let two_down: Vec<_> = (0..4).map(|peer| guardian(peer, peer >= 2)).collect();
+        assert_eq!(
+            unreachable_guardians_warning(&two_down).as_deref(),
+            Some("2 of 4 guardians unreachable, proceeding")
+        );
+    }
+
+    #[test]
+    fn test_unsupported_invite_versions() {
+        use bitcoin::bech32::{Bech32m, Hrp};
+
+        let (hrp, data) = bitcoin::bech32::decode(INVITE_CODE).unwrap();
+
+        // The same payload under a bumped prefix, like a future format might use
+        let bumped =
+            bitcoin::bech32::encode::<Bech32m>(Hrp::parse("fed2").unwrap(), &data).unwrap();
+        assert_eq!(
+            MintConnectionInfo::from_str(&bumped).unwrap_err(),
+            MintConnectionError::UnsupportedInviteVersion
+        );
+
+        // The right prefix and checksum around a payload we can't decode
+        let mut unknown = data;
+        unknown.insert(0, 0xff);
+        let unknown = bitcoin::bech32::encode::<Bech32m>(hrp, &unknown).unwrap();
+        let err = MintConnectionInfo::from_str(&unknown).unwrap_err();
+        assert_eq!(err, MintConnectionError::UnsupportedInviteVersion);
+        assert_eq!(
+            err.to_string(),
+            "This invite code requires a newer version of Harbor"
+        );
+    }
+
+    #[test]
+    fn test_mint_connection_errors() {
+        use bitcoin::bech32::{Bech32m, Hrp};
+
+        let (_, data) = bitcoin::bech32::decode(INVITE_CODE).unwrap();
+        let mut typo = INVITE_CODE.to_string();
+        typo.replace_range(20..21, if &typo[20..21] == "q" { "p" } else { "q" });
+        let address = bitcoin::bech32::encode::<Bech32m>(Hrp::parse("bc").unwrap(), &data).unwrap();
+
+        let cases = [
+            ("hello", MintConnectionError::NotBech32),
+            ("", MintConnectionError::NotBech32),
+            ("abc1defghijk", MintConnectionError::NotBech32),
+            (typo.as_str(), MintConnectionError::BadChecksum),
+            (
+                &INVITE_CODE[..INVITE_CODE.len() - 1],
+                MintConnectionError::BadChecksum,
+            ),
+            (
+                address.as_str(),
+                MintConnectionError::WrongHrp("bc".to_string()),
+            ),
+            (&INVITE_CODE[..40], MintConnectionError::TruncatedInvite),
+            (
+                "mint.example.com",
+                MintConnectionError::InvalidUrl(url::ParseError::RelativeUrlWithoutBase),
+            ),
+            (
+                "https://",
+                MintConnectionError::InvalidUrl(url::ParseError::EmptyHost),
+            ),
+            (
+                "ftp://mint.example.com",
+                MintConnectionError::UnsupportedScheme("ftp".to_string()),
+            ),
+            (
+                "wss://mint.example.com",
+                MintConnectionError::UnsupportedScheme("wss".to_string()),
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                MintConnectionInfo::parse(input).unwrap_err(),
+                expected,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_mint_url() {
+        let same = [
+            ("https://Mint.Example.com/", "https://mint.example.com"),
+            ("https://mint.example.com//", "https://mint.example.com"),
+            ("HTTPS://MINT.EXAMPLE.COM", "https://mint.example.com"),
+            ("https://mint.example.com:443", "https://mint.example.com"),
+            ("http://mint.example.com:80/", "http://mint.example.com"),
+            ("https://mint.example.com/#info", "https://mint.example.com"),
+            ("  https://mint.example.com\n", "https://mint.example.com"),
+            (
+                "https://mint.example.com/Bitcoin//",
+                "https://mint.example.com/Bitcoin",
+            ),
+        ];
+        for (a, b) in same {
+            assert_eq!(
+                normalize_mint_url(a).unwrap(),
+                normalize_mint_url(b).unwrap(),
+                "{a} and {b} should be the same mint"
+            );
+        }
+        assert_eq!(
+            normalize_mint_url("https://Mint.Example.com//")
+                .unwrap()
+                .to_string(),
+            "https://mint.example.com"
+        );
+
+        let different = [
+            ("https://mint.example.com", "http://mint.example.com"),
+            ("https://mint.example.com", "https://mint.example.com:8443"),
+            (
+                "https://mint.example.com",
+                "https://mint.example.com/Bitcoin",
+            ),
+            (
+                "https://mint.example.com/Bitcoin",
+                "https://mint.example.com/bitcoin",
+            ),
+            ("https://mint.example.com", "https://other.example.com"),
+        ];
+        for (a, b) in different {
+            assert_ne!(
+                normalize_mint_url(a).unwrap(),
+                normalize_mint_url(b).unwrap(),
+                "{a} and {b} should be different mints"
+            );
+        }
+
+        assert!(normalize_mint_url("ftp://mint.example.com").is_err());
+        assert!(normalize_mint_url("not a url").is_err());
+
+        // Parsing what the user typed goes through the same normalization
+        assert_eq!(
+            MintConnectionInfo::from_str("https://Mint.Example.com:443//").unwrap(),
+            MintConnectionInfo::from_str("https://mint.example.com").unwrap()
+        );
+        assert!(same_mint_url(
+            &MintUrl::from_str("https://mint.example.com/").unwrap(),
+            &normalize_mint_url("https://MINT.example.com:443").unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_requires_tor() {
+        let onion = "wss://fedimintxyzabcdefghijklmnopqrstuvwxyz234567abcdefghijklmnopq.onion/";
+        let guardian = |peer: u16, url: &str| {
+            InviteCode::new(
+                SafeUrl::parse(url).unwrap(),
+                PeerId::from(peer),
+                FederationId::dummy(),
+                None,
+            )
+        };
+
+        let info = MintConnectionInfo::Fedimint(guardian(0, onion));
+        assert!(info.requires_tor());
+
+        // One clearnet guardian is enough to get in without Tor
+        let mixed = merge_invite_codes(&[
+            guardian(0, onion),
+            guardian(1, "wss://guardian.example.com/"),
+        ])
+        .unwrap();
+        assert!(!MintConnectionInfo::Fedimint(mixed).requires_tor());
+
+        let cashu = MintConnectionInfo::from_str("http://mintabcdefghijklmnop.onion").unwrap();
+        assert!(cashu.requires_tor());
+        let cashu = MintConnectionInfo::from_str("https://mint.example.com").unwrap();
+        assert!(!cashu.requires_tor());
+        assert!(
+            !MintConnectionInfo::from_str(INVITE_CODE)
+                .unwrap()
+                .requires_tor()
+        );
+    }
+
+    #[test]
+    fn test_mint_connection_info_accessors() {
+        let fedimint = MintConnectionInfo::from_str(INVITE_CODE).unwrap();
+        assert_eq!(fedimint.kind(), MintKind::Fedimint);
+        assert!(fedimint.is_fedimint());
+        assert!(!fedimint.is_cashu());
+        assert_eq!(
+            fedimint.federation_id(),
+            Some(InviteCode::from_str(INVITE_CODE).unwrap().federation_id())
+        );
+        assert_eq!(fedimint.mint_url(), None);
+
+        let cashu = MintConnectionInfo::from_str("https://mint.example.com").unwrap();
+        assert_eq!(cashu.kind(), MintKind::Cashu);
+        assert!(cashu.is_cashu());
+        assert!(!cashu.is_fedimint());
+        assert_eq!(cashu.federation_id(), None);
+        assert_eq!(
+            cashu.mint_url(),
+            Some(MintUrl::from_str("https://mint.example.com").unwrap())
+        );
+
+        assert!(MintConnectionInfo::from_str("not a mint").is_err());
+    }
+
+    #[test]
+    fn test_cheapest_send_fee() {
+        let mint = |url: &str| MintIdentifier::Cashu(MintUrl::from_str(url).unwrap());
+        let estimate = |url: &str, fee: Result<u64, &str>, sufficient_balance| SendFeeEstimate {
+            mint: mint(url),
+            fee: fee.map(Amount::from_sats).map_err(str::to_string),
+            sufficient_balance,
+        };
+
+        let estimates = [
+            estimate("https://a.example.com", Ok(31), true),
+            estimate("https://b.example.com", Ok(12), true),
+            // Cheaper still, but it can't cover the invoice
+            estimate("https://c.example.com", Ok(5), false),
+            estimate("https://d.example.com", Err("No quote in time"), false),
+        ];
+        assert_eq!(
+            SendFeeEstimate::cheapest(&estimates).map(|best| &best.mint),
+            Some(&mint("https://b.example.com"))
+        );
+
+        // Nothing to pick when no mint can afford it
+        assert_eq!(SendFeeEstimate::cheapest(&estimates[2..]), None);
+        assert_eq!(SendFeeEstimate::cheapest(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_probe_send_fees() {
+        let mint = |url: &str| MintIdentifier::Cashu(MintUrl::from_str(url).unwrap());
+        let (quick, poor, broken, stuck, also_stuck) = (
+            mint("https://quick.example.com"),
+            mint("https://poor.example.com"),
+            mint("https://broken.example.com"),
+            mint("https://stuck.example.com"),
+            mint("https://also-stuck.example.com"),
+        );
+        let mints = vec![
+            quick.clone(),
+            poor.clone(),
+            broken.clone(),
+            stuck.clone(),
+            also_stuck.clone(),
+        ];
+        let timeout = Duration::from_millis(200);
+
+        let started = std::time::Instant::now();
+        let estimates = probe_send_fees(mints, Amount::from_sats(1_000), timeout, |mint| {
+            let (quick, poor, broken) = (quick.clone(), poor.clone(), broken.clone());
+            async move {
+                if mint == quick {
+                    Ok((Amount::from_sats(12), Amount::from_sats(5_000)))
+                } else if mint == poor {
+                    Ok((Amount::from_sats(3), Amount::from_sats(1_000)))
+                } else if mint == broken {
+                    Err(anyhow!("Gateway is down"))
+                } else {
+                    std::future::pending().await
+                }
+            }
+        })
+        .await;
+
+        // The two that never answer ran out their time together, not one after the other
+        assert!(started.elapsed() < timeout * 2);
+
+        let fees: Vec<_> = estimates
+            .iter()
+            .map(|estimate| {
+                (
+                    &estimate.mint,
+                    estimate.fee.clone(),
+                    estimate.sufficient_balance,
+                )
+            })
+            .collect();
+        assert_eq!(
+            fees,
+            vec![
+                (&quick, Ok(Amount::from_sats(12)), true),
+                (&poor, Ok(Amount::from_sats(3)), false),
+                (&broken, Err("Gateway is down".to_string()), false),
+                (&stuck, Err("No quote in time".to_string()), false),
+                (&also_stuck, Err("No quote in time".to_string()), false),
+            ]
+        );
+        assert_eq!(
+            SendFeeEstimate::cheapest(&estimates).map(|best| &best.mint),
+            Some(&quick)
+        );
+    }
+
+    #[test]
+    fn test_mint_max_balance() {
+        let mut item = MintItem::unknown(FederationId::dummy());
+        item.balance = 150_000;
+        assert_eq!(item.max_balance(), None);
+        assert!(!item.over_max_balance());
+
+        // The federation's recommendation, as its meta field gives it
+        item.metadata.max_balance_msats = Some("100000000".to_string());
+        assert_eq!(item.max_balance(), Some(Amount::from_sats(100_000)));
+        assert!(item.over_max_balance());
+
+        // The user's own cap wins over it
+        item.max_balance_override = Some(Amount::from_sats(200_000));
+        assert!(!item.over_max_balance());
+        item.balance = 200_000;
+        assert!(!item.over_max_balance());
+        item.balance = 200_001;
+        assert!(item.over_max_balance());
+
+        // A meta value that isn't a number is no limit at all
+        item.max_balance_override = None;
+        item.metadata.max_balance_msats = Some("lots".to_string());
+        assert_eq!(item.max_balance(), None);
+    }
+
+    #[test]
+    fn test_idle_empty_days() {
+        const DAY: u64 = 24 * 60 * 60;
+        let now = 1_750_000_000;
+        let mut item = MintItem::unknown(FederationId::dummy());
+
+        // Nothing known about when it was last used
+        assert_eq!(item.idle_empty_days(now), None);
+
+        // Joining counts as activity until it's been used
+        item.joined_at = Some(now - 40 * DAY);
+        assert_eq!(item.idle_empty_days(now), Some(40));
+        item.last_used_at = Some(now - 3 * DAY - 1);
+        assert_eq!(item.last_activity(), item.last_used_at);
+        assert_eq!(item.idle_empty_days(now), Some(3));
+
+        // Holding or expecting anything isn't idle
+        item.balance = 1;
+        assert_eq!(item.idle_empty_days(now), None);
+        item.balance = 0;
+        item.pending.incoming = 500;
+        assert_eq!(item.idle_empty_days(now), None);
+        item.pending.incoming = 0;
+        item.syncing = true;
+        assert_eq!(item.idle_empty_days(now), None);
+    }
+
+    #[test]
+    fn test_pending_amounts() {
+        use crate::db_models::PaymentStatus;
+        use crate::db_models::transaction_item::TransactionDirection;
+
+        let fedimint = MintIdentifier::Fedimint(FederationId::dummy());
+        let cashu = MintIdentifier::Cashu(MintUrl::from_str("https://mint.example.com").unwrap());
+        let item = |mint: &MintIdentifier, direction, status, amount| TransactionItem {
+            amount,
+            direction,
+            status,
+            mint_identifier: mint.clone(),
+            ..TransactionItem::make_dummy()
+        };
+
+        let items = [
+            // A payment in flight counts its fee too
+            item(
+                &fedimint,
+                TransactionDirection::Outgoing,
+                PaymentStatus::Pending,
+                30_000,
+            ),
+            // A deposit waiting on confirmations
+            item(
+                &fedimint,
+                TransactionDirection::Incoming,
+                PaymentStatus::WaitingConfirmation,
+                12_000,
+            ),
+            // An invoice nobody has paid yet isn't on its way
+            item(
+                &cashu,
+                TransactionDirection::Incoming,
+                PaymentStatus::Pending,
+                5_000,
+            ),
+            // Finished ones are already in the balance
+            item(
+                &cashu,
+                TransactionDirection::Outgoing,
+                PaymentStatus::Success,
+                1_000,
+            ),
+            item(
+                &cashu,
+                TransactionDirection::Outgoing,
+                PaymentStatus::Failed,
+                2_000,
+            ),
+        ];
+        let pending = PendingAmounts::tally(&items);
+        assert_eq!(
+            pending.get(&fedimint),
+            Some(&PendingAmounts {
+                incoming: 12_000,
+                outgoing: 30_001,
+            })
+        );
+        assert_eq!(pending.get(&cashu), None);
+        assert!(PendingAmounts::default().is_empty());
+
+        // Once the payment settles there's nothing left pending for it
+        let settled = [item(
+            &fedimint,
+            TransactionDirection::Outgoing,
+            PaymentStatus::Success,
+            30_000,
+        )];
+        assert!(PendingAmounts::tally(&settled).is_empty());
+    }
+
+    #[test]
+    fn test_mint_capabilities() {
+        use crate::db_models::MintCapabilities;
+
+        // Nothing known about the modules yet, so nothing is ruled out
+        let mut item = MintItem::unknown(FederationId::dummy());
+        let caps = item.capabilities();
+        assert!(caps.lightning && caps.on_chain && caps.ecash);
+
+        let kinds = |kinds: &[&'static str]| {
+            Some(
+                kinds
+                    .iter()
+                    .copied()
+                    .map(ModuleKind::from_static_str)
+                    .collect(),
+            )
+        };
+        item.module_kinds = kinds(&["mint", "lnv2", "wallet"]);
+        assert_eq!(
+            item.capabilities(),
+            MintCapabilities {
+                lightning: true,
+                on_chain: true,
+                ecash: true,
+            }
+        );
+
+        // A federation without a wallet module can't do on-chain
+        item.module_kinds = kinds(&["mint", "ln"]);
+        let caps = item.capabilities();
+        assert!(caps.lightning && !caps.on_chain && caps.can_receive() && caps.can_send());
+
+        item.module_kinds = kinds(&["mint"]);
+        assert!(!item.capabilities().can_receive());
+        assert!(!item.capabilities().can_send());
+
+        // Cashu mints are lightning only
+        item.id = MintIdentifier::Cashu(MintUrl::from_str("https://mint.example.com").unwrap());
+        item.module_kinds = None;
+        let caps = item.capabilities();
+        assert!(caps.lightning && !caps.on_chain);
+    }
+}
@@ -2,6 +2,7 @@ use crate::http::{make_get_request_direct, make_get_request_tor};
 use bitcoin::secp256k1::PublicKey;
 use cdk::nuts::MintInfo;
 use fedimint_client::ClientHandleArc;
+use fedimint_core::Amount;
 use fedimint_core::config::ClientConfig;
 use fedimint_core::config::FederationId;
 use fedimint_core::module::serde_json;
@@ -12,6 +13,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::RwLock;
+use url::Url;
 
 /// Global cache of federation metadata
 pub(crate) static CACHE: Lazy<RwLock<HashMap<FederationId, FederationMeta>>> =
@@ -54,10 +56,14 @@ pub struct FederationMeta {
     pub vetted_gateways: Option<String>,
     // undocumented parameters that fedi uses: https://meta.dev.fedibtc.com/meta.json
     pub federation_icon_url: Option<String>,
+    // Not a documented meta field yet, but it's where a federation's homepage would go
+    pub federation_site_url: Option<String>,
     pub meta_external_url: Option<String>,
     pub preview_message: Option<String>,
     pub popup_end_timestamp: Option<String>,
     pub popup_countdown_message: Option<String>,
+    /// The most the federation recommends holding in it
+    pub max_balance_msats: Option<String>,
 }
 
 impl FederationMeta {
@@ -67,12 +73,35 @@ impl FederationMeta {
             .and_then(|s| s.parse().ok())
     }
 
+    pub fn max_balance(&self) -> Option<Amount> {
+        self.max_balance_msats
+            .as_ref()
+            .and_then(|s| s.trim().parse().ok())
+            .map(Amount::from_msats)
+    }
+
     pub fn popup_end_timestamp(&self) -> Option<u64> {
         self.popup_end_timestamp
             .as_ref()
             .and_then(|s| s.parse().ok())
     }
 
+    /// A warning to show the user when the federation has announced it is shutting down
+    pub fn shutdown_notice(&self) -> Option<String> {
+        let expiry = self.federation_expiry_timestamp()?;
+        let date = chrono::DateTime::from_timestamp(expiry as i64, 0)?;
+        Some(format!(
+            "This federation has announced it is shutting down on {} — withdraw your funds",
+            date.format("%B %-d, %Y")
+        ))
+    }
+
+    /// The federation's website, as long as it's a plain web link that's safe to open
+    pub fn site_url(&self) -> Option<Url> {
+        let url = Url::parse(self.federation_site_url.as_deref()?).ok()?;
+        matches!(url.scheme(), "https" | "http").then_some(url)
+    }
+
     pub fn vetted_gateways(&self) -> Vec<PublicKey> {
         match self.vetted_gateways.as_deref() {
             None => vec![],
@@ -89,10 +118,12 @@ impl From<Option<MintInfo>> for FederationMeta {
             welcome_message: None,
             vetted_gateways: None,
             federation_icon_url: info.as_ref().and_then(|i| i.icon_url.clone()),
+            federation_site_url: None,
             meta_external_url: None,
             preview_message: info.and_then(|i| i.description),
             popup_end_timestamp: None,
             popup_countdown_message: None,
+            max_balance_msats: None,
         }
     }
 }
@@ -166,6 +197,10 @@ pub(crate) async fn get_federation_metadata(
             data.get_meta("federation_icon_url"),
             config.as_ref().and_then(|c| c.federation_icon_url.clone()),
         ),
+        federation_site_url: merge_values(
+            data.get_meta("federation_site_url"),
+            config.as_ref().and_then(|c| c.federation_site_url.clone()),
+        ),
         preview_message: merge_values(
             data.get_meta("preview_message"),
             config.as_ref().and_then(|c| c.preview_message.clone()),
@@ -180,6 +215,10 @@ pub(crate) async fn get_federation_metadata(
                 .as_ref()
                 .and_then(|c| c.popup_countdown_message.clone()),
         ),
+        max_balance_msats: merge_values(
+            data.get_meta("max_balance_msats"),
+            config.as_ref().and_then(|c| c.max_balance_msats.clone()),
+        ),
     }
 }
 
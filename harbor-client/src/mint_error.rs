@@ -0,0 +1,296 @@
+use std::fmt;
+
+/// Roughly what went wrong when previewing or joining a mint, so the UI can say what to do next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddMintErrorKind {
+    /// The machine has no internet connection, so nothing was tried
+    Offline,
+    /// Couldn't reach the mint at all, or it's only reachable over Tor and Tor is off
+    NetworkUnreachable,
+    /// Reached the guardians or mint, but they turned the request down
+    GuardianRejected,
+    /// The mint runs on a different bitcoin network than this wallet
+    WrongNetwork,
+    /// The config that came back didn't check out against the invite code
+    InvalidConfig,
+    /// The wallet has joined this mint already
+    AlreadyJoined,
+    /// Gave up waiting for the mint to answer
+    TimedOut,
+    /// Something went wrong saving to the wallet's own database, often a full disk or a data
+    /// directory Harbor can't write to
+    Database,
+    /// Anything else, the message is all there is to go on
+    Other,
+}
+
+impl AddMintErrorKind {
+    /// A name for the category that stays the same across releases, for storing
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::Offline => "offline",
+            Self::NetworkUnreachable => "network_unreachable",
+            Self::GuardianRejected => "guardian_rejected",
+            Self::WrongNetwork => "wrong_network",
+            Self::InvalidConfig => "invalid_config",
+            Self::AlreadyJoined => "already_joined",
+            Self::TimedOut => "timed_out",
+            Self::Database => "database",
+            Self::Other => "other",
+        }
+    }
+
+    /// The category stored under `code`, anything unknown is [`Self::Other`]
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "offline" => Self::Offline,
+            "network_unreachable" => Self::NetworkUnreachable,
+            "guardian_rejected" => Self::GuardianRejected,
+            "wrong_network" => Self::WrongNetwork,
+            "invalid_config" => Self::InvalidConfig,
+            "already_joined" => Self::AlreadyJoined,
+            "timed_out" => Self::TimedOut,
+            "database" => Self::Database,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl fmt::Display for AddMintErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Offline => "You appear to be offline",
+            Self::NetworkUnreachable => "Couldn't reach the mint",
+            Self::GuardianRejected => "The mint turned down the request",
+            Self::WrongNetwork => "The mint is on a different network",
+            Self::InvalidConfig => "The mint's config couldn't be verified",
+            Self::AlreadyJoined => "Already joined",
+            Self::TimedOut => "Timed out",
+            Self::Database => "Couldn't save to storage",
+            Self::Other => "Something went wrong",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// A failed preview or join, with the category worked out once in the client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddMintError {
+    pub kind: AddMintErrorKind,
+    pub message: String,
+    /// What caused `message`, outermost first, for bug reports
+    pub chain: Vec<String>,
+}
+
+impl AddMintError {
+    pub fn new(kind: AddMintErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            chain: Vec::new(),
+        }
+    }
+
+    /// Works out the category of an error from preview or join. Errors Harbor raises itself
+    /// carry their category, anything from fedimint or cdk is sorted by where it came from
+    /// and failing that by what it says.
+    pub fn classify(error: &anyhow::Error) -> Self {
+        let chain = error.chain().skip(1).map(ToString::to_string).collect();
+        if let Some(e) = error.downcast_ref::<Self>() {
+            return Self { chain, ..e.clone() };
+        }
+
+        let kind = error
+            .chain()
+            .find_map(source_kind)
+            .unwrap_or_else(|| message_kind(&format!("{error:#}")));
+        Self {
+            chain,
+            ..Self::new(kind, error.to_string())
+        }
+    }
+}
+
+impl fmt::Display for AddMintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for AddMintError {}
+
+fn source_kind(source: &(dyn std::error::Error + 'static)) -> Option<AddMintErrorKind> {
+    if source.is::<tokio::time::error::Elapsed>() {
+        return Some(AddMintErrorKind::TimedOut);
+    }
+    if source.is::<diesel::result::Error>() || source.is::<diesel::r2d2::PoolError>() {
+        return Some(AddMintErrorKind::Database);
+    }
+    if let Some(e) = source.downcast_ref::<std::io::Error>() {
+        use std::io::ErrorKind;
+        return match e.kind() {
+            ErrorKind::TimedOut => Some(AddMintErrorKind::TimedOut),
+            ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::AddrNotAvailable => Some(AddMintErrorKind::NetworkUnreachable),
+            ErrorKind::StorageFull
+            | ErrorKind::ReadOnlyFilesystem
+            | ErrorKind::PermissionDenied => Some(AddMintErrorKind::Database),
+            _ => None,
+        };
+    }
+    None
+}
+
+// Fedimint and cdk mostly hand back stringly errors, so this is the fallback. Timeouts are
+// checked first since they're often reported as a failed connection too.
+fn message_kind(message: &str) -> AddMintErrorKind {
+    let message = message.to_ascii_lowercase();
+    let any = |needles: &[&str]| needles.iter().any(|n| message.contains(n));
+
+    if any(&["timed out", "timeout", "deadline"]) {
+        AddMintErrorKind::TimedOut
+    } else if any(&["network mismatch", "different network", "wrong network"]) {
+        AddMintErrorKind::WrongNetwork
+    } else if any(&["already added", "already joined", "already exists"]) {
+        AddMintErrorKind::AlreadyJoined
+    } else if any(&[
+        "signature",
+        "config hash",
+        "invalid config",
+        "federation id mismatch",
+    ]) {
+        AddMintErrorKind::InvalidConfig
+    } else if any(&[
+        "database",
+        "sqlite",
+        "diesel",
+        "disk is full",
+        "no space left",
+        "disk i/o",
+        "read-only file system",
+        "readonly",
+    ]) {
+        AddMintErrorKind::Database
+    } else if any(&["rejected", "unauthorized", "forbidden", "not allowed"]) {
+        AddMintErrorKind::GuardianRejected
+    } else if any(&[
+        "connection refused",
+        "connect",
+        "dns",
+        "unreachable",
+        "no route to host",
+        "resolve",
+        "only reachable over tor",
+    ]) {
+        AddMintErrorKind::NetworkUnreachable
+    } else {
+        AddMintErrorKind::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_classify_typed_errors() {
+        // Errors Harbor raises itself keep the category they were given
+        let typed = anyhow::Error::new(AddMintError::new(
+            AddMintErrorKind::AlreadyJoined,
+            "Federation already added",
+        ));
+        assert_eq!(
+            AddMintError::classify(&typed),
+            AddMintError::new(AddMintErrorKind::AlreadyJoined, "Federation already added")
+        );
+
+        let refused = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused");
+        let e = anyhow::Error::new(refused).context("Could not download federation info");
+        assert_eq!(
+            AddMintError::classify(&e).kind,
+            AddMintErrorKind::NetworkUnreachable
+        );
+        assert_eq!(
+            AddMintError::classify(&e).message,
+            "Could not download federation info"
+        );
+        assert_eq!(AddMintError::classify(&e).chain, vec!["refused"]);
+
+        let e = anyhow::Error::new(diesel::result::Error::NotFound);
+        assert_eq!(AddMintError::classify(&e).kind, AddMintErrorKind::Database);
+
+        let full = std::io::Error::new(std::io::ErrorKind::StorageFull, "full");
+        assert_eq!(
+            AddMintError::classify(&anyhow::Error::new(full)).kind,
+            AddMintErrorKind::Database
+        );
+
+        let io_timeout = std::io::Error::new(std::io::ErrorKind::TimedOut, "slow");
+        assert_eq!(
+            AddMintError::classify(&anyhow::Error::new(io_timeout)).kind,
+            AddMintErrorKind::TimedOut
+        );
+    }
+
+    #[tokio::test]
+    async fn test_classify_elapsed() {
+        let elapsed = tokio::time::timeout(
+            std::time::Duration::from_millis(1),
+            std::future::pending::<()>(),
+        )
+        .await
+        .unwrap_err();
+        let e = anyhow::Error::new(elapsed);
+        assert_eq!(AddMintError::classify(&e).kind, AddMintErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_classify_messages() {
+        let cases = [
+            (
+                "Failed to download client config: request timed out",
+                AddMintErrorKind::TimedOut,
+            ),
+            (
+                "Network mismatch, expected: signet",
+                AddMintErrorKind::WrongNetwork,
+            ),
+            ("Mint already added", AddMintErrorKind::AlreadyJoined),
+            (
+                "Invalid signature on client config",
+                AddMintErrorKind::InvalidConfig,
+            ),
+            (
+                "Request rejected by peer 0: unauthorized",
+                AddMintErrorKind::GuardianRejected,
+            ),
+            (
+                "error trying to connect: dns error: failed to lookup address",
+                AddMintErrorKind::NetworkUnreachable,
+            ),
+            (
+                "This mint is only reachable over Tor, turn on Tor in Settings",
+                AddMintErrorKind::NetworkUnreachable,
+            ),
+            ("Could not open sqlite file", AddMintErrorKind::Database),
+            (
+                "No space left on device (os error 28)",
+                AddMintErrorKind::Database,
+            ),
+            ("disk I/O error", AddMintErrorKind::Database),
+            ("Unexpected module kind", AddMintErrorKind::Other),
+        ];
+
+        for (message, kind) in cases {
+            assert_eq!(
+                AddMintError::classify(&anyhow!(message)).kind,
+                kind,
+                "{message}"
+            );
+        }
+    }
+}
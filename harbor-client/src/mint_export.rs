@@ -0,0 +1,293 @@
+use crate::db_models::MintItem;
+use crate::{MintIdentifier, normalize_mint_url};
+use anyhow::anyhow;
+use bitcoin::Network;
+use bitcoin::hashes::{Hash, sha256};
+use bitcoin::secp256k1::{Message, Secp256k1, XOnlyPublicKey, schnorr};
+use fedimint_core::config::FederationId;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use url::Url;
+
+/// Bump this whenever the export format changes so imports can tell versions apart
+pub const MINT_EXPORT_VERSION: u32 = 1;
+
+/// A portable list of the mints a wallet has joined.
+///
+/// This only holds what is needed to find and rejoin the mints again,
+/// it never contains seed words, ecash, or any other key material.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MintListExport {
+    pub version: u32,
+    pub network: Network,
+    pub mints: Vec<ExportedMint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExportedMint {
+    Fedimint {
+        federation_id: String,
+        invite_codes: Vec<String>,
+        nickname: Option<String>,
+        archived: bool,
+        // Added after the first version, so older files won't have it
+        #[serde(default)]
+        notes: Option<String>,
+    },
+    Cashu {
+        mint_url: String,
+        nickname: Option<String>,
+        archived: bool,
+        #[serde(default)]
+        notes: Option<String>,
+    },
+}
+
+impl MintListExport {
+    pub fn from_mint_items(items: &[MintItem], network: Network) -> Self {
+        let mints = items
+            .iter()
+            .map(|item| match &item.id {
+                MintIdentifier::Fedimint(id) => ExportedMint::Fedimint {
+                    federation_id: id.to_string(),
+                    invite_codes: item.invite_code.iter().map(ToString::to_string).collect(),
+                    nickname: item.nickname.clone(),
+                    archived: !item.active,
+                    notes: item.notes.clone(),
+                },
+                MintIdentifier::Cashu(url) => ExportedMint::Cashu {
+                    mint_url: url.to_string(),
+                    nickname: item.nickname.clone(),
+                    archived: !item.active,
+                    notes: item.notes.clone(),
+                },
+            })
+            .collect();
+
+        Self {
+            version: MINT_EXPORT_VERSION,
+            network,
+            mints,
+        }
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        // Check the version on its own first so a newer file gets a useful error
+        // instead of whatever field happened to fail to parse
+        #[derive(Deserialize)]
+        struct Versioned {
+            version: u32,
+        }
+
+        let Versioned { version } =
+            serde_json::from_str(json).map_err(|_| anyhow!("Not a Harbor mint list file"))?;
+        if version > MINT_EXPORT_VERSION {
+            return Err(anyhow!(
+                "This mint list was made by a newer version of Harbor (format version {version})"
+            ));
+        }
+
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+impl ExportedMint {
+    pub fn id(&self) -> anyhow::Result<MintIdentifier> {
+        match self {
+            Self::Fedimint { federation_id, .. } => Ok(MintIdentifier::Fedimint(
+                FederationId::from_str(federation_id)?,
+            )),
+            Self::Cashu { mint_url, .. } => {
+                Ok(MintIdentifier::Cashu(normalize_mint_url(mint_url)?))
+            }
+        }
+    }
+
+    /// A short label for progress messages and error reports
+    pub fn label(&self) -> String {
+        match self {
+            Self::Fedimint {
+                nickname: Some(nickname),
+                ..
+            }
+            | Self::Cashu {
+                nickname: Some(nickname),
+                ..
+            } => nickname.clone(),
+            Self::Fedimint { federation_id, .. } => format!(
+                "Federation {}",
+                federation_id.chars().take(8).collect::<String>()
+            ),
+            Self::Cashu { mint_url, .. } => mint_url.clone(),
+        }
+    }
+
+    pub fn nickname(&self) -> Option<String> {
+        match self {
+            Self::Fedimint { nickname, .. } | Self::Cashu { nickname, .. } => nickname.clone(),
+        }
+    }
+
+    pub fn notes(&self) -> Option<String> {
+        match self {
+            Self::Fedimint { notes, .. } | Self::Cashu { notes, .. } => notes.clone(),
+        }
+    }
+
+    pub const fn archived(&self) -> bool {
+        match self {
+            Self::Fedimint { archived, .. } | Self::Cashu { archived, .. } => *archived,
+        }
+    }
+}
+
+/// Where the detached signature for a list published at `url` is expected: the same path with
+/// `.sig` on the end, keeping any query or fragment where they were
+pub fn signature_url(url: &str) -> anyhow::Result<String> {
+    let mut url = Url::parse(url).map_err(|e| anyhow!("Not a valid list URL: {e}"))?;
+    let path = format!("{}.sig", url.path());
+    url.set_path(&path);
+    Ok(url.to_string())
+}
+
+/// Checks a detached signature over a published list: a hex BIP-340 schnorr signature over the
+/// SHA256 of the file exactly as it was served, the same scheme nostr events are signed with
+pub fn verify_list_signature(
+    payload: &[u8],
+    signature: &str,
+    signer: &XOnlyPublicKey,
+) -> anyhow::Result<()> {
+    let signature = schnorr::Signature::from_str(signature.trim())
+        .map_err(|_| anyhow!("The list's signature file doesn't hold a signature"))?;
+    let message = Message::from_digest(sha256::Hash::hash(payload).to_byte_array());
+    Secp256k1::verification_only()
+        .verify_schnorr(&signature, &message, signer)
+        .map_err(|_| {
+            anyhow!(
+                "The signature doesn't match this list and key, so nothing was imported. The list may have been changed since it was signed."
+            )
+        })
+}
+
+/// A mint list fetched from a URL, waiting for the user to look it over
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedMintList {
+    pub url: String,
+    pub export: MintListExport,
+    /// The npub whose signature checked out, `None` for a list fetched without one
+    pub verified_by: Option<String>,
+}
+
+/// The outcome of importing a mint list, reported back to the UI when every item has been tried
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MintImportSummary {
+    pub joined: usize,
+    pub skipped: usize,
+    /// The label of each mint that failed, alongside the reason
+    pub failed: Vec<(String, String)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cdk::mint_url::MintUrl;
+    use fedimint_core::invite_code::InviteCode;
+
+    const FEDERATION_ID: &str = "c8d423964c7ad944d30f57359b6e5b260e211dcfdb945140e28d4df51fd572d2";
+    const INVITE_CODE: &str = "fed11qgqzc2nhwden5te0vejkg6tdd9h8gepwvejkg6tdd9h8garhduhx6at5d9h8jmn9wshxxmmd9uqqzgxg6s3evnr6m9zdxr6hxkdkukexpcs3mn7mj3g5pc5dfh63l4tj6g9zk4er";
+
+    #[test]
+    fn test_mint_list_export() {
+        let mut fedimint = MintItem::unknown(FederationId::from_str(FEDERATION_ID).unwrap());
+        fedimint.nickname = Some("Meetup mint".to_string());
+        fedimint.invite_code = Some(InviteCode::from_str(INVITE_CODE).unwrap());
+        fedimint.notes = Some("Fees spike on weekends".to_string());
+
+        let mut cashu = MintItem::unknown(FederationId::from_str(FEDERATION_ID).unwrap());
+        cashu.id = MintIdentifier::Cashu(MintUrl::from_str("https://mint.example.com").unwrap());
+        cashu.active = false;
+
+        let export = MintListExport::from_mint_items(&[fedimint, cashu], Network::Signet);
+        assert_eq!(export.version, MINT_EXPORT_VERSION);
+        assert_eq!(
+            export.mints,
+            vec![
+                ExportedMint::Fedimint {
+                    federation_id: FEDERATION_ID.to_string(),
+                    invite_codes: vec![INVITE_CODE.to_string()],
+                    nickname: Some("Meetup mint".to_string()),
+                    archived: false,
+                    notes: Some("Fees spike on weekends".to_string()),
+                },
+                ExportedMint::Cashu {
+                    mint_url: "https://mint.example.com".to_string(),
+                    nickname: None,
+                    archived: true,
+                    notes: None,
+                },
+            ]
+        );
+
+        let json = export.to_json().unwrap();
+        let parsed = MintListExport::from_json(&json).unwrap();
+        assert_eq!(parsed, export);
+    }
+
+    #[test]
+    fn test_list_signature() {
+        let secp = Secp256k1::new();
+        let keypair = bitcoin::secp256k1::Keypair::from_seckey_slice(&secp, &[7; 32]).unwrap();
+        let (signer, _) = keypair.x_only_public_key();
+        let payload = br#"{"version":1,"network":"signet","mints":[]}"#;
+        let message = Message::from_digest(sha256::Hash::hash(payload).to_byte_array());
+        let signature = secp
+            .sign_schnorr_no_aux_rand(&message, &keypair)
+            .to_string();
+
+        // Signature files usually end in a newline
+        let signature_file = format!("{signature}\n");
+        assert!(verify_list_signature(payload, &signature_file, &signer).is_ok());
+
+        // A changed list, another key or a file that isn't a signature all fail
+        let tampered = br#"{"version":1,"network":"bitcoin","mints":[]}"#;
+        assert!(verify_list_signature(tampered, &signature, &signer).is_err());
+        let other = bitcoin::secp256k1::Keypair::from_seckey_slice(&secp, &[8; 32]).unwrap();
+        assert!(verify_list_signature(payload, &signature, &other.x_only_public_key().0).is_err());
+        assert!(verify_list_signature(payload, "<html>", &signer).is_err());
+
+        assert_eq!(
+            signature_url("https://example.com/mints.json").unwrap(),
+            "https://example.com/mints.json.sig"
+        );
+        assert_eq!(
+            signature_url("https://example.com/mints.json?v=2#top").unwrap(),
+            "https://example.com/mints.json.sig?v=2#top"
+        );
+        assert!(signature_url("not a url").is_err());
+    }
+
+    #[test]
+    fn test_mint_list_import_version() {
+        let newer = format!(
+            r#"{{"version": {}, "network": "signet", "mints": []}}"#,
+            MINT_EXPORT_VERSION + 1
+        );
+        let err = MintListExport::from_json(&newer).unwrap_err();
+        assert!(err.to_string().contains("newer version of Harbor"));
+
+        assert!(MintListExport::from_json("not json").is_err());
+
+        // Files from before notes were exported still load
+        let without_notes = r#"{"version": 1, "network": "signet", "mints": [
+            {"kind": "cashu", "mint_url": "https://mint.example.com", "nickname": null, "archived": false}
+        ]}"#;
+        let parsed = MintListExport::from_json(without_notes).unwrap();
+        assert_eq!(parsed.mints[0].notes(), None);
+        assert!(MintListExport::from_json(r#"{"mints": []}"#).is_err());
+    }
+}
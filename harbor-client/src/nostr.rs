@@ -0,0 +1,232 @@
+use anyhow::anyhow;
+use bip39::{Language, Mnemonic};
+use bitcoin::Network;
+use bitcoin::bech32::{self, Bech32, Hrp};
+use bitcoin::bip32::{DerivationPath, Xpriv};
+use bitcoin::secp256k1::{Secp256k1, SecretKey, XOnlyPublicKey};
+use std::str::FromStr;
+
+const NSEC_HRP: &str = "nsec";
+const NPUB_HRP: &str = "npub";
+
+// NIP-06, so other clients restoring from the same seed end up with the same key
+const NIP06_PATH: &str = "m/44'/1237'/0'/0/0";
+
+/// Reads a public key given as an npub or as hex, the two ways people share them
+pub fn parse_public_key(s: &str) -> anyhow::Result<XOnlyPublicKey> {
+    let s = s.trim();
+    if let Ok(key) = XOnlyPublicKey::from_str(s) {
+        return Ok(key);
+    }
+    let (hrp, data) = bech32::decode(s).map_err(|_| anyhow!("Not an npub or hex public key"))?;
+    let hrp = hrp.to_lowercase();
+    if hrp != NPUB_HRP {
+        return Err(anyhow!("Expected an npub, found a {hrp} code"));
+    }
+    XOnlyPublicKey::from_slice(&data).map_err(|_| anyhow!("This npub doesn't hold a valid key"))
+}
+
+pub fn npub(public_key: &XOnlyPublicKey) -> String {
+    let hrp = Hrp::parse_unchecked(NPUB_HRP);
+    bech32::encode::<Bech32>(hrp, &public_key.serialize())
+        .expect("a public key is short enough for bech32")
+}
+
+/// Where the user's nostr key came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NostrKeySource {
+    /// Generated or imported, it has to be backed up on its own
+    #[default]
+    Separate,
+    /// Derived from the wallet seed, restoring the wallet brings it back
+    Seed,
+}
+
+impl NostrKeySource {
+    pub const ALL: [Self; 2] = [Self::Seed, Self::Separate];
+}
+
+impl std::fmt::Display for NostrKeySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Separate => write!(f, "Imported key"),
+            Self::Seed => write!(f, "Derived from seed"),
+        }
+    }
+}
+
+/// What the UI gets to know about the user's nostr key, which is never the secret
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NostrPublicIdentity {
+    pub npub: String,
+    pub source: NostrKeySource,
+}
+
+/// Why a pasted key couldn't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NostrKeyError {
+    /// Not bech32 at all, or the checksum doesn't match
+    NotBech32,
+    /// Valid bech32 for something other than a secret key, usually an npub pasted by mistake
+    WrongHrp(String),
+    /// An nsec that doesn't hold a usable secp256k1 secret key
+    InvalidKey,
+}
+
+impl std::fmt::Display for NostrKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotBech32 => write!(f, "Not an nsec, check it was copied in full"),
+            Self::WrongHrp(hrp) if hrp == NPUB_HRP => {
+                write!(f, "That's a public key, the secret key starts with nsec")
+            }
+            Self::WrongHrp(hrp) => write!(f, "Expected an nsec, found a {hrp} code"),
+            Self::InvalidKey => write!(f, "This nsec doesn't hold a valid key"),
+        }
+    }
+}
+
+impl std::error::Error for NostrKeyError {}
+
+/// The user's nostr keypair. Neither `Debug` nor anything else public shows the secret, it
+/// only leaves as the hex saved in the encrypted database.
+#[derive(Clone, PartialEq, Eq)]
+pub struct NostrIdentity {
+    secret_key: SecretKey,
+}
+
+impl std::fmt::Debug for NostrIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NostrIdentity({})", self.npub())
+    }
+}
+
+impl NostrIdentity {
+    /// A new random key, the entropy comes from the same place the wallet seed's does
+    pub fn generate() -> anyhow::Result<Self> {
+        let entropy = Mnemonic::generate_in(Language::English, 24)?.to_entropy();
+        let secret_key = SecretKey::from_slice(&entropy)?;
+        Ok(Self { secret_key })
+    }
+
+    /// The key NIP-06 derives from the wallet seed, with no passphrase like the rest of
+    /// the wallet
+    pub fn from_seed(mnemonic: &Mnemonic) -> anyhow::Result<Self> {
+        let secp = Secp256k1::new();
+        let seed = mnemonic.to_seed_normalized("");
+        let path = DerivationPath::from_str(NIP06_PATH)?;
+        let xpriv = Xpriv::new_master(Network::Bitcoin, &seed)?.derive_priv(&secp, &path)?;
+        Ok(Self {
+            secret_key: xpriv.private_key,
+        })
+    }
+
+    pub fn from_nsec(nsec: &str) -> Result<Self, NostrKeyError> {
+        let (hrp, data) = bech32::decode(nsec.trim()).map_err(|_| NostrKeyError::NotBech32)?;
+        let hrp = hrp.to_lowercase();
+        if hrp != NSEC_HRP {
+            return Err(NostrKeyError::WrongHrp(hrp));
+        }
+        let secret_key = SecretKey::from_slice(&data).map_err(|_| NostrKeyError::InvalidKey)?;
+        Ok(Self { secret_key })
+    }
+
+    pub(crate) fn from_hex(hex: &str) -> anyhow::Result<Self> {
+        let bytes = hex::decode(hex)?;
+        Ok(Self {
+            secret_key: SecretKey::from_slice(&bytes)?,
+        })
+    }
+
+    // How the key is kept in the profile table
+    pub(crate) fn to_hex(&self) -> String {
+        hex::encode(self.secret_key.secret_bytes())
+    }
+
+    pub fn public_key(&self) -> XOnlyPublicKey {
+        self.secret_key
+            .x_only_public_key(&Secp256k1::signing_only())
+            .0
+    }
+
+    pub fn npub(&self) -> String {
+        npub(&self.public_key())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The NIP-19 test vector
+    const SECRET_HEX: &str = "67dea2ed018072d675f5415ecfaed7d2597555e202d85b3d65ea4e58d2d92ffa";
+    const NSEC: &str = "nsec1vl029mgpspedva04g90vltkh6fvh240zqtv9k0t9af8935ke9laqsnlfe5";
+    const NPUB: &str = "npub10elfcs4fr0l0r8af98jlmgdh9c8tcxjvz9qkw038js35mp4dma8qzvjptg";
+
+    #[test]
+    fn test_nostr_identity() {
+        let identity = NostrIdentity::from_nsec(NSEC).unwrap();
+        assert_eq!(identity.to_hex(), SECRET_HEX);
+        assert_eq!(NostrIdentity::from_hex(SECRET_HEX).unwrap(), identity);
+
+        // Nothing but the npub shows up when it's printed
+        let printed = format!("{identity:?}");
+        assert!(!printed.contains(SECRET_HEX));
+        assert!(!printed.contains(NSEC));
+
+        // Pasting with stray whitespace is fine, anything else that isn't an nsec is not
+        assert!(NostrIdentity::from_nsec(&format!(" {NSEC}\n")).is_ok());
+        assert_eq!(
+            NostrIdentity::from_nsec(NPUB),
+            Err(NostrKeyError::WrongHrp("npub".to_string()))
+        );
+        assert_eq!(
+            NostrIdentity::from_nsec(&NSEC[..NSEC.len() - 1]),
+            Err(NostrKeyError::NotBech32)
+        );
+        assert_eq!(
+            NostrIdentity::from_nsec(SECRET_HEX),
+            Err(NostrKeyError::NotBech32)
+        );
+
+        // Public keys are read back as npubs or hex, and the secret key isn't one
+        let public_key = parse_public_key(NPUB).unwrap();
+        assert_eq!(public_key, identity.public_key());
+        assert_eq!(
+            parse_public_key(&public_key.to_string()).unwrap(),
+            public_key
+        );
+        assert_eq!(npub(&public_key), NPUB);
+        assert!(parse_public_key(NSEC).is_err());
+
+        let generated = NostrIdentity::generate().unwrap();
+        assert_ne!(generated, NostrIdentity::generate().unwrap());
+        assert!(generated.npub().starts_with("npub1"));
+    }
+
+    #[test]
+    fn test_nostr_identity_from_seed() {
+        // The NIP-06 test vectors
+        let vectors = [
+            (
+                "leader monkey parrot ring guide accident before fence cannon height naive bean",
+                "7f7ff03d123792d6ac594bfa67bf6d0c0ab55b6b1fdb6249303fe861f1ccba9a",
+                "17162c921dc4d2518f9a101db33695df1afb56ab82f5ff3e5da6eec3ca5cd917",
+            ),
+            (
+                "what bleak badge arrange retreat wolf trade produce cricket blur garlic valid proud rude strong choose busy staff weather area salt hollow arm fade",
+                "c15d739894c81a2fcfd3a2df85a0d2c0dbc47a280d092799f144d73d7ae78add",
+                "d41b22899549e1f3d335a31002cfd382174006e166d3e658e3a5eecdb6463573",
+            ),
+        ];
+        for (words, secret, public) in vectors {
+            let mnemonic = Mnemonic::from_str(words).unwrap();
+            let identity = NostrIdentity::from_seed(&mnemonic).unwrap();
+            assert_eq!(identity.to_hex(), secret);
+            assert_eq!(identity.public_key().to_string(), public);
+
+            // The same seed always gives the same key
+            assert_eq!(NostrIdentity::from_seed(&mnemonic).unwrap(), identity);
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use crate::mint_error::{AddMintError, AddMintErrorKind};
+use crate::{MintIdentifier, StatusSeverity};
+use futures::future::{AbortHandle, abortable};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+pub use futures::future::Aborted;
+
+/// The kinds of operation that get their own time limit and are kept in the activity log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperationKind {
+    Peek = 0,
+    Join = 1,
+    Resync = 2,
+}
+
+impl OperationKind {
+    pub const ALL: [Self; 3] = [Self::Peek, Self::Join, Self::Resync];
+
+    pub fn from_i32(kind: i32) -> Self {
+        match kind {
+            0 => Self::Peek,
+            1 => Self::Join,
+            2 => Self::Resync,
+            _ => panic!("invalid operation kind"),
+        }
+    }
+}
+
+impl std::fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Peek => write!(f, "Preview"),
+            Self::Join => write!(f, "Join"),
+            Self::Resync => write!(f, "Resync"),
+        }
+    }
+}
+
+// Anything shorter can't get through a Tor circuit, anything longer is as good as never
+const MIN_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// How long each kind of operation gets before it's failed as timed out, and how long the
+/// single requests they're made of get
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperationTimeouts {
+    pub peek: Duration,
+    pub join: Duration,
+    pub resync: Duration,
+    /// Opening a connection and its TLS handshake, for cashu mints and other HTTP requests
+    pub connect: Duration,
+    /// One request to one guardian, like checking whether it's up
+    pub guardian_request: Duration,
+}
+
+impl Default for OperationTimeouts {
+    fn default() -> Self {
+        // A preview is one config download, joining and resyncing can have a lot of
+        // history to get through on a big federation
+        Self {
+            peek: Duration::from_secs(60),
+            join: Duration::from_secs(5 * 60),
+            resync: Duration::from_secs(10 * 60),
+            connect: Duration::from_secs(30),
+            guardian_request: Duration::from_secs(10),
+        }
+    }
+}
+
+impl OperationTimeouts {
+    /// Roomier limits for Tor, where building circuits and onion services make everything
+    /// several times slower
+    pub const TOR_FRIENDLY: Self = Self {
+        peek: Duration::from_secs(5 * 60),
+        join: Duration::from_secs(10 * 60),
+        resync: Duration::from_secs(30 * 60),
+        connect: Duration::from_secs(60),
+        guardian_request: Duration::from_secs(30),
+    };
+
+    /// Keeps every limit within reason, and a single request no longer than a whole preview
+    #[must_use]
+    pub fn clamped(self) -> Self {
+        let clamp = |timeout: Duration| timeout.clamp(MIN_TIMEOUT, MAX_TIMEOUT);
+        let peek = clamp(self.peek);
+        Self {
+            peek,
+            join: clamp(self.join),
+            resync: clamp(self.resync),
+            connect: clamp(self.connect).min(peek),
+            guardian_request: clamp(self.guardian_request).min(peek),
+        }
+    }
+
+    pub const fn get(&self, kind: OperationKind) -> Duration {
+        match kind {
+            OperationKind::Peek => self.peek,
+            OperationKind::Join => self.join,
+            OperationKind::Resync => self.resync,
+        }
+    }
+}
+
+/// Operations the UI can cancel while they run, keyed by the id the UI sent them with
+#[derive(Debug, Clone, Default)]
+pub struct CancellableOperations {
+    handles: Arc<Mutex<HashMap<Uuid, AbortHandle>>>,
+    timeouts: Arc<Mutex<OperationTimeouts>>,
+}
+
+impl CancellableOperations {
+    /// Swaps in new time limits, operations already running keep the one they started with
+    pub fn set_timeouts(&self, timeouts: OperationTimeouts) {
+        if let Ok(mut current) = self.timeouts.lock() {
+            *current = timeouts;
+        }
+    }
+
+    pub fn timeouts(&self) -> OperationTimeouts {
+        self.timeouts
+            .lock()
+            .map(|timeouts| *timeouts)
+            .unwrap_or_default()
+    }
+
+    /// Runs `operation` under `id` until it finishes, runs past the time limit for its
+    /// `kind`, or [`Self::cancel`] is called for it. Going over the limit fails it with a
+    /// [`AddMintErrorKind::TimedOut`] error. Either way it's dropped at its next await, so
+    /// anything it had already done stays done.
+    pub async fn run<T>(
+        &self,
+        id: Uuid,
+        kind: OperationKind,
+        operation: impl Future<Output = anyhow::Result<T>>,
+    ) -> Result<anyhow::Result<T>, Aborted> {
+        let limit = self.timeouts().get(kind);
+        let (operation, handle) = abortable(tokio::time::timeout(limit, operation));
+        if let Ok(mut handles) = self.handles.lock() {
+            handles.insert(id, handle);
+        }
+
+        let result = operation.await.map(|finished| {
+            finished.unwrap_or_else(|_| {
+                Err(AddMintError::new(
+                    AddMintErrorKind::TimedOut,
+                    format!("Gave up after {}s without an answer", limit.as_secs()),
+                )
+                .into())
+            })
+        });
+
+        if let Ok(mut handles) = self.handles.lock() {
+            handles.remove(&id);
+        }
+        result
+    }
+
+    /// Stops the operation running under `id`, returning false if there isn't one
+    pub fn cancel(&self, id: Uuid) -> bool {
+        let handle = self
+            .handles
+            .lock()
+            .ok()
+            .and_then(|mut handles| handles.remove(&id));
+        match handle {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// A repeat of the last update still goes out after this long, so an operation that's only
+// saying "still syncing" doesn't look stuck to the UI
+const REPEAT_STATUS_AFTER: Duration = Duration::from_secs(10);
+
+// Operations that stop sending updates are forgotten, oldest first, past this many
+const MAX_TRACKED_STATUSES: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SentStatus {
+    message: String,
+    severity: StatusSeverity,
+    target: Option<MintIdentifier>,
+}
+
+/// The last status update sent for each operation, so a long sync repeating itself doesn't
+/// flood the UI
+#[derive(Debug, Clone, Default)]
+pub struct StatusUpdates {
+    last: Arc<Mutex<HashMap<Uuid, (SentStatus, Instant)>>>,
+}
+
+impl StatusUpdates {
+    /// Whether an update for `id` should be sent. Anything that differs from the last one sent,
+    /// in message, severity or mint, always is. A repeat is only let through again once
+    /// [`REPEAT_STATUS_AFTER`] has passed. Finishing or failing isn't a status update, so this
+    /// never holds those back.
+    pub fn should_send(
+        &self,
+        id: Uuid,
+        message: &str,
+        severity: StatusSeverity,
+        target: Option<&MintIdentifier>,
+    ) -> bool {
+        let Ok(mut last) = self.last.lock() else {
+            return true;
+        };
+        let now = Instant::now();
+
+        let repeat = last.get(&id).is_some_and(|(sent, at)| {
+            sent.message == message
+                && sent.severity == severity
+                && sent.target.as_ref() == target
+                && now.duration_since(*at) < REPEAT_STATUS_AFTER
+        });
+        if repeat {
+            return false;
+        }
+
+        if last.len() >= MAX_TRACKED_STATUSES && !last.contains_key(&id) {
+            let oldest = last
+                .iter()
+                .min_by_key(|(_, (_, at))| *at)
+                .map(|(oldest, _)| *oldest);
+            if let Some(oldest) = oldest {
+                last.remove(&oldest);
+            }
+        }
+        last.insert(
+            id,
+            (
+                SentStatus {
+                    message: message.to_string(),
+                    severity,
+                    target: target.cloned(),
+                },
+                now,
+            ),
+        );
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cancel_operation() {
+        let operations = CancellableOperations::default();
+        let id = Uuid::new_v4();
+
+        let running = operations.clone();
+        let task = tokio::spawn(async move {
+            running
+                .run(
+                    id,
+                    OperationKind::Join,
+                    std::future::pending::<anyhow::Result<()>>(),
+                )
+                .await
+        });
+
+        // Give the operation a chance to register before cancelling it
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(operations.cancel(id));
+        assert!(matches!(task.await.unwrap(), Err(Aborted)));
+
+        // It's gone once cancelled, so a second cancel finds nothing
+        assert!(!operations.cancel(id));
+    }
+
+    #[tokio::test]
+    async fn test_finished_operation() {
+        let operations = CancellableOperations::default();
+        let id = Uuid::new_v4();
+
+        let result = operations
+            .run(id, OperationKind::Peek, async { Ok(42) })
+            .await;
+        assert_eq!(result.unwrap().unwrap(), 42);
+
+        // Cancelling after it finished doesn't do anything
+        assert!(!operations.cancel(id));
+        assert!(!operations.cancel(Uuid::new_v4()));
+    }
+
+    #[tokio::test]
+    async fn test_operation_timeout() {
+        let operations = CancellableOperations::default();
+        operations.set_timeouts(OperationTimeouts {
+            peek: Duration::from_millis(10),
+            ..OperationTimeouts::default()
+        });
+        let id = Uuid::new_v4();
+
+        // An operation that never answers is failed once its kind's limit runs out
+        let result = operations
+            .run(
+                id,
+                OperationKind::Peek,
+                std::future::pending::<anyhow::Result<()>>(),
+            )
+            .await;
+        let error = result.expect("timing out isn't a cancel").unwrap_err();
+        assert_eq!(
+            AddMintError::classify(&error).kind,
+            AddMintErrorKind::TimedOut
+        );
+        assert!(!operations.cancel(id));
+
+        // Other kinds keep their own limit
+        assert_eq!(
+            operations.timeouts().get(OperationKind::Join),
+            OperationTimeouts::default().join
+        );
+        let slow_join = operations.run(
+            Uuid::new_v4(),
+            OperationKind::Join,
+            std::future::pending::<anyhow::Result<()>>(),
+        );
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), slow_join)
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_clamped_timeouts() {
+        assert_eq!(
+            OperationTimeouts::default().clamped(),
+            OperationTimeouts::default()
+        );
+        assert_eq!(
+            OperationTimeouts::TOR_FRIENDLY.clamped(),
+            OperationTimeouts::TOR_FRIENDLY
+        );
+
+        let absurd = OperationTimeouts {
+            peek: Duration::from_secs(20),
+            join: Duration::ZERO,
+            resync: Duration::from_secs(7 * 24 * 60 * 60),
+            connect: Duration::from_secs(60),
+            guardian_request: Duration::from_millis(1),
+        }
+        .clamped();
+        assert_eq!(absurd.join, MIN_TIMEOUT);
+        assert_eq!(absurd.resync, MAX_TIMEOUT);
+        assert_eq!(absurd.guardian_request, MIN_TIMEOUT);
+        // A single connection can't be given longer than the preview it's part of
+        assert_eq!(absurd.connect, absurd.peek);
+    }
+
+    #[test]
+    fn test_repeated_status_updates() {
+        let updates = StatusUpdates::default();
+        let id = Uuid::new_v4();
+        let mint = MintIdentifier::Fedimint(fedimint_core::config::FederationId::dummy());
+
+        // A sync saying the same thing over and over only gets through once
+        let sent = (0..1_000)
+            .filter(|_| updates.should_send(id, "Still syncing", StatusSeverity::Info, None))
+            .count();
+        assert_eq!(sent, 1);
+
+        // Anything that changes is sent straight away
+        assert!(updates.should_send(id, "Almost done", StatusSeverity::Info, None));
+        assert!(updates.should_send(id, "Almost done", StatusSeverity::Warning, None));
+        assert!(updates.should_send(id, "Almost done", StatusSeverity::Warning, Some(&mint)));
+        assert!(!updates.should_send(id, "Almost done", StatusSeverity::Warning, Some(&mint)));
+
+        // Going back to an earlier message is a change too
+        assert!(updates.should_send(id, "Still syncing", StatusSeverity::Info, None));
+
+        // Each operation is tracked on its own
+        assert!(updates.should_send(Uuid::new_v4(), "Still syncing", StatusSeverity::Info, None));
+
+        // Operations that went quiet are forgotten rather than kept forever
+        for _ in 0..MAX_TRACKED_STATUSES * 2 {
+            updates.should_send(Uuid::new_v4(), "Starting", StatusSeverity::Info, None);
+        }
+        assert_eq!(updates.last.lock().unwrap().len(), MAX_TRACKED_STATUSES);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::MintIdentifier;
+use crate::db_models::MintItem;
+use anyhow::anyhow;
+use qrcode::{Color, EcLevel, QrCode};
+use std::fmt::Write;
+
+// Each QR module is drawn this many SVG units wide, with the quiet zone the spec asks for
+const MODULE_SIZE: usize = 8;
+const QUIET_ZONE: usize = 4;
+
+/// A printable page for handing a mint to someone else.
+///
+/// It has the mint's name, a QR code, the code as text and how to join. It only has what the
+/// mint already publishes: the announced name rather than the user's nickname, and nothing
+/// about their wallet, balance or notes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareBundle {
+    pub mint_name: String,
+    /// The invite code for federations, the URL for cashu mints
+    pub code: String,
+    pub is_fedimint: bool,
+}
+
+impl ShareBundle {
+    pub fn from_mint_item(item: &MintItem) -> Option<Self> {
+        let code = match &item.id {
+            MintIdentifier::Fedimint(_) => item.invite_code.as_ref()?.to_string(),
+            MintIdentifier::Cashu(url) => url.to_string(),
+        };
+        Some(Self {
+            mint_name: item.name.clone(),
+            code,
+            is_fedimint: item.id.federation_id().is_some(),
+        })
+    }
+
+    /// A file name for the page, from the mint's name
+    pub fn file_name(&self) -> String {
+        let name: String = self
+            .mint_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        let name = name.trim_matches('-');
+        if name.is_empty() {
+            "mint-invite.html".to_string()
+        } else {
+            format!("{name}-invite.html")
+        }
+    }
+
+    fn instructions(&self) -> &'static str {
+        if self.is_fedimint {
+            "Open a Fedimint wallet such as Harbor, choose to add a mint, then scan this code or paste the invite code below. Nothing is spent by joining, and the wallet shows the federation's details before you confirm."
+        } else {
+            "Open a Cashu wallet such as Harbor, choose to add a mint, then scan this code or paste the mint URL below. Nothing is spent by adding the mint, and the wallet shows its details before you confirm."
+        }
+    }
+
+    /// The code as an SVG, black on white so it prints
+    fn qr_svg(&self) -> anyhow::Result<String> {
+        let qr = QrCode::with_error_correction_level(self.code.as_bytes(), EcLevel::M)
+            .map_err(|e| anyhow!("Could not make a QR code: {e}"))?;
+        let width = qr.width();
+        let size = (width + 2 * QUIET_ZONE) * MODULE_SIZE;
+
+        let mut path = String::new();
+        for (i, color) in qr.to_colors().into_iter().enumerate() {
+            if color == Color::Dark {
+                let x = (i % width + QUIET_ZONE) * MODULE_SIZE;
+                let y = (i / width + QUIET_ZONE) * MODULE_SIZE;
+                let _ = write!(path, "M{x} {y}h{MODULE_SIZE}v{MODULE_SIZE}h-{MODULE_SIZE}z");
+            }
+        }
+
+        Ok(format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {size} {size}" shape-rendering="crispEdges"><rect width="{size}" height="{size}" fill="#fff"/><path d="{path}" fill="#000"/></svg>"##
+        ))
+    }
+
+    /// The whole page as one HTML file, with the QR code inline so it works offline
+    pub fn to_html(&self) -> anyhow::Result<String> {
+        let name = escape_html(&self.mint_name);
+        let code = escape_html(&self.code);
+        let label = if self.is_fedimint {
+            "Invite code"
+        } else {
+            "Mint URL"
+        };
+        Ok(format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Join {name}</title>
+<style>
+body {{ font-family: sans-serif; max-width: 40rem; margin: 2rem auto; padding: 0 1rem; color: #000; background: #fff; }}
+.qr svg {{ width: 100%; max-width: 28rem; display: block; margin: 2rem auto; }}
+.code {{ font-family: monospace; word-break: break-all; border: 1px solid #000; padding: 1rem; }}
+</style>
+</head>
+<body>
+<h1>Join {name}</h1>
+<p>{instructions}</p>
+<div class="qr">{qr}</div>
+<h2>{label}</h2>
+<p class="code">{code}</p>
+</body>
+</html>
+"#,
+            instructions = self.instructions(),
+            qr = self.qr_svg()?,
+        ))
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cdk::mint_url::MintUrl;
+    use fedimint_core::config::FederationId;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_share_bundle() {
+        let url = MintUrl::from_str("https://mint.example.com").unwrap();
+        let item = MintItem {
+            id: MintIdentifier::Cashu(url),
+            name: "Meetup <Mint>".to_string(),
+            nickname: Some("my secret stash".to_string()),
+            notes: Some("seed is in the drawer".to_string()),
+            balance: 21_000,
+            ..MintItem::unknown(FederationId::dummy())
+        };
+        let bundle = ShareBundle::from_mint_item(&item).unwrap();
+        assert_eq!(bundle.file_name(), "meetup--mint-invite.html");
+
+        // The announced name, escaped, and none of what the user added
+        let html = bundle.to_html().unwrap();
+        assert!(html.contains("Join Meetup &lt;Mint&gt;"));
+        assert!(html.contains("https://mint.example.com"));
+        assert!(html.contains("Mint URL"));
+        assert!(!html.contains("my secret stash"));
+        assert!(!html.contains("drawer"));
+        assert!(!html.contains("21000"));
+        assert!(html.contains("<svg"));
+
+        // A federation without a stored invite code has nothing to share
+        let federation = MintItem::unknown(FederationId::dummy());
+        assert!(ShareBundle::from_mint_item(&federation).is_none());
+    }
+}
@@ -0,0 +1,161 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A named step of opening the wallet, measured from when unlocking started
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimingSpan {
+    pub name: String,
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+/// Where the time went while the wallet was opening.
+///
+/// Recording a span is a lock and a push, so this is always on. Span names are picked by
+/// the caller and should never contain anything more sensitive than a short federation ID.
+#[derive(Debug, Clone)]
+pub struct StartupTimings {
+    started: Instant,
+    spans: Arc<Mutex<Vec<TimingSpan>>>,
+}
+
+impl Default for StartupTimings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StartupTimings {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            spans: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Records a step that began at `started` and has just finished
+    pub fn record(&self, name: impl Into<String>, started: Instant) {
+        self.push(name.into(), started, started.elapsed());
+    }
+
+    /// Records a moment with no duration of its own, like the UI becoming usable
+    pub fn mark(&self, name: impl Into<String>) {
+        self.push(name.into(), Instant::now(), Duration::ZERO);
+    }
+
+    fn push(&self, name: String, started: Instant, duration: Duration) {
+        let span = TimingSpan {
+            name,
+            start: started.saturating_duration_since(self.started),
+            duration,
+        };
+        if let Ok(mut spans) = self.spans.lock() {
+            spans.push(span);
+        }
+    }
+
+    /// Every span recorded so far, in the order they started
+    pub fn spans(&self) -> Vec<TimingSpan> {
+        let mut spans = self
+            .spans
+            .lock()
+            .map(|spans| spans.clone())
+            .unwrap_or_default();
+        spans.sort_by_key(|span| span.start);
+        spans
+    }
+}
+
+/// A plain text table of `spans`, for showing and copying into a bug report
+pub fn format_timings(spans: &[TimingSpan]) -> String {
+    spans
+        .iter()
+        .map(|span| {
+            let end = span.start + span.duration;
+            format!(
+                "{:>7}ms {:>7}ms  {}",
+                end.as_millis(),
+                span.duration.as_millis(),
+                span.name
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A federation's ID cut down to something recognizable, for span names
+pub fn short_federation_id(id: &impl ToString) -> String {
+    id.to_string().chars().take(8).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_startup_timings() {
+        let timings = StartupTimings::new();
+        let db_started = Instant::now();
+        std::thread::sleep(Duration::from_millis(5));
+        let federation_started = Instant::now();
+        std::thread::sleep(Duration::from_millis(5));
+
+        // Spans come back in the order they started, not the order they finished
+        timings.record("Federation c8d42396", federation_started);
+        timings.record("Database open", db_started);
+        timings.mark("UI ready");
+
+        let spans = timings.spans();
+        let names: Vec<_> = spans.iter().map(|span| span.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Database open", "Federation c8d42396", "UI ready"]
+        );
+        assert!(spans[0].duration >= Duration::from_millis(10));
+        assert!(spans[1].start >= Duration::from_millis(5));
+        assert_eq!(spans[2].duration, Duration::ZERO);
+
+        // Clones share the same report, so the bridge and the core can both record into it
+        #[allow(clippy::redundant_clone)]
+        let clone = timings.clone();
+        clone.mark("Clone");
+        assert_eq!(timings.spans().len(), 4);
+
+        let report = format_timings(&spans);
+        assert_eq!(report.lines().count(), 3);
+        assert!(
+            report
+                .lines()
+                .next()
+                .unwrap()
+                .ends_with("ms  Database open")
+        );
+    }
+
+    #[test]
+    fn test_format_timings() {
+        let spans = vec![
+            TimingSpan {
+                name: "Database open".to_string(),
+                start: Duration::from_millis(2),
+                duration: Duration::from_millis(1500),
+            },
+            TimingSpan {
+                name: "UI ready".to_string(),
+                start: Duration::from_millis(20300),
+                duration: Duration::ZERO,
+            },
+        ];
+        assert_eq!(
+            format_timings(&spans),
+            "   1502ms    1500ms  Database open\n  20300ms       0ms  UI ready"
+        );
+        assert_eq!(format_timings(&[]), "");
+        assert_eq!(
+            short_federation_id(
+                &"c8d423964c7ad944d30f57359b6e5b260e211dcfdb945140e28d4df51fd572d2"
+            ),
+            "c8d42396"
+        );
+    }
+}
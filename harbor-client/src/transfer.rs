@@ -0,0 +1,205 @@
+use crate::{CoreUIMsg, MintIdentifier, ReceiveSuccessMsg, SendSuccessMsg};
+use fedimint_core::Amount;
+
+/// How far a transfer between two mints has got, which says where the money is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferStage {
+    /// Getting an invoice from the destination, nothing has moved yet
+    Invoicing,
+    /// The source is paying the destination's invoice
+    Paying,
+    /// The source paid, the destination is issuing the funds
+    Claiming,
+    Complete,
+    /// The source's payment didn't go through, so the money is still in the source
+    Failed,
+    /// The destination gave up before the source's payment finished, so the payment should
+    /// fail and come back to the source
+    Refunding,
+    /// The source paid but the destination hasn't issued the funds, it's owed by the
+    /// destination until it does
+    Stuck,
+}
+
+/// What the user can do about a transfer that didn't finish
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferResolution {
+    /// Nothing moved, start the transfer again
+    Retry,
+    /// Ask the destination to issue what was paid to it
+    RetryClaim,
+}
+
+/// Both legs of a transfer, paying out of one mint and receiving into another, followed
+/// together under the one operation id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferState {
+    pub from: MintIdentifier,
+    pub to: MintIdentifier,
+    pub amount: Amount,
+    pub stage: TransferStage,
+    /// What the source paid on top of the amount, once it's known
+    pub fee: Option<Amount>,
+    /// The last error either leg reported
+    pub error: Option<String>,
+}
+
+impl TransferState {
+    pub const fn new(from: MintIdentifier, to: MintIdentifier, amount: Amount) -> Self {
+        Self {
+            from,
+            to,
+            amount,
+            stage: TransferStage::Invoicing,
+            fee: None,
+            error: None,
+        }
+    }
+
+    /// Moves the transfer along for a message either leg sent under its operation id.
+    /// Returns whether the message was about the transfer at all.
+    pub fn apply(&mut self, msg: &CoreUIMsg) -> bool {
+        match msg {
+            CoreUIMsg::Sending => {
+                if self.stage == TransferStage::Invoicing {
+                    self.stage = TransferStage::Paying;
+                }
+            }
+            CoreUIMsg::SendSuccess(SendSuccessMsg::Transfer { fee }) => {
+                self.fee = *fee;
+                // The destination can report the funds arriving before the source reports
+                // the payment finishing
+                if self.stage != TransferStage::Complete {
+                    self.stage = match self.stage {
+                        TransferStage::Refunding | TransferStage::Stuck => TransferStage::Stuck,
+                        _ => TransferStage::Claiming,
+                    };
+                }
+            }
+            CoreUIMsg::ReceiveSuccess(ReceiveSuccessMsg::Transfer) => {
+                self.stage = TransferStage::Complete;
+                self.error = None;
+            }
+            CoreUIMsg::ReceiveFailed(reason) => {
+                self.error = Some(reason.clone());
+                self.stage = match self.stage {
+                    TransferStage::Claiming | TransferStage::Stuck => TransferStage::Stuck,
+                    TransferStage::Complete => TransferStage::Complete,
+                    TransferStage::Invoicing => TransferStage::Failed,
+                    _ => TransferStage::Refunding,
+                };
+            }
+            CoreUIMsg::TransferFailure(reason) => {
+                self.error = Some(reason.clone());
+                if self.stage != TransferStage::Complete {
+                    self.stage = TransferStage::Failed;
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Whether the transfer is done with, either way
+    pub const fn is_finished(&self) -> bool {
+        matches!(
+            self.stage,
+            TransferStage::Complete | TransferStage::Failed | TransferStage::Stuck
+        )
+    }
+
+    pub const fn resolution(&self) -> Option<TransferResolution> {
+        match self.stage {
+            TransferStage::Failed => Some(TransferResolution::Retry),
+            TransferStage::Stuck => Some(TransferResolution::RetryClaim),
+            _ => None,
+        }
+    }
+
+    /// Where the money is, in words, given what the two mints are called
+    pub fn funds_location(&self, from: &str, to: &str) -> String {
+        match self.stage {
+            TransferStage::Invoicing => format!("Still in {from}"),
+            TransferStage::Paying => format!("Leaving {from}"),
+            TransferStage::Claiming => format!("Paid out of {from}, {to} is issuing it"),
+            TransferStage::Complete => format!("In {to}"),
+            TransferStage::Failed => format!("Still in {from}, nothing was moved"),
+            TransferStage::Refunding => {
+                format!("{to} turned the payment down, it's being returned to {from}")
+            }
+            TransferStage::Stuck => {
+                format!("Paid out of {from}, {to} owes it until the funds are claimed")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cdk::mint_url::MintUrl;
+    use fedimint_core::config::FederationId;
+    use std::str::FromStr;
+
+    fn transfer() -> TransferState {
+        TransferState::new(
+            MintIdentifier::Fedimint(FederationId::dummy()),
+            MintIdentifier::Cashu(MintUrl::from_str("https://mint.example.com").unwrap()),
+            Amount::from_sats(1_000),
+        )
+    }
+
+    #[test]
+    fn test_transfer_legs() {
+        let fee = Some(Amount::from_sats(3));
+
+        // Both legs going through, the fee is kept for the summary
+        let mut state = transfer();
+        assert!(state.apply(&CoreUIMsg::Sending));
+        assert_eq!(state.stage, TransferStage::Paying);
+        state.apply(&CoreUIMsg::SendSuccess(SendSuccessMsg::Transfer { fee }));
+        assert_eq!(state.stage, TransferStage::Claiming);
+        assert!(!state.is_finished());
+        state.apply(&CoreUIMsg::ReceiveSuccess(ReceiveSuccessMsg::Transfer));
+        assert_eq!(state.stage, TransferStage::Complete);
+        assert_eq!(state.fee, fee);
+        assert_eq!(state.resolution(), None);
+
+        // The destination can finish first, a late payment report doesn't undo that
+        let mut state = transfer();
+        state.apply(&CoreUIMsg::ReceiveSuccess(ReceiveSuccessMsg::Transfer));
+        state.apply(&CoreUIMsg::SendSuccess(SendSuccessMsg::Transfer { fee }));
+        assert_eq!(state.stage, TransferStage::Complete);
+        assert_eq!(state.fee, fee);
+
+        // The payment failing leaves everything in the source
+        let mut state = transfer();
+        state.apply(&CoreUIMsg::Sending);
+        state.apply(&CoreUIMsg::TransferFailure("Payment failed".to_string()));
+        assert_eq!(state.stage, TransferStage::Failed);
+        assert_eq!(state.resolution(), Some(TransferResolution::Retry));
+        assert_eq!(state.error.as_deref(), Some("Payment failed"));
+
+        // Paid but not issued is money the destination owes, so it gets a claim retry
+        let mut state = transfer();
+        state.apply(&CoreUIMsg::Sending);
+        state.apply(&CoreUIMsg::SendSuccess(SendSuccessMsg::Transfer { fee }));
+        state.apply(&CoreUIMsg::ReceiveFailed("Mint unreachable".to_string()));
+        assert_eq!(state.stage, TransferStage::Stuck);
+        assert!(state.is_finished());
+        assert_eq!(state.resolution(), Some(TransferResolution::RetryClaim));
+
+        // The destination refusing before the payment lands is waited out until the
+        // source reports what happened
+        let mut state = transfer();
+        state.apply(&CoreUIMsg::Sending);
+        state.apply(&CoreUIMsg::ReceiveFailed("Canceled".to_string()));
+        assert_eq!(state.stage, TransferStage::Refunding);
+        assert!(!state.is_finished());
+        state.apply(&CoreUIMsg::TransferFailure("Payment failed".to_string()));
+        assert_eq!(state.stage, TransferStage::Failed);
+
+        // Messages about anything else are left alone
+        assert!(!transfer().apply(&CoreUIMsg::Unlocking));
+    }
+}
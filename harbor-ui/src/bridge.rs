@@ -8,11 +8,15 @@ use harbor_client::cdk::nuts::CurrencyUnit;
 use harbor_client::cdk::wallet::WalletBuilder;
 use harbor_client::cdk_sqlite::WalletSqliteDatabase;
 use harbor_client::db::{DBConnection, check_password, setup_db};
-use harbor_client::fedimint_client::{FederationInviteOrId, FedimintClient};
+use harbor_client::fedimint_core::Amount;
 use harbor_client::fedimint_core::config::FederationId;
 use harbor_client::metadata::FederationMeta;
+use harbor_client::mint_error::AddMintError;
+use harbor_client::operations::{Aborted, OperationKind};
+use harbor_client::startup_timings::StartupTimings;
 use harbor_client::{
-    CoreUIMsg, CoreUIMsgPacket, HarborCore, MintIdentifier, UICoreMsg, UICoreMsgPacket, data_dir,
+    CoreUIMsg, CoreUIMsgPacket, HarborCore, MintIdentifier, ReceiveSuccessMsg, StatusSeverity,
+    UICoreMsg, UICoreMsgPacket, data_dir,
 };
 use iced::futures::channel::mpsc::Sender;
 use iced::futures::{SinkExt, Stream, StreamExt};
@@ -25,7 +29,7 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, mpsc};
 use tokio::task::spawn_blocking;
 use uuid::Uuid;
@@ -85,14 +89,17 @@ async fn setup_harbor_core(
     // Setup core message channel
     let (core_tx, mut core_rx) = iced::futures::channel::mpsc::channel::<CoreUIMsgPacket>(128);
     let mut tx_clone = tx.clone();
+    let timings = StartupTimings::new();
 
-    // Setup database
+    // Setup database, migrations run as the first connection is opened
+    let started = Instant::now();
     let db_path = db_path.to_string();
     let pw = password.to_string();
     let db = spawn_blocking(move || setup_db(&db_path, pw))
         .await
         .expect("Could not create join handle")
         .ok()?;
+    timings.record("Database open and migrations", started);
 
     // Retrieve mnemonic
     let profile = db
@@ -105,37 +112,27 @@ async fn setup_harbor_core(
     // Create stop signal
     let stop = Arc::new(AtomicBool::new(false));
 
-    // Setup federation clients
+    // Federation clients are opened once the core is up. Only active ones are opened, archived
+    // federations are shown from their saved metadata and don't touch the network until
+    // they're unarchived.
     let federation_ids = db
         .list_federations()
-        .expect("should load initial fedimints");
-    let mut clients = HashMap::with_capacity(federation_ids.len());
-    for f in federation_ids {
-        let client = FedimintClient::new(
-            db.clone(),
-            FederationInviteOrId::Id(
-                FederationId::from_str(&f).expect("should parse federation id"),
-            ),
-            &mnemonic,
-            network,
-            stop.clone(),
-            core_tx.clone(),
-            None,
-        )
-        .await
-        .expect("Could not create fedimint client");
-
-        clients.insert(client.federation_id(), client);
-    }
+        .expect("should load initial fedimints")
+        .iter()
+        .map(|f| FederationId::from_str(f).expect("should parse federation id"))
+        .collect();
 
+    let started = Instant::now();
     let cashu_db_path = data_dir.join("cashu.sqlite");
     let cashu_db = Arc::new(
         WalletSqliteDatabase::new(&cashu_db_path, password.to_string())
             .await
             .expect("Could not create cashu WalletRedbDatabase"),
     );
+    timings.record("Cashu database open", started);
 
     // Setup cashu clients
+    let started = Instant::now();
     let mint_urls = db
         .list_cashu_mints()
         .expect("should load initial fedimints");
@@ -164,6 +161,7 @@ async fn setup_harbor_core(
 
         cashu_clients.insert(wallet.mint_url.clone(), wallet);
     }
+    timings.record("Cashu clients", started);
 
     // setup message passing thread
     tokio::spawn(async move {
@@ -180,22 +178,28 @@ async fn setup_harbor_core(
     });
 
     // Create and return HarborCore
-    Some(
-        HarborCore::new(
-            network,
-            mnemonic,
-            data_dir,
-            core_tx,
-            Arc::new(RwLock::new(clients)),
-            Arc::new(RwLock::new(cashu_clients)),
-            db,
-            cashu_db,
-            stop.clone(),
-            Arc::new(AtomicBool::new(profile.tor_enabled())),
-        )
-        .await
-        .expect("Failed to build harbor core"),
+    let started = Instant::now();
+    let core = HarborCore::new(
+        network,
+        mnemonic,
+        data_dir,
+        core_tx,
+        Arc::new(RwLock::new(HashMap::new())),
+        Arc::new(RwLock::new(cashu_clients)),
+        db,
+        cashu_db,
+        stop,
+        Arc::new(AtomicBool::new(profile.tor_enabled())),
+        timings.clone(),
     )
+    .await
+    .expect("Failed to build harbor core");
+    timings.record("Core setup", started);
+
+    // Each federation shows up as it's ready instead of the whole unlock waiting on all of them
+    core.open_federations(federation_ids).await;
+
+    Some(core)
 }
 
 /// Attempts to auto-unlock the wallet using a password from the environment or keyring.
@@ -221,6 +225,7 @@ async fn try_auto_unlock(
                 tx.send(Message::core_msg(None, CoreUIMsg::UnlockSuccess))
                     .await
                     .expect("should send");
+                core.startup_timings.mark("UI ready");
                 return Some(core);
             } else {
                 log::warn!("Password from keyring is invalid");
@@ -242,6 +247,7 @@ async fn try_auto_unlock(
             tx.send(Message::core_msg(None, CoreUIMsg::UnlockSuccess))
                 .await
                 .expect("should send");
+            core.startup_timings.mark("UI ready");
             return Some(core);
         } else {
             log::warn!("Password from environment is invalid");
@@ -394,6 +400,7 @@ pub fn run_core() -> impl Stream<Item = Message> {
                             tx.send(Message::core_msg(id, CoreUIMsg::UnlockSuccess))
                                 .await
                                 .expect("should send");
+                            core.startup_timings.mark("UI ready");
                             process_core(&mut core_handle, &core).await;
                         }
                         _ => {
@@ -469,6 +476,7 @@ pub fn run_core() -> impl Stream<Item = Message> {
                         cashu_db,
                         Arc::new(AtomicBool::new(false)), // stop
                         Arc::new(AtomicBool::new(true)),  // tor enabled
+                        StartupTimings::new(),
                     )
                     .await
                     .expect("Failed to build harbor core");
@@ -491,6 +499,12 @@ pub fn run_core() -> impl Stream<Item = Message> {
 async fn process_core(core_handle: &mut CoreHandle, core: &HarborCore) {
     // Initialize the ui's state
     core.init_ui_state().await.expect("Could not init ui state");
+    core.prune_preview_cache();
+    core.interrupt_unfinished_operations();
+    core.spawn_guardian_health_checks();
+    core.spawn_connectivity_checks();
+    core.spawn_metadata_refresh();
+    core.spawn_recovery_watchers();
 
     loop {
         let msg = core_handle.recv().await;
@@ -580,14 +594,43 @@ async fn process_core(core_handle: &mut CoreHandle, core: &HarborCore) {
                                 .await;
                         }
                     }
-                    UICoreMsg::GetFederationInfo(invite_code) => {
-                        match core.get_federation_info(msg.id, invite_code).await {
+                    UICoreMsg::RetryTransferClaim(mint) => {
+                        // Failing to claim leaves the transfer where it was, owed by the
+                        // destination, rather than failing it
+                        let reply = match core.claim_paid_receives(msg.id, mint).await {
+                            Ok(claimed) if claimed > Amount::ZERO => {
+                                CoreUIMsg::ReceiveSuccess(ReceiveSuccessMsg::Transfer)
+                            }
+                            Ok(_) => CoreUIMsg::ReceiveFailed(
+                                "The mint hasn't issued anything yet, try again later".to_string(),
+                            ),
                             Err(e) => {
+                                error!("Error claiming transfer: {e}");
+                                CoreUIMsg::ReceiveFailed(e.to_string())
+                            }
+                        };
+                        core.msg(msg.id, reply).await;
+                    }
+                    UICoreMsg::GetFederationInfo(invite_code) => {
+                        let target = invite_code.to_string();
+                        let mint = invite_code.federation_id().to_string();
+                        let peek = core.get_federation_info(msg.id, invite_code);
+                        match core
+                            .run_recorded(msg.id, OperationKind::Peek, target, mint, peek)
+                            .await
+                        {
+                            Err(Aborted) => {
+                                core.msg(msg.id, CoreUIMsg::OperationCancelled).await;
+                            }
+                            Ok(Err(e)) => {
                                 error!("Error getting federation info: {e}");
-                                core.msg(msg.id, CoreUIMsg::AddMintFailed(e.to_string()))
-                                    .await;
+                                core.msg(
+                                    msg.id,
+                                    CoreUIMsg::AddMintFailed(AddMintError::classify(&e)),
+                                )
+                                .await;
                             }
-                            Ok((config, metadata)) => {
+                            Ok(Ok((config, metadata))) => {
                                 core.msg(
                                     msg.id,
                                     CoreUIMsg::MintInfo {
@@ -603,13 +646,24 @@ async fn process_core(core_handle: &mut CoreHandle, core: &HarborCore) {
                         }
                     }
                     UICoreMsg::GetCashuMintInfo(mint_url) => {
-                        match core.get_cashu_mint_info(msg.id, mint_url.clone()).await {
-                            Err(e) => {
+                        let target = mint_url.to_string();
+                        let peek = core.get_cashu_mint_info(msg.id, mint_url.clone());
+                        match core
+                            .run_recorded(msg.id, OperationKind::Peek, target.clone(), target, peek)
+                            .await
+                        {
+                            Err(Aborted) => {
+                                core.msg(msg.id, CoreUIMsg::OperationCancelled).await;
+                            }
+                            Ok(Err(e)) => {
                                 error!("Error getting cashu mint info: {e}");
-                                core.msg(msg.id, CoreUIMsg::AddMintFailed(e.to_string()))
-                                    .await;
+                                core.msg(
+                                    msg.id,
+                                    CoreUIMsg::AddMintFailed(AddMintError::classify(&e)),
+                                )
+                                .await;
                             }
-                            Ok(info) => {
+                            Ok(Ok(info)) => {
                                 let metadata = FederationMeta {
                                     federation_name: info
                                         .as_ref()
@@ -621,10 +675,12 @@ async fn process_core(core_handle: &mut CoreHandle, core: &HarborCore) {
                                     federation_icon_url: info
                                         .as_ref()
                                         .and_then(|i| i.icon_url.clone()),
+                                    federation_site_url: None,
                                     meta_external_url: None,
                                     preview_message: info.and_then(|i| i.description),
                                     popup_end_timestamp: None,
                                     popup_countdown_message: None,
+                                    max_balance_msats: None,
                                 };
                                 core.msg(
                                     msg.id,
@@ -640,13 +696,24 @@ async fn process_core(core_handle: &mut CoreHandle, core: &HarborCore) {
                     }
                     UICoreMsg::AddFederation(invite_code) => {
                         let id = invite_code.federation_id();
-                        match core.add_federation(msg.id, invite_code).await {
-                            Err(e) => {
+                        let target = invite_code.to_string();
+                        let add = core.add_federation(msg.id, invite_code);
+                        match core
+                            .run_recorded(msg.id, OperationKind::Join, target, id.to_string(), add)
+                            .await
+                        {
+                            Err(Aborted) => {
+                                core.msg(msg.id, CoreUIMsg::OperationCancelled).await;
+                            }
+                            Ok(Err(e)) => {
                                 error!("Error adding federation: {e}");
-                                core.msg(msg.id, CoreUIMsg::AddMintFailed(e.to_string()))
-                                    .await;
+                                core.msg(
+                                    msg.id,
+                                    CoreUIMsg::AddMintFailed(AddMintError::classify(&e)),
+                                )
+                                .await;
                             }
-                            Ok(()) => {
+                            Ok(Ok(())) => {
                                 if let Ok(new_federation_list) = core.get_mint_items().await {
                                     core.msg(
                                         msg.id,
@@ -663,15 +730,24 @@ async fn process_core(core_handle: &mut CoreHandle, core: &HarborCore) {
                         }
                     }
                     UICoreMsg::AddCashuMint(url) => match core
-                        .add_cashu_mint(msg.id, url.clone())
+                        .run_recorded(
+                            msg.id,
+                            OperationKind::Join,
+                            url.to_string(),
+                            url.to_string(),
+                            core.add_cashu_mint(msg.id, url.clone()),
+                        )
                         .await
                     {
-                        Err(e) => {
+                        Err(Aborted) => {
+                            core.msg(msg.id, CoreUIMsg::OperationCancelled).await;
+                        }
+                        Ok(Err(e)) => {
                             error!("Error adding mint: {e}");
-                            core.msg(msg.id, CoreUIMsg::AddMintFailed(e.to_string()))
+                            core.msg(msg.id, CoreUIMsg::AddMintFailed(AddMintError::classify(&e)))
                                 .await;
                         }
-                        Ok(()) => {
+                        Ok(Ok(())) => {
                             if let Ok(new_federation_list) = core.get_mint_items().await {
                                 core.msg(msg.id, CoreUIMsg::MintListUpdated(new_federation_list))
                                     .await;
@@ -690,6 +766,8 @@ async fn process_core(core_handle: &mut CoreHandle, core: &HarborCore) {
                             CoreUIMsg::StatusUpdate {
                                 message: "Removing mint...".to_string(),
                                 operation_id: Some(msg.id),
+                                target: Some(id.clone()),
+                                severity: StatusSeverity::Info,
                             },
                         )
                         .await;
@@ -753,8 +831,11 @@ async fn process_core(core_handle: &mut CoreHandle, core: &HarborCore) {
                                 match core.add_federation(msg.id, invite_code).await {
                                     Err(e) => {
                                         error!("Error adding federation: {e}");
-                                        core.msg(msg.id, CoreUIMsg::AddMintFailed(e.to_string()))
-                                            .await;
+                                        core.msg(
+                                            msg.id,
+                                            CoreUIMsg::AddMintFailed(AddMintError::classify(&e)),
+                                        )
+                                        .await;
                                     }
                                     Ok(()) => {
                                         if let Ok(new_federation_list) = core.get_mint_items().await
@@ -775,8 +856,11 @@ async fn process_core(core_handle: &mut CoreHandle, core: &HarborCore) {
                             match core.add_cashu_mint(msg.id, mint_url.clone()).await {
                                 Err(e) => {
                                     error!("Error adding cashu mint: {e}");
-                                    core.msg(msg.id, CoreUIMsg::AddMintFailed(e.to_string()))
-                                        .await;
+                                    core.msg(
+                                        msg.id,
+                                        CoreUIMsg::AddMintFailed(AddMintError::classify(&e)),
+                                    )
+                                    .await;
                                 }
                                 Ok(()) => {
                                     if let Ok(new_list) = core.get_mint_items().await {
@@ -818,6 +902,338 @@ async fn process_core(core_handle: &mut CoreHandle, core: &HarborCore) {
                             core.msg(msg.id, CoreUIMsg::TorEnabled(enabled)).await;
                         }
                     },
+                    UICoreMsg::SetNostrIdentity(identity) => {
+                        match core.set_nostr_identity(identity) {
+                            Ok(public) => core.msg(msg.id, CoreUIMsg::NostrIdentity(public)).await,
+                            Err(e) => error!("error setting nostr identity: {e}"),
+                        }
+                    }
+                    UICoreMsg::DeriveNostrIdentity => match core.derive_nostr_identity() {
+                        Ok(public) => core.msg(msg.id, CoreUIMsg::NostrIdentity(public)).await,
+                        Err(e) => error!("error deriving nostr identity: {e}"),
+                    },
+                    UICoreMsg::SetMintNickname { mint, nickname } => {
+                        match core.set_mint_nickname(mint, nickname) {
+                            Err(e) => {
+                                error!("error setting mint nickname: {e}");
+                            }
+                            _ => {
+                                if let Ok(new_federation_list) = core.get_mint_items().await {
+                                    core.msg(
+                                        msg.id,
+                                        CoreUIMsg::MintListUpdated(new_federation_list),
+                                    )
+                                    .await;
+                                }
+                            }
+                        }
+                    }
+                    UICoreMsg::SetMintNotes { mint, notes } => {
+                        match core.set_mint_notes(mint, notes) {
+                            Err(e) => {
+                                error!("error setting mint notes: {e}");
+                            }
+                            _ => {
+                                if let Ok(new_federation_list) = core.get_mint_items().await {
+                                    core.msg(
+                                        msg.id,
+                                        CoreUIMsg::MintListUpdated(new_federation_list),
+                                    )
+                                    .await;
+                                }
+                            }
+                        }
+                    }
+                    UICoreMsg::SetMintMaxBalance { mint, max_balance } => {
+                        match core.set_mint_max_balance(mint, max_balance) {
+                            Err(e) => {
+                                error!("error setting mint max balance: {e}");
+                            }
+                            _ => {
+                                if let Ok(new_federation_list) = core.get_mint_items().await {
+                                    core.msg(
+                                        msg.id,
+                                        CoreUIMsg::MintListUpdated(new_federation_list),
+                                    )
+                                    .await;
+                                }
+                            }
+                        }
+                    }
+                    UICoreMsg::FetchMintList { url, signer } => {
+                        match core.fetch_mint_list(&url, signer.as_deref()).await {
+                            Ok(fetched) => {
+                                core.msg(msg.id, CoreUIMsg::MintListFetched(fetched)).await;
+                            }
+                            Err(e) => {
+                                error!("Error fetching mint list from {url}: {e}");
+                                core.msg(msg.id, CoreUIMsg::MintListFetchFailed(e.to_string()))
+                                    .await;
+                            }
+                        }
+                    }
+                    UICoreMsg::ImportMints(mints) => {
+                        let summary = core.import_mints(msg.id, mints).await;
+                        if let Ok(new_federation_list) = core.get_mint_items().await {
+                            core.msg(msg.id, CoreUIMsg::MintListUpdated(new_federation_list))
+                                .await;
+                        }
+                        core.msg(msg.id, CoreUIMsg::MintImportFinished(summary))
+                            .await;
+                    }
+                    UICoreMsg::RemoveMints(mints) => {
+                        let mut removed = 0;
+                        let mut failed = vec![];
+                        for mint in mints {
+                            let result = match &mint {
+                                MintIdentifier::Fedimint(id) => {
+                                    core.remove_federation(msg.id, *id).await
+                                }
+                                MintIdentifier::Cashu(url) => {
+                                    core.remove_cashu_mint(msg.id, url).await
+                                }
+                            };
+                            match result {
+                                Ok(()) => removed += 1,
+                                Err(e) => {
+                                    error!("Error removing mint {mint:?}: {e}");
+                                    failed.push((mint, e.to_string()));
+                                }
+                            }
+                        }
+
+                        if let Ok(new_federation_list) = core.get_mint_items().await {
+                            core.msg(msg.id, CoreUIMsg::MintListUpdated(new_federation_list))
+                                .await;
+                        }
+                        core.msg(msg.id, CoreUIMsg::RemoveMintsFinished { removed, failed })
+                            .await;
+                    }
+                    UICoreMsg::GetFederationVersions(id) => {
+                        match core.get_federation_versions(id).await {
+                            Ok(versions) => {
+                                core.msg(msg.id, CoreUIMsg::FederationVersions { id, versions })
+                                    .await;
+                            }
+                            Err(e) => {
+                                error!("Error getting federation versions: {e}");
+                            }
+                        }
+                    }
+                    UICoreMsg::GetGateways(id) => match core.get_gateways(id).await {
+                        Ok((gateways, pinned)) => {
+                            core.msg(
+                                msg.id,
+                                CoreUIMsg::GatewayList {
+                                    id,
+                                    gateways,
+                                    pinned,
+                                },
+                            )
+                            .await;
+                        }
+                        Err(e) => {
+                            error!("Error getting gateways: {e}");
+                        }
+                    },
+                    UICoreMsg::GetSessionProgress(id) => {
+                        match core.get_session_progress(id).await {
+                            Ok(progress) => {
+                                core.msg(msg.id, CoreUIMsg::SessionProgress { id, progress })
+                                    .await;
+                            }
+                            Err(e) => {
+                                error!("Error getting session progress: {e}");
+                            }
+                        }
+                    }
+                    UICoreMsg::GetMintDiagnostics(mint) => {
+                        // A missing or unreadable log just means there are no lines to include
+                        let log = std::fs::read_to_string(core.data_dir.join(LOG_FILE_NAME))
+                            .unwrap_or_default();
+                        match core.get_mint_diagnostics(mint, &log).await {
+                            Ok(diagnostics) => {
+                                core.msg(msg.id, CoreUIMsg::MintDiagnostics(Box::new(diagnostics)))
+                                    .await;
+                            }
+                            Err(e) => {
+                                error!("Error gathering mint diagnostics: {e}");
+                                core.msg(msg.id, CoreUIMsg::MintDiagnosticsFailed(e.to_string()))
+                                    .await;
+                            }
+                        }
+                    }
+                    UICoreMsg::GetOperationHistory => match core.storage.get_operation_records() {
+                        Ok(records) => {
+                            core.msg(msg.id, CoreUIMsg::OperationHistory(records)).await;
+                        }
+                        Err(e) => error!("Error getting operation history: {e}"),
+                    },
+                    UICoreMsg::GetExchangeRate(currency) => {
+                        // No rate just means sats only, so there's nothing to report on failure
+                        let rate = core.get_exchange_rate(currency).await;
+                        core.msg(msg.id, CoreUIMsg::ExchangeRate(rate)).await;
+                    }
+                    UICoreMsg::GetPendingAmounts => match core.get_pending_amounts() {
+                        Ok(pending) => {
+                            core.msg(msg.id, CoreUIMsg::PendingAmountsUpdated(pending))
+                                .await;
+                        }
+                        Err(e) => error!("Error getting pending amounts: {e}"),
+                    },
+                    UICoreMsg::GetStartupTimings => {
+                        core.msg(
+                            msg.id,
+                            CoreUIMsg::StartupTimings(core.startup_timings.spans()),
+                        )
+                        .await;
+                    }
+                    UICoreMsg::ResyncMint(mint) => {
+                        let target = match &mint {
+                            MintIdentifier::Fedimint(id) => id.to_string(),
+                            MintIdentifier::Cashu(url) => url.to_string(),
+                        };
+                        let resync = core.resync_mint(msg.id, mint.clone());
+                        match core
+                            .run_recorded(
+                                msg.id,
+                                OperationKind::Resync,
+                                target.clone(),
+                                target,
+                                resync,
+                            )
+                            .await
+                        {
+                            Err(Aborted) => {
+                                core.msg(msg.id, CoreUIMsg::OperationCancelled).await;
+                            }
+                            Ok(result) => {
+                                if let Err(e) = result {
+                                    error!("Error resyncing mint: {e}");
+                                    core.status_update(msg.id, &format!("Resync failed: {e}"))
+                                        .await;
+                                }
+                                core.msg(msg.id, CoreUIMsg::ResyncFinished(mint)).await;
+                            }
+                        }
+                        if let Ok(mint_list) = core.get_mint_items().await {
+                            core.msg(msg.id, CoreUIMsg::MintListUpdated(mint_list))
+                                .await;
+                        }
+                    }
+                    UICoreMsg::RefreshMintBalance(mint) => {
+                        let error = match core.refresh_mint_balance(msg.id, mint.clone()).await {
+                            Ok(_) => None,
+                            Err(e) => {
+                                error!("Error refreshing mint balance: {e}");
+                                Some(e.to_string())
+                            }
+                        };
+                        core.msg(msg.id, CoreUIMsg::MintBalanceRefreshed { mint, error })
+                            .await;
+                    }
+                    UICoreMsg::CancelOperation(id) => {
+                        if !core.operations.cancel(id) {
+                            log::info!("Operation {id} already finished, nothing to cancel");
+                        }
+                    }
+                    UICoreMsg::SetOperationTimeouts(timeouts) => {
+                        core.set_operation_timeouts(timeouts);
+                    }
+                    UICoreMsg::SetReduceBackgroundData(reduced) => {
+                        core.background.set_reduced(reduced);
+                    }
+                    UICoreMsg::RetryFederationInit(id) => {
+                        if let Err(e) = core.retry_federation_init(id).await {
+                            error!("Error retrying federation {id}: {e}");
+                        }
+                    }
+                    UICoreMsg::PingGuardians(id) => {
+                        if let Err(e) = core.ping_guardians(id).await {
+                            error!("Error pinging guardians: {e}");
+                        }
+                        core.msg(msg.id, CoreUIMsg::GuardiansPinged(id)).await;
+                        if let Ok(mint_list) = core.get_mint_items().await {
+                            core.msg(msg.id, CoreUIMsg::MintListUpdated(mint_list))
+                                .await;
+                        }
+                    }
+                    UICoreMsg::RegenerateInviteCode {
+                        federation_id,
+                        preferred_guardian,
+                    } => match core
+                        .regenerate_invite_code(federation_id, preferred_guardian)
+                        .await
+                    {
+                        Ok(invite_code) => {
+                            core.msg(
+                                msg.id,
+                                CoreUIMsg::InviteCodeRegenerated {
+                                    federation_id,
+                                    invite_code,
+                                },
+                            )
+                            .await;
+                        }
+                        Err(e) => {
+                            error!("Error regenerating invite code: {e}");
+                            core.msg(msg.id, CoreUIMsg::RegenerateInviteCodeFailed(e.to_string()))
+                                .await;
+                        }
+                    },
+                    UICoreMsg::GetMintFees(mint) => match core.get_mint_fees(mint.clone()).await {
+                        Ok(fees) => {
+                            core.msg(msg.id, CoreUIMsg::MintFees { mint, fees }).await;
+                        }
+                        Err(e) => {
+                            error!("Error getting mint fees: {e}");
+                        }
+                    },
+                    UICoreMsg::EstimateSendFees(invoice) => {
+                        match core.estimate_send_fees(&invoice).await {
+                            Ok(estimates) => {
+                                core.msg(msg.id, CoreUIMsg::SendFeeEstimates(estimates))
+                                    .await;
+                            }
+                            Err(e) => {
+                                error!("Error estimating send fees: {e}");
+                                core.msg(msg.id, CoreUIMsg::SendFailure(e.to_string()))
+                                    .await;
+                            }
+                        }
+                    }
+                    UICoreMsg::GetMintHistory(mint) => {
+                        match core.storage.get_transaction_history(Some(&mint)) {
+                            Ok(history) => {
+                                core.msg(msg.id, CoreUIMsg::MintHistoryUpdated { mint, history })
+                                    .await;
+                            }
+                            Err(e) => {
+                                error!("Error getting mint history: {e}");
+                            }
+                        }
+                    }
+                    UICoreMsg::SetPinnedGateway {
+                        federation_id,
+                        gateway_id,
+                    } => {
+                        if let Err(e) = core.set_pinned_gateway(federation_id, gateway_id) {
+                            error!("Error pinning gateway: {e}");
+                            core.msg(msg.id, CoreUIMsg::SetPinnedGatewayFailed(e.to_string()))
+                                .await;
+                        }
+                        if let Ok((gateways, pinned)) = core.get_gateways(federation_id).await {
+                            core.msg(
+                                msg.id,
+                                CoreUIMsg::GatewayList {
+                                    id: federation_id,
+                                    gateways,
+                                    pinned,
+                                },
+                            )
+                            .await;
+                        }
+                    }
                     UICoreMsg::TestStatusUpdates => {
                         core.test_status_updates(msg.id).await;
                     }
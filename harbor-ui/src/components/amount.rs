@@ -0,0 +1,88 @@
+use iced::Element;
+use iced::widget::{column, row, text};
+
+use crate::{AmountUnit, HarborWallet, Message};
+
+use super::subtitle;
+
+const MSATS_PER_SAT: u64 = 1_000;
+const SATS_PER_BTC: u64 = 100_000_000;
+
+/// An amount in the unit picked in settings, with what it's worth in fiat when that's on.
+/// Compact puts it all on one smaller line, for list rows.
+pub fn h_amount<'a>(harbor: &HarborWallet, msats: u64, compact: bool) -> Element<'a, Message> {
+    let amount = format_msats(msats, harbor.config.amount_unit);
+    let fiat = harbor.fiat_value(msats / MSATS_PER_SAT);
+    if compact {
+        let line = match fiat {
+            Some(fiat) => format!("{amount} · {fiat}"),
+            None => amount,
+        };
+        return row![text(line).size(18)].into();
+    }
+    column![text(amount).size(24)]
+        .push_maybe(fiat.map(|fiat| text(fiat).size(16).style(subtitle)))
+        .spacing(4)
+        .into()
+}
+
+/// An amount as shown anywhere in the app. Sats are whole with thousands separators, and
+/// anything under one sat says so rather than showing as zero. Bitcoin always has all eight
+/// decimals so amounts line up.
+pub fn format_msats(msats: u64, unit: AmountUnit) -> String {
+    let sats = msats / MSATS_PER_SAT;
+    match unit {
+        AmountUnit::Sats => match sats {
+            0 if msats > 0 => "less than 1 sat".to_string(),
+            1 => "1 sat".to_string(),
+            _ => format!("{} sats", group_thousands(sats)),
+        },
+        AmountUnit::Bitcoin => format!(
+            "{}.{:08} BTC",
+            group_thousands(sats / SATS_PER_BTC),
+            sats % SATS_PER_BTC
+        ),
+    }
+}
+
+fn group_thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_msats() {
+        let sats = |msats| format_msats(msats, AmountUnit::Sats);
+        let btc = |msats| format_msats(msats, AmountUnit::Bitcoin);
+
+        assert_eq!(sats(0), "0 sats");
+        assert_eq!(btc(0), "0.00000000 BTC");
+
+        // Dust under a sat isn't shown as nothing
+        assert_eq!(sats(1), "less than 1 sat");
+        assert_eq!(sats(999), "less than 1 sat");
+        assert_eq!(sats(1_000), "1 sat");
+        assert_eq!(sats(1_999), "1 sat");
+        assert_eq!(btc(1_000), "0.00000001 BTC");
+
+        assert_eq!(sats(1_234_567_000), "1,234,567 sats");
+        assert_eq!(btc(150_000_000_000), "1.50000000 BTC");
+
+        // More than will ever exist still formats, so a bad value from a mint shows as is
+        let over_supply = 21_000_001 * SATS_PER_BTC * MSATS_PER_SAT;
+        assert_eq!(btc(over_supply), "21,000,001.00000000 BTC");
+        assert_eq!(sats(over_supply), "2,100,000,100,000,000 sats");
+        assert_eq!(sats(u64::MAX), "18,446,744,073,709,551 sats");
+    }
+}
@@ -0,0 +1,99 @@
+use iced::widget::{column, container, row, text, tooltip};
+use iced::{Alignment, Border, Color, Element, Shadow, Theme};
+
+use crate::Message;
+
+use super::{SvgIcon, blue, green, lighten, map_icon, red, tag_style, yellow};
+
+/// What a badge is saying about the thing it's on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BadgeStyle {
+    #[default]
+    Neutral,
+    Info,
+    Success,
+    Warning,
+    Danger,
+}
+
+impl BadgeStyle {
+    fn color(self) -> Option<Color> {
+        match self {
+            Self::Neutral => None,
+            Self::Info => Some(blue()),
+            Self::Success => Some(green()),
+            Self::Warning => Some(yellow()),
+            Self::Danger => Some(red()),
+        }
+    }
+}
+
+/// One badge in a set passed to [`h_badges`]
+pub struct Badge {
+    pub label: String,
+    pub style: BadgeStyle,
+    pub icon: Option<SvgIcon>,
+}
+
+/// A small label on a mint, like a module it runs or the network it's on
+pub fn h_badge<'a>(label: &str, style: BadgeStyle) -> Element<'a, Message> {
+    badge(None, label, style)
+}
+
+/// A set of badges that wraps onto more lines as needed. Past `max_visible` the rest collapse
+/// into a "+3" badge that lists them in a tooltip.
+pub fn h_badges<'a>(badges: Vec<Badge>, max_visible: usize) -> Element<'a, Message> {
+    // Collapsing a single badge into "+1" wouldn't save any room
+    let visible = if badges.len() > max_visible + 1 {
+        max_visible
+    } else {
+        badges.len()
+    };
+
+    let mut badges = badges.into_iter();
+    let mut badge_row = row![].spacing(8);
+    for item in badges.by_ref().take(visible) {
+        badge_row = badge_row.push(badge(item.icon, &item.label, item.style));
+    }
+
+    let hidden: Vec<Badge> = badges.collect();
+    if !hidden.is_empty() {
+        let names = hidden.iter().fold(column![].spacing(4), |names, item| {
+            names.push(text(item.label.clone()).size(14))
+        });
+        badge_row = badge_row.push(tooltip(
+            badge(None, &format!("+{}", hidden.len()), BadgeStyle::Neutral),
+            container(names).padding(8).style(tag_style),
+            tooltip::Position::Bottom,
+        ));
+    }
+
+    badge_row.wrap().into()
+}
+
+// Every badge is the same height and padding whatever its style, with the color as a tint so
+// the text stays readable
+fn badge<'a>(icon: Option<SvgIcon>, label: &str, style: BadgeStyle) -> Element<'a, Message> {
+    let content = row![]
+        .push_maybe(icon.map(|icon| map_icon(icon, 14., 14.)))
+        .push(text(label.to_string()).size(14))
+        .align_y(Alignment::Center)
+        .spacing(4);
+
+    let Some(color) = style.color() else {
+        return container(content).padding([2, 8]).style(tag_style).into();
+    };
+    container(content)
+        .padding([2, 8])
+        .style(move |_: &Theme| container::Style {
+            text_color: Some(lighten(color, 0.1)),
+            background: Some(color.scale_alpha(0.15).into()),
+            border: Border {
+                color: color.scale_alpha(0.6),
+                width: 1.,
+                radius: (4.).into(),
+            },
+            shadow: Shadow::default(),
+        })
+        .into()
+}
@@ -1,11 +1,63 @@
+use chrono::{DateTime, Local};
+use harbor_client::db_models::PendingAmounts;
+use harbor_client::exchange_rate::ExchangeRate;
 use iced::Element;
 use iced::widget::{column, text};
 
-use super::{format_amount, subtitle};
-use crate::Message;
+use super::{format_amount, h_amount, subtitle, very_subtle, yellow};
+use crate::{HarborWallet, Message};
 
-pub fn h_balance_display(balance: u64) -> Element<'static, Message> {
-    let balance_row = text(format_amount(balance)).size(24);
+pub fn h_balance_display<'a>(harbor: &HarborWallet, balance: u64) -> Element<'a, Message> {
     let balance_subtitle = text("Your balance").size(18).style(subtitle);
-    column![balance_row, balance_subtitle].spacing(4).into()
+    column![h_amount(harbor, balance.saturating_mul(1_000), false)]
+        .push(balance_subtitle)
+        .spacing(4)
+        .into()
+}
+
+/// What's in flight next to a settled balance, like "30,000 sats pending out". `None` when
+/// nothing is.
+pub fn pending_caption(pending: PendingAmounts) -> Option<Element<'static, Message>> {
+    if pending.is_empty() {
+        return None;
+    }
+    let parts: Vec<String> = [
+        (pending.incoming, "pending in"),
+        (pending.outgoing, "pending out"),
+    ]
+    .into_iter()
+    .filter(|(amount, _)| *amount > 0)
+    .map(|(amount, label)| format!("{} {label}", format_amount(amount)))
+    .collect();
+    Some(text(parts.join(" · ")).size(16).style(subtitle).into())
+}
+
+/// When the price fiat values are worked out from was fetched, flagged once it's old enough
+/// that fetching must have been failing since
+pub fn fiat_rate_note(rate: &ExchangeRate) -> Element<'static, Message> {
+    let fetched_at = i64::try_from(rate.fetched_at)
+        .ok()
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .map(|utc| DateTime::<Local>::from(utc).format("%l:%M %P").to_string())
+        .unwrap_or_default();
+    let now = chrono::Utc::now().timestamp().unsigned_abs();
+    if rate.is_stale(now) {
+        text(format!(
+            "{} rate as of {}, couldn't update it",
+            rate.currency,
+            fetched_at.trim()
+        ))
+        .size(14)
+        .color(yellow())
+        .into()
+    } else {
+        text(format!(
+            "{} rate as of {}",
+            rate.currency,
+            fetched_at.trim()
+        ))
+        .size(14)
+        .style(very_subtle)
+        .into()
+    }
 }
@@ -9,11 +9,39 @@ use iced::{
 
 use crate::{Message, Route};
 
-use super::{SvgIcon, darken, lighten, link, map_icon, the_spinner};
+use super::{SvgIcon, darken, green, lighten, link, map_icon, red, the_spinner};
+
+/// What a button shows besides its label: a spinner while its operation runs, or briefly how
+/// the operation ended. A plain `bool` still works for just loading or not.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ButtonState {
+    #[default]
+    Idle,
+    Loading,
+    Success,
+    Failure,
+}
+
+impl From<bool> for ButtonState {
+    fn from(loading: bool) -> Self {
+        if loading { Self::Loading } else { Self::Idle }
+    }
+}
 
-pub fn h_button(text_str: &str, icon: SvgIcon, loading: bool) -> Button<'_, Message, Theme> {
+pub fn h_button(
+    text_str: &str,
+    icon: SvgIcon,
+    state: impl Into<ButtonState>,
+) -> Button<'_, Message, Theme> {
+    let state = state.into();
+    let loading = state == ButtonState::Loading;
     let spinner: Element<'static, Message, Theme> = the_spinner();
-    let svg = map_icon(icon, 24., 24.);
+    // The icon makes way for a check or a cross while the result is showing
+    let svg = match state {
+        ButtonState::Success => map_icon(SvgIcon::SmallCheck, 24., 24.),
+        ButtonState::Failure => map_icon(SvgIcon::SmallClose, 24., 24.),
+        ButtonState::Idle | ButtonState::Loading => map_icon(icon, 24., 24.),
+    };
     let content = if loading {
         row![spinner].align_y(iced::Alignment::Center)
     } else {
@@ -26,10 +54,11 @@ pub fn h_button(text_str: &str, icon: SvgIcon, loading: bool) -> Button<'_, Mess
         .style(move |theme, status| {
             let gray = lighten(theme.palette().background, 0.5);
 
-            let border_color = if loading || matches!(status, Status::Disabled) {
-                gray
-            } else {
-                Color::WHITE
+            let border_color = match state {
+                ButtonState::Success => green(),
+                ButtonState::Failure => red(),
+                _ if loading || matches!(status, Status::Disabled) => gray,
+                _ => Color::WHITE,
             };
 
             let border = Border {
@@ -125,9 +154,9 @@ pub fn sidebar_button(
     text_str: &str,
     icon: SvgIcon,
     self_route: Route,
-    active_route: Route,
+    active_route: &Route,
 ) -> Button<'_, Message, Theme> {
-    let is_active = self_route == active_route;
+    let is_active = &self_route == active_route;
     let svg = map_icon(icon, 24., 24.);
     let content = row!(svg, text(text_str).size(24.), horizontal_space(),)
         .align_y(iced::Alignment::Center)
@@ -1,10 +1,20 @@
 use iced::Element;
 use iced::widget::text;
 
-use crate::Message;
+use crate::{HarborWallet, Message};
 
-use super::subtitle;
+use super::{red, subtitle};
 
 pub fn h_caption_text(string: &'static str) -> Element<'static, Message> {
     text(string).size(18).style(subtitle).into()
 }
+
+/// Goes under anything disabled for lack of a connection, so it's clear why
+pub fn offline_caption(harbor: &HarborWallet) -> Option<Element<'static, Message>> {
+    (!harbor.is_online()).then(|| {
+        text("You appear to be offline")
+            .size(14)
+            .color(red())
+            .into()
+    })
+}
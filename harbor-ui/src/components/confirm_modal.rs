@@ -3,7 +3,7 @@ use iced::{Color, Element, Length, Shadow, Theme, Vector};
 
 use crate::Message;
 
-use super::{SvgIcon, h_small_button, light_container_style};
+use super::{InputArgs, SvgIcon, h_input, h_small_button, light_container_style};
 
 #[derive(Debug, Clone)]
 pub struct ConfirmModalState {
@@ -12,6 +12,26 @@ pub struct ConfirmModalState {
     pub confirm_action: Box<Message>,
     pub cancel_action: Box<Message>,
     pub confirm_button_text: String,
+    /// Something the user has to type before confirming, for actions that are hard to undo.
+    /// Enter doesn't confirm these, only the button does.
+    pub type_to_confirm: Option<String>,
+    /// Whether Enter confirms too. Off for anything that reaches outside the wallet, like
+    /// opening a link or joining mints from a list, so a stray Enter can't do it.
+    pub enter_confirms: bool,
+}
+
+impl ConfirmModalState {
+    /// Whether the confirm button is enabled, given what's been typed so far
+    pub fn can_confirm(&self, typed: &str) -> bool {
+        self.type_to_confirm
+            .as_ref()
+            .is_none_or(|phrase| typed.trim() == phrase.trim())
+    }
+
+    /// Whether pressing Enter should take the confirm action
+    pub const fn confirms_on_enter(&self) -> bool {
+        self.enter_confirms && self.type_to_confirm.is_none()
+    }
 }
 
 impl Default for ConfirmModalState {
@@ -22,6 +42,8 @@ impl Default for ConfirmModalState {
             confirm_action: Box::new(Message::SetConfirmModal(None)),
             cancel_action: Box::new(Message::SetConfirmModal(None)),
             confirm_button_text: "Confirm".to_string(),
+            type_to_confirm: None,
+            enter_confirms: false,
         }
     }
 }
@@ -29,23 +51,41 @@ impl Default for ConfirmModalState {
 pub fn confirm_modal<'a>(
     content: Element<'a, Message>,
     state: Option<&'a ConfirmModalState>,
+    typed: &'a str,
 ) -> Element<'a, Message> {
     let mut layers = stack![content];
 
     if let Some(state) = state {
-        let modal_content = container(
+        let type_to_confirm = state.type_to_confirm.as_ref().map(|phrase| {
             column![
-                text(&state.title).size(24),
-                text(&state.description),
-                row![
-                    h_small_button("Cancel", SvgIcon::SmallClose, false)
-                        .on_press((*state.cancel_action).clone()),
-                    h_small_button(&state.confirm_button_text, SvgIcon::SmallCheck, false)
-                        .on_press((*state.confirm_action).clone()),
-                ]
-                .spacing(10)
+                text(format!("Type \"{phrase}\" to confirm")).size(14),
+                h_input(InputArgs {
+                    value: typed,
+                    on_input: Message::ConfirmModalInputChanged,
+                    id: Some("confirm_modal_input"),
+                    ..InputArgs::default()
+                })
             ]
-            .spacing(20),
+            .spacing(8)
+        });
+
+        let modal_content = container(
+            column![text(&state.title).size(24), text(&state.description)]
+                .push_maybe(type_to_confirm)
+                .push(
+                    row![
+                        h_small_button("Cancel", SvgIcon::SmallClose, false)
+                            .on_press((*state.cancel_action).clone()),
+                        h_small_button(&state.confirm_button_text, SvgIcon::SmallCheck, false)
+                            .on_press_maybe(
+                                state
+                                    .can_confirm(typed)
+                                    .then(|| (*state.confirm_action).clone())
+                            ),
+                    ]
+                    .spacing(10),
+                )
+                .spacing(20),
         )
         .width(400)
         .padding(24)
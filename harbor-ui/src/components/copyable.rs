@@ -0,0 +1,93 @@
+use iced::widget::{Button, button, column, row, text};
+use iced::{Alignment, Border, Color, Element, Length, Shadow, Theme};
+
+use crate::{HarborWallet, Message};
+
+use super::{SvgIcon, darken, lighten, map_icon, subtitle};
+
+// Enough to tell ids apart at a glance while fitting on one line of the details column
+const COPYABLE_MAX_CHARS: usize = 36;
+
+/// A value that's copied by clicking it, like an id or invite code, showing a check for a
+/// moment after. Long values are cut down in the middle since both ends tend to matter, but
+/// the whole value is what's copied.
+pub fn h_copyable<'a>(
+    harbor: &HarborWallet,
+    label: &'a str,
+    value: String,
+) -> Element<'a, Message> {
+    let copied = harbor.copied_values.contains_key(&value);
+    let icon = if copied {
+        SvgIcon::SmallCheck
+    } else {
+        SvgIcon::Copy
+    };
+
+    let chip: Button<'a, Message, Theme> = button(
+        row![
+            text(truncate_middle(&value, COPYABLE_MAX_CHARS))
+                .size(16)
+                .width(Length::Fill),
+            map_icon(icon, 16., 16.)
+        ]
+        .align_y(Alignment::Center)
+        .spacing(8),
+    )
+    .on_press(Message::CopyValue(value))
+    .padding(8)
+    .width(Length::Fill)
+    .style(|theme: &Theme, status| {
+        let background = match status {
+            button::Status::Hovered => lighten(theme.palette().background, 0.1),
+            button::Status::Pressed => darken(theme.palette().background, 0.05),
+            _ => theme.palette().background,
+        };
+        button::Style {
+            background: Some(background.into()),
+            text_color: Color::WHITE,
+            border: Border {
+                color: lighten(theme.palette().background, 0.2),
+                width: 1.,
+                radius: (8.).into(),
+            },
+            shadow: Shadow::default(),
+        }
+    });
+
+    if label.is_empty() {
+        return chip.into();
+    }
+    column![text(label).size(16).style(subtitle), chip]
+        .spacing(8)
+        .into()
+}
+
+// Keeps the start and end of `value`, with an ellipsis for whatever's cut from the middle
+fn truncate_middle(value: &str, max_chars: usize) -> String {
+    let count = value.chars().count();
+    if count <= max_chars {
+        return value.to_string();
+    }
+    let keep = max_chars.saturating_sub(1);
+    let tail = keep / 2;
+    let head = keep - tail;
+    let start: String = value.chars().take(head).collect();
+    let end: String = value.chars().skip(count - tail).collect();
+    format!("{start}…{end}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_middle() {
+        assert_eq!(truncate_middle("short", 10), "short");
+        assert_eq!(truncate_middle("0123456789", 10), "0123456789");
+        assert_eq!(truncate_middle("0123456789abcdef", 9), "0123…cdef");
+        assert_eq!(truncate_middle("0123456789abcdef", 9).chars().count(), 9);
+
+        // Cut by characters, not bytes
+        assert_eq!(truncate_middle("ñññññññññññ", 5), "ññ…ññ");
+    }
+}
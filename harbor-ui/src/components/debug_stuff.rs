@@ -17,6 +17,7 @@ pub fn debug_stuff(harbor: &HarborWallet) -> Element<'static, Message> {
             title: "Hello".to_string(),
             body: Some("This is a toast".to_string()),
             status: ToastStatus::Good,
+            action: None,
         }));
 
     let add_error_toast_button =
@@ -24,6 +25,7 @@ pub fn debug_stuff(harbor: &HarborWallet) -> Element<'static, Message> {
             title: "Error".to_string(),
             body: Some("This is a toast".to_string()),
             status: ToastStatus::Bad,
+            action: None,
         }));
 
     let test_confirm_modal_button = h_button("Test Confirm Modal", SvgIcon::Shield, false)
@@ -38,11 +40,14 @@ pub fn debug_stuff(harbor: &HarborWallet) -> Element<'static, Message> {
                         title: "You confirmed!".to_string(),
                         body: None,
                         status: ToastStatus::Good,
+                        action: None,
                     }),
                     Message::SetConfirmModal(None),
                 ])),
                 cancel_action: Box::new(Message::SetConfirmModal(None)),
                 confirm_button_text: "Confirm".to_string(),
+                type_to_confirm: None,
+                enter_confirms: true,
             },
         )));
 
@@ -1,37 +1,41 @@
-use crate::{AddFederationStatus, HarborWallet, Message};
+use crate::config::Config;
+use crate::routes::{MintSubroute, Route};
+use crate::{AddFederationStatus, HarborWallet, Message, health_alert_text};
 use harbor_client::MintIdentifier;
-use harbor_client::db_models::MintItem;
-use harbor_client::metadata::FederationMeta;
+use harbor_client::bitcoin::Network;
+use harbor_client::db_models::{FederationInitState, GuardianHealth, MintItem, RecoveryState};
+use harbor_client::fedimint_core::config::FederationId;
+use harbor_client::health::MintHealth;
 use iced::{
     Alignment, Element, Length,
-    widget::{column, container, horizontal_space, row, text},
+    widget::{column, container, horizontal_space, image, progress_bar, row, svg, text, tooltip},
 };
 
 use super::{
-    ConfirmModalState, SvgIcon, h_balance_display, h_small_button, light_container_style, map_icon,
-    subtitle, tag_style,
+    Badge, BadgeStyle, ConfirmModalState, InputArgs, SvgIcon, dimmed_container_style,
+    format_amount, format_msats, h_badge, h_badges, h_balance_display, h_checkbox, h_input,
+    h_relative_time, h_small_button, light_container_style, map_icon, pending_caption, red,
+    subtitle, tag_style, very_subtle, yellow,
 };
 
 // Helper function to create the common mint info layout
-fn mint_info<'a>(
-    name: &'a str,
-    guardians: &'a Option<Vec<String>>,
-    metadata: &'a FederationMeta,
-    id: &'a MintIdentifier,
-) -> iced::widget::Column<'a, Message> {
-    // Choose the right icon based on the mint type
-    let mint_icon = match id {
-        MintIdentifier::Cashu(_) => map_icon(SvgIcon::Squirrel, 24., 24.),
-        MintIdentifier::Fedimint(_) => map_icon(SvgIcon::People, 24., 24.),
-    };
+fn mint_info(item: &MintItem) -> iced::widget::Column<'_, Message> {
+    let MintItem {
+        guardians,
+        guardian_health,
+        metadata,
+        ..
+    } = item;
 
-    let name_row = row![mint_icon, text(name).size(24)]
+    let name_row = row![mint_icon(item, 24.), text(item.display_name()).size(24)]
         .align_y(Alignment::Center)
         .spacing(16);
 
     let mut column = column![name_row].spacing(32);
 
-    if let Some(guardians) = guardians {
+    if let Some(health) = guardian_health {
+        column = column.push(guardian_health_summary(health));
+    } else if let Some(guardians) = guardians {
         let count = guardians.len();
         // handle 0 as 1, these are cashu mints
         let guardian_text = if count <= 1 {
@@ -49,12 +53,137 @@ fn mint_info<'a>(
     column
 }
 
+// The mint's own icon once it's been downloaded, otherwise a glyph for the kind of mint
+pub fn mint_icon(item: &MintItem, size: f32) -> Element<'_, Message> {
+    match &item.icon {
+        Some(path) if path.extension().is_some_and(|ext| ext == "svg") => {
+            svg(svg::Handle::from_path(path))
+                .width(size)
+                .height(size)
+                .into()
+        }
+        Some(path) => image(image::Handle::from_path(path))
+            .width(size)
+            .height(size)
+            .into(),
+        None => match item.id {
+            MintIdentifier::Cashu(_) => map_icon(SvgIcon::Squirrel, size, size).into(),
+            MintIdentifier::Fedimint(_) => map_icon(SvgIcon::People, size, size).into(),
+        },
+    }
+}
+
+// "3/4 guardians online", colored once guardians start dropping, with the per-guardian
+// breakdown in a tooltip
+pub fn guardian_health_summary(health: &[GuardianHealth]) -> Element<'_, Message> {
+    let total = health.len();
+    let online = health.iter().filter(|g| g.online).count();
+
+    let summary = text(format!("{online}/{total} guardians online")).size(18);
+    let summary = match MintHealth::of(health) {
+        MintHealth::Healthy => summary.style(subtitle),
+        MintHealth::Degraded => summary.color(yellow()),
+        MintHealth::Unreachable => summary.color(red()),
+    };
+
+    let breakdown = health.iter().fold(column![], |column, guardian| {
+        let status = if guardian.online { "online" } else { "offline" };
+        column.push(text(format!("{}: {status}", guardian.name)).size(14))
+    });
+
+    tooltip(
+        summary,
+        container(breakdown.spacing(4)).padding(8).style(tag_style),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+// Progress of restoring a mint's ecash from backup, nothing once it's done
+pub fn recovery_progress(state: &RecoveryState) -> Option<Element<'_, Message>> {
+    let label = match state {
+        RecoveryState::Complete => return None,
+        RecoveryState::Failed(e) => {
+            return Some(
+                text(format!("Recovery failed: {e}"))
+                    .size(14)
+                    .color(red())
+                    .into(),
+            );
+        }
+        RecoveryState::NotStarted => "Waiting to start recovering your ecash".to_string(),
+        RecoveryState::Scanning { .. } => {
+            format!("Recovering your ecash, {:.0}%", state.fraction() * 100.)
+        }
+    };
+
+    Some(
+        column![
+            text(label).size(14).style(subtitle),
+            progress_bar(0.0..=1.0, state.fraction())
+        ]
+        .spacing(8)
+        .into(),
+    )
+}
+
+// Shown in place of a federation's details while its client is opening, or if it couldn't be
+fn init_status<'a>(
+    federation_id: FederationId,
+    state: &FederationInitState,
+) -> Element<'a, Message> {
+    match state {
+        FederationInitState::Opening | FederationInitState::Ready => {
+            text("Connecting to this mint...")
+                .size(14)
+                .style(subtitle)
+                .into()
+        }
+        FederationInitState::Failed(e) => column![
+            text(format!("Couldn't open this mint: {e}"))
+                .size(14)
+                .color(red()),
+            h_small_button("Retry", SvgIcon::Restart, false)
+                .on_press(Message::RetryFederationInit(federation_id))
+        ]
+        .spacing(8)
+        .into(),
+    }
+}
+
+// Disabled buttons don't say why on their own, so put the reason in a tooltip
+pub fn explain_disabled<'a>(
+    button: impl Into<Element<'a, Message>>,
+    reason: Option<&'a str>,
+) -> Element<'a, Message> {
+    match reason {
+        Some(reason) => tooltip(
+            button,
+            container(text(reason).size(14)).padding(8).style(tag_style),
+            tooltip::Position::Bottom,
+        )
+        .into(),
+        None => button.into(),
+    }
+}
+
 pub fn h_federation_item_preview(item: &MintItem) -> Element<Message> {
-    let mut column = mint_info(&item.name, &item.guardians, &item.metadata, &item.id);
+    let mut column = mint_info(item);
 
-    let preview_tag = container(text("Preview").size(18).style(subtitle))
-        .padding(8)
-        .style(tag_style);
+    // Set when several of the federation's invite codes were pasted at once
+    let endpoints = item
+        .invite_code
+        .as_ref()
+        .map_or(0, |code| code.peers().len());
+    if endpoints > 1 {
+        column = column.push(
+            text(format!("{endpoints} connection endpoints"))
+                .size(18)
+                .style(subtitle),
+        );
+    }
+
+    let preview_tag = h_badge("Preview", BadgeStyle::Neutral);
 
     column = column.push(row![horizontal_space().width(Length::Fill), preview_tag]);
 
@@ -64,29 +193,487 @@ pub fn h_federation_item_preview(item: &MintItem) -> Element<Message> {
         .into()
 }
 
-pub fn h_federation_item(item: &MintItem) -> Element<Message> {
-    let mut column = mint_info(&item.name, &item.guardians, &item.metadata, &item.id);
+pub fn h_federation_item<'a>(item: &'a MintItem, harbor: &'a HarborWallet) -> Element<'a, Message> {
+    let mut column = mint_info(item).push(capability_badges(item, harbor.config.network));
+
+    let is_default = harbor.config.default_mint.as_ref() == Some(&item.id);
+    if is_default {
+        let badge = row![
+            map_icon(SvgIcon::Star, 16., 16.),
+            text("Default mint").size(14)
+        ]
+        .align_y(Alignment::Center)
+        .spacing(8);
+        column = column.push(container(badge).padding([4, 8]).style(tag_style));
+    }
+
+    if let Some(banner) = shutdown_banner(item, harbor) {
+        column = column.push(banner);
+    }
+    if let Some(banner) = max_balance_banner(item) {
+        column = column.push(banner);
+    }
+    if let Some(banner) = health_banner(item, harbor) {
+        column = column.push(banner);
+    }
+    if let Some(banner) = archive_hint(item, harbor) {
+        column = column.push(banner);
+    }
+
+    if harbor.renaming_mint.as_ref() == Some(&item.id) {
+        column = column.push(rename_form(harbor));
+    }
+
+    // Empty mints stay in the list, just less prominent than funded ones
+    let is_empty = is_empty_mint(item);
+    let style = if is_empty {
+        dimmed_container_style
+    } else {
+        light_container_style
+    };
+    if is_empty {
+        column = column.push(
+            text(format_msats(0, harbor.config.amount_unit))
+                .size(18)
+                .style(very_subtle),
+        );
+    } else {
+        column = column.push(h_balance_display(harbor, item.balance));
+    }
+    if let Some(pending) = pending_caption(item.pending) {
+        column = column.push(pending);
+    }
+
+    if let Some(progress) = item
+        .id
+        .federation_id()
+        .and_then(|id| harbor.mint_recovery.get(&id))
+        .and_then(recovery_progress)
+    {
+        column = column.push(progress);
+    }
+
+    if let Some(status) = item.id.federation_id().and_then(|id| {
+        harbor
+            .mint_init
+            .get(&id)
+            .map(|state| init_status(id, state))
+    }) {
+        column = column.push(status);
+    }
+
+    // Selecting swaps the star and menu for a checkbox, the balance and its refresh stay put
+    // since they decide whether a bulk archive skips the mint
+    let menu_open = !harbor.selecting_mints && harbor.mint_actions_open.as_ref() == Some(&item.id);
+    let actions = if harbor.selecting_mints {
+        // The checkbox fills the row on its own, so it goes first with the refresh after it
+        let id = item.id.clone();
+        row![
+            h_checkbox(
+                "Select",
+                None,
+                harbor.selected_mints.contains(&item.id),
+                false,
+                move |selected| Message::ToggleMintSelected(id.clone(), selected),
+            ),
+            balance_refresh(item, harbor)
+        ]
+    } else {
+        row![
+            balance_refresh(item, harbor),
+            horizontal_space().width(Length::Fill),
+            mint_actions(item, is_default, menu_open)
+        ]
+    };
+    column = column.push(actions.align_y(Alignment::Center).spacing(8));
 
-    column = column.push(h_balance_display(item.balance));
+    if menu_open {
+        column = column.push(quick_actions(item, harbor));
+    }
 
-    let remove_button = h_small_button("", SvgIcon::Trash, false).on_press(
-        Message::SetConfirmModal(Some(ConfirmModalState {
-            title: "Are you sure?".to_string(),
-            description: format!("This will remove {} from your list of mints.", item.name),
-            confirm_action: Box::new(Message::RemoveMint(item.id.clone())),
-            cancel_action: Box::new(Message::SetConfirmModal(None)),
-            confirm_button_text: "Remove Mint".to_string(),
-        })),
+    container(column).padding(16).style(style).into()
+}
+
+// The star and overflow menu buttons at the end of a mint's row
+fn mint_actions<'a>(item: &MintItem, is_default: bool, menu_open: bool) -> Element<'a, Message> {
+    let menu_button = h_small_button("", SvgIcon::Ellipsis, false).on_press(
+        Message::SetMintActionsMenu(if menu_open {
+            None
+        } else {
+            Some(item.id.clone())
+        }),
     );
 
-    column = column.push(row![
-        horizontal_space().width(Length::Fill),
-        remove_button.width(48)
-    ]);
+    // Starring the default mint again unsets it
+    let star_button = h_small_button("", SvgIcon::Star, false)
+        .on_press(Message::SetDefaultMint(
+            (!is_default).then(|| item.id.clone()),
+        ))
+        .width(48);
+    let star_button = tooltip(
+        star_button,
+        container(
+            text(if is_default {
+                "Stop using as the default"
+            } else {
+                "Use as the default mint"
+            })
+            .size(14),
+        )
+        .padding(8)
+        .style(tag_style),
+        tooltip::Position::Top,
+    );
 
-    container(column)
-        .padding(16)
-        .style(light_container_style)
+    row![star_button, menu_button.width(48)]
+        .align_y(Alignment::Center)
+        .spacing(8)
+        .into()
+}
+
+// Nothing in it, nothing on the way and nothing that could change that right now
+fn is_empty_mint(item: &MintItem) -> bool {
+    item.balance == 0 && !item.syncing && item.pending.is_empty()
+}
+
+// How many days the mint has been empty and unused, and its last activity, once that's long
+// enough to suggest archiving it and the suggestion hasn't been dismissed since it was last
+// used
+fn archive_hint_due(item: &MintItem, config: &Config, now: u64) -> Option<(u64, u64)> {
+    let after = config.archive_hint_after.days()?;
+    let idle_days = item.idle_empty_days(now).filter(|days| *days >= after)?;
+    let last_activity = item.last_activity()?;
+
+    let dismissed = config
+        .dismissed_archive_hints
+        .iter()
+        .any(|(mint, at)| mint == &item.id && *at == last_activity);
+    (!dismissed).then_some((idle_days, last_activity))
+}
+
+// Suggests archiving a mint that's been empty and unused for as long as the setting says,
+// until dismissed. Using it and leaving it empty again brings the suggestion back.
+fn archive_hint<'a>(item: &MintItem, harbor: &HarborWallet) -> Option<Element<'a, Message>> {
+    let now = chrono::Utc::now().timestamp().unsigned_abs();
+    let (idle_days, last_activity) = archive_hint_due(item, &harbor.config, now)?;
+
+    let archive_button =
+        h_small_button("Archive", SvgIcon::Trash, false).on_press(confirm_archive(item));
+    let dismiss_button = h_small_button("Dismiss", SvgIcon::SmallClose, false)
+        .on_press(Message::DismissArchiveHint(item.id.clone(), last_activity));
+
+    let banner = column![
+        text(format!(
+            "Empty and unused for {idle_days} days, archive it to tidy up your list"
+        ))
+        .size(16),
+        row![archive_button, dismiss_button].spacing(8)
+    ]
+    .spacing(8);
+
+    Some(
+        container(banner)
+            .padding(16)
+            .width(Length::Fill)
+            .style(tag_style)
+            .into(),
+    )
+}
+
+// Shown until dismissed, and again if the federation changes its notice
+fn shutdown_banner<'a>(item: &MintItem, harbor: &HarborWallet) -> Option<Element<'a, Message>> {
+    let federation_id = item.id.federation_id()?;
+    let notice = item.metadata.shutdown_notice()?;
+
+    let dismissed = harbor
+        .config
+        .dismissed_shutdown_notices
+        .get(&federation_id.to_string());
+    if dismissed == Some(&notice) {
+        return None;
+    }
+
+    let send_blocked = harbor.send_disabled_reason(&item.id);
+    let withdraw_button = h_small_button("Withdraw funds", SvgIcon::UpRight, false).on_press_maybe(
+        send_blocked
+            .is_none()
+            .then(|| Message::SendFromMint(item.id.clone())),
+    );
+    let withdraw_button = explain_disabled(withdraw_button, send_blocked);
+    let dismiss_button = h_small_button("Dismiss", SvgIcon::SmallClose, false).on_press(
+        Message::DismissShutdownNotice(federation_id, notice.clone()),
+    );
+
+    let banner = column![
+        row![
+            map_icon(SvgIcon::ShieldAlert, 24., 24.),
+            text(notice).size(18).color(red())
+        ]
+        .align_y(Alignment::Center)
+        .spacing(8),
+        row![withdraw_button, dismiss_button].spacing(8)
+    ]
+    .spacing(16);
+
+    Some(
+        container(banner)
+            .padding(16)
+            .width(Length::Fill)
+            .style(tag_style)
+            .into(),
+    )
+}
+
+/// Shown when health notifications are on and the federation's guardians went down, until
+/// they come back or it's dismissed
+pub fn health_banner<'a>(item: &MintItem, harbor: &HarborWallet) -> Option<Element<'a, Message>> {
+    let health = *harbor.health_alerts.get(&item.id)?;
+    let color = if health == MintHealth::Unreachable {
+        red()
+    } else {
+        yellow()
+    };
+
+    let dismiss_button = h_small_button("Dismiss", SvgIcon::SmallClose, false)
+        .on_press(Message::DismissHealthAlert(item.id.clone()));
+
+    let banner = column![
+        row![
+            map_icon(SvgIcon::ShieldAlert, 24., 24.),
+            text(format!("This mint is {health}")).size(18).color(color)
+        ]
+        .align_y(Alignment::Center)
+        .spacing(8),
+        text(health_alert_text(health)).size(14).style(subtitle),
+        dismiss_button,
+    ]
+    .spacing(8);
+
+    Some(
+        container(banner)
+            .padding(16)
+            .width(Length::Fill)
+            .style(tag_style)
+            .into(),
+    )
+}
+
+/// A warning for when the balance is over the mint's cap, saying whose cap it is.
+pub fn max_balance_banner<'a>(item: &MintItem) -> Option<Element<'a, Message>> {
+    if !item.over_max_balance() {
+        return None;
+    }
+    let max = item.max_balance()?;
+    let source = if item.max_balance_override.is_some() {
+        "your limit"
+    } else {
+        "the limit this mint recommends"
+    };
+
+    let banner = column![
+        row![
+            map_icon(SvgIcon::ShieldAlert, 24., 24.),
+            text("You hold more in this mint than recommended — consider spreading funds")
+                .size(18)
+                .color(yellow())
+        ]
+        .align_y(Alignment::Center)
+        .spacing(8),
+        text(format!(
+            "{} is over {source} of {}",
+            format_amount(item.balance),
+            format_amount(max.sats_round_down())
+        ))
+        .size(14)
+        .style(subtitle),
+    ]
+    .spacing(8);
+
+    Some(
+        container(banner)
+            .padding(16)
+            .width(Length::Fill)
+            .style(tag_style)
+            .into(),
+    )
+}
+
+/// A button that rereads the mint's balance, spinning while it does, with when that last
+/// happened
+pub fn balance_refresh<'a>(item: &MintItem, harbor: &HarborWallet) -> Element<'a, Message> {
+    let refreshing = harbor.refreshing_mints.contains(&item.id);
+    let refresh_button = h_small_button("", SvgIcon::Restart, refreshing)
+        .on_press_maybe(
+            (!refreshing && harbor.is_online())
+                .then(|| Message::RefreshMintBalance(item.id.clone())),
+        )
+        .width(48);
+
+    let caption = harbor.mint_synced_at.get(&item.id).map(|at| {
+        row![
+            text("Synced").size(14).style(very_subtle),
+            h_relative_time(*at)
+        ]
+        .spacing(4)
+    });
+
+    row![refresh_button]
+        .push_maybe(caption)
+        .align_y(Alignment::Center)
+        .spacing(8)
+        .into()
+}
+
+// More than this and a mint row starts to be mostly badges
+const MAX_ROW_BADGES: usize = 4;
+
+/// Small tags for what the mint supports, from the modules its federation runs, and whether
+/// it's off mainnet
+pub fn capability_badges<'a>(item: &MintItem, network: Network) -> Element<'a, Message> {
+    let capabilities = item.capabilities();
+    let badge = |label: &str, style, icon| Badge {
+        label: label.to_string(),
+        style,
+        icon,
+    };
+    let badges = [
+        capabilities
+            .lightning
+            .then(|| badge("Lightning", BadgeStyle::Info, Some(SvgIcon::Bolt))),
+        capabilities
+            .on_chain
+            .then(|| badge("On-chain", BadgeStyle::Info, Some(SvgIcon::Chain))),
+        capabilities
+            .ecash
+            .then(|| badge("Ecash", BadgeStyle::Neutral, None)),
+        (network != Network::Bitcoin)
+            .then(|| badge(&network_name(network), BadgeStyle::Warning, None)),
+    ];
+    h_badges(badges.into_iter().flatten().collect(), MAX_ROW_BADGES)
+}
+
+// "Signet" rather than "signet", for a badge
+fn network_name(network: Network) -> String {
+    let name = network.to_string();
+    let mut chars = name.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+// The overflow menu shown under a mint when its ⋯ button is pressed
+fn quick_actions<'a>(item: &'a MintItem, harbor: &HarborWallet) -> Element<'a, Message> {
+    // Every action closes the menu, batch that in with the action itself
+    let close_with = |msg: Message| Message::Batch(vec![Message::SetMintActionsMenu(None), msg]);
+
+    let details_button = h_small_button("Details", SvgIcon::Eye, false).on_press(close_with(
+        Message::Navigate(Route::Mints(MintSubroute::Details(item.id.clone()))),
+    ));
+
+    let capabilities = item.capabilities();
+    let receive_blocked = (!capabilities.can_receive())
+        .then_some("This mint has neither lightning nor on-chain, so it can't receive");
+    let receive_button = h_small_button("Receive to this mint", SvgIcon::DownLeft, false)
+        .on_press_maybe(
+            receive_blocked
+                .is_none()
+                .then(|| Message::ReceiveToMint(item.id.clone())),
+        );
+    let receive_button = explain_disabled(receive_button, receive_blocked);
+
+    let send_blocked = harbor.send_disabled_reason(&item.id).or_else(|| {
+        (!capabilities.can_send())
+            .then_some("This mint has neither lightning nor on-chain, so it can't send")
+    });
+    let send_button = h_small_button("Send from this mint", SvgIcon::UpRight, false)
+        .on_press_maybe(
+            send_blocked
+                .is_none()
+                .then(|| Message::SendFromMint(item.id.clone())),
+        );
+    let send_button = explain_disabled(send_button, send_blocked);
+
+    let mut actions = column![details_button, receive_button, send_button].spacing(8);
+
+    match &item.id {
+        MintIdentifier::Fedimint(federation_id) => {
+            let copy_invite_button = h_small_button("Copy invite code", SvgIcon::Copy, false)
+                .on_press_maybe(
+                    item.invite_code
+                        .as_ref()
+                        .map(|code| close_with(Message::CopyToClipboard(code.to_string()))),
+                );
+
+            let copy_id_button = h_small_button("Copy federation ID", SvgIcon::Copy, false)
+                .on_press(close_with(Message::CopyToClipboard(
+                    federation_id.to_string(),
+                )));
+
+            actions = actions.push(copy_invite_button).push(copy_id_button);
+        }
+        MintIdentifier::Cashu(mint_url) => {
+            let copy_url_button = h_small_button("Copy mint URL", SvgIcon::Copy, false)
+                .on_press(close_with(Message::CopyToClipboard(mint_url.to_string())));
+
+            actions = actions.push(copy_url_button);
+        }
+    }
+
+    let rename_button = h_small_button("Rename", SvgIcon::Pencil, false)
+        .on_press(Message::StartRenameMint(item.id.clone()));
+
+    let archive_button = h_small_button("Archive", SvgIcon::Trash, false)
+        .on_press(close_with(confirm_archive(item)));
+
+    actions.push(rename_button).push(archive_button).into()
+}
+
+// Archiving asks first, both from the menu and the details screen. A mint that still holds
+// funds needs its name typed, so a stray Enter can't archive it.
+pub fn confirm_archive(item: &MintItem) -> Message {
+    let has_balance = item.balance > 0;
+    let description = if has_balance {
+        format!(
+            "This will remove {} from your list of mints while it still holds {}. Rejoin it from the archived mints to get them back.",
+            item.display_name(),
+            format_amount(item.balance)
+        )
+    } else {
+        format!(
+            "This will remove {} from your list of mints.",
+            item.display_name()
+        )
+    };
+    Message::SetConfirmModal(Some(ConfirmModalState {
+        title: "Are you sure?".to_string(),
+        description,
+        confirm_action: Box::new(Message::RemoveMint(item.id.clone())),
+        cancel_action: Box::new(Message::SetConfirmModal(None)),
+        confirm_button_text: "Remove Mint".to_string(),
+        type_to_confirm: has_balance.then(|| item.display_name().to_string()),
+        enter_confirms: true,
+    }))
+}
+
+pub fn rename_form(harbor: &HarborWallet) -> Element<Message> {
+    let name_input = h_input(InputArgs {
+        label: "Nickname",
+        placeholder: "Leave empty to use the mint's name",
+        value: &harbor.rename_mint_input_str,
+        on_input: Message::RenameMintInputChanged,
+        on_submit: Some(Message::SaveMintNickname),
+        id: Some("rename_mint_input"),
+        ..InputArgs::default()
+    });
+
+    let cancel_button =
+        h_small_button("Cancel", SvgIcon::SmallClose, false).on_press(Message::CancelRenameMint);
+
+    let save_button =
+        h_small_button("Save", SvgIcon::SmallCheck, false).on_press(Message::SaveMintNickname);
+
+    column![name_input, row![cancel_button, save_button].spacing(8)]
+        .spacing(16)
         .into()
 }
 
@@ -94,7 +681,7 @@ pub fn h_federation_archived<'a>(
     item: &'a MintItem,
     harbor: &'a HarborWallet,
 ) -> Element<'a, Message> {
-    let mut column = mint_info(&item.name, &item.guardians, &item.metadata, &item.id);
+    let mut column = mint_info(item);
 
     let is_joining = harbor.add_federation_status == AddFederationStatus::Adding
         && harbor.current_rejoin_id == Some(item.id.clone());
@@ -112,3 +699,68 @@ pub fn h_federation_archived<'a>(
         .style(light_container_style)
         .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArchiveHintAfter;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    #[test]
+    fn test_is_empty_mint() {
+        let mut item = MintItem::unknown(FederationId::dummy());
+        assert!(is_empty_mint(&item));
+
+        // Anything held, coming in or being synced keeps it looking funded
+        item.balance = 1;
+        assert!(!is_empty_mint(&item));
+        item.balance = 0;
+        item.pending.incoming = 500;
+        assert!(!is_empty_mint(&item));
+        item.pending.incoming = 0;
+        item.syncing = true;
+        assert!(!is_empty_mint(&item));
+    }
+
+    #[test]
+    fn test_archive_hint_due() {
+        let mut item = MintItem {
+            last_used_at: Some(100 * DAY),
+            ..MintItem::unknown(FederationId::dummy())
+        };
+        let mut config = Config::default();
+
+        // A month by default
+        assert_eq!(archive_hint_due(&item, &config, 129 * DAY), None);
+        assert_eq!(
+            archive_hint_due(&item, &config, 130 * DAY),
+            Some((30, 100 * DAY))
+        );
+        config.archive_hint_after = ArchiveHintAfter::OneWeek;
+        assert_eq!(
+            archive_hint_due(&item, &config, 107 * DAY),
+            Some((7, 100 * DAY))
+        );
+        config.archive_hint_after = ArchiveHintAfter::Never;
+        assert_eq!(archive_hint_due(&item, &config, 1_000 * DAY), None);
+        config.archive_hint_after = ArchiveHintAfter::OneMonth;
+
+        // Money in it means it isn't idle, however long it's been
+        item.balance = 1_000;
+        assert_eq!(archive_hint_due(&item, &config, 1_000 * DAY), None);
+        item.balance = 0;
+
+        // Dismissing it holds until the mint is used again and left empty for as long
+        config
+            .dismissed_archive_hints
+            .push((item.id.clone(), 100 * DAY));
+        assert_eq!(archive_hint_due(&item, &config, 1_000 * DAY), None);
+        item.last_used_at = Some(200 * DAY);
+        assert_eq!(archive_hint_due(&item, &config, 229 * DAY), None);
+        assert_eq!(
+            archive_hint_due(&item, &config, 230 * DAY),
+            Some((30, 200 * DAY))
+        );
+    }
+}
@@ -4,6 +4,7 @@ use iced::widget::{column, text};
 use crate::Message;
 
 use super::subtitle as subtitle_style;
+use super::{SvgIcon, h_small_button};
 
 pub fn h_header(title: &'static str, subtitle: &'static str) -> Element<'static, Message> {
     column![
@@ -13,3 +14,19 @@ pub fn h_header(title: &'static str, subtitle: &'static str) -> Element<'static,
     .spacing(8)
     .into()
 }
+
+/// A header with a back button above it, once there's somewhere to go back to
+pub fn h_back_header(
+    title: &'static str,
+    subtitle: &'static str,
+    can_go_back: bool,
+) -> Element<'static, Message> {
+    if !can_go_back {
+        return h_header(title, subtitle);
+    }
+    let back_button =
+        h_small_button("Back", SvgIcon::ArrowLeft, false).on_press(Message::NavigateBack);
+    column![back_button, h_header(title, subtitle)]
+        .spacing(16)
+        .into()
+}
@@ -5,6 +5,7 @@ use iced::{
 
 pub enum SvgIcon {
     ChevronDown,
+    ChevronUp,
     ChevronRight,
     DownLeft,
     Heart,
@@ -31,6 +32,12 @@ pub enum SvgIcon {
     Shield,
     FolderLock,
     ShieldAlert,
+    Ellipsis,
+    Pencil,
+    Download,
+    Upload,
+    Star,
+    Clipboard,
 }
 
 macro_rules! icon_handle {
@@ -45,6 +52,7 @@ macro_rules! icon_handle {
 pub fn map_icon<'a>(icon: SvgIcon, width: f32, height: f32) -> Svg<'a, Theme> {
     match icon {
         SvgIcon::ChevronDown => icon_handle!("chevron_down.svg"),
+        SvgIcon::ChevronUp => icon_handle!("chevron_up.svg"),
         SvgIcon::ChevronRight => icon_handle!("chevron_right.svg"),
         SvgIcon::DownLeft => icon_handle!("down_left.svg"),
         SvgIcon::Heart => icon_handle!("heart.svg"),
@@ -71,6 +79,12 @@ pub fn map_icon<'a>(icon: SvgIcon, width: f32, height: f32) -> Svg<'a, Theme> {
         SvgIcon::Shield => icon_handle!("shield.svg"),
         SvgIcon::FolderLock => icon_handle!("folder_lock.svg"),
         SvgIcon::ShieldAlert => icon_handle!("shield_alert.svg"),
+        SvgIcon::Ellipsis => icon_handle!("ellipsis.svg"),
+        SvgIcon::Pencil => icon_handle!("pencil.svg"),
+        SvgIcon::Download => icon_handle!("download.svg"),
+        SvgIcon::Upload => icon_handle!("upload.svg"),
+        SvgIcon::Star => icon_handle!("star.svg"),
+        SvgIcon::Clipboard => icon_handle!("clipboard.svg"),
     }
     .width(width)
     .height(height)
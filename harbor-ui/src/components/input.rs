@@ -8,7 +8,7 @@ use iced::{
 
 use crate::Message;
 
-use super::{darken, lighten};
+use super::{SvgIcon, darken, lighten, mini_icon_button, red, subtitle};
 
 pub fn focus_input_id(id: &'static str) -> Task<Message> {
     let id = Id::new(id);
@@ -26,6 +26,12 @@ pub struct InputArgs<'a> {
     pub numeric: bool,
     pub id: Option<&'static str>,
     pub suffix: Option<&'static str>,
+    /// An icon button at the end of the field, like paste or scan
+    pub trailing_action: Option<(SvgIcon, Message)>,
+    /// A line under the field, like what was detected or what's wrong with it
+    pub helper_text: Option<&'a str>,
+    /// Shows the field, and its helper text, as invalid
+    pub error: bool,
 }
 
 impl Default for InputArgs<'_> {
@@ -41,6 +47,9 @@ impl Default for InputArgs<'_> {
             numeric: false,
             id: None,
             suffix: None,
+            trailing_action: None,
+            helper_text: None,
+            error: false,
         }
     }
 }
@@ -57,18 +66,22 @@ pub fn h_input(args: InputArgs<'_>) -> Element<Message, Theme> {
         numeric,
         id,
         suffix,
+        trailing_action,
+        helper_text,
+        error,
     } = args;
 
     let on_submit = on_submit.unwrap_or(Message::Noop);
 
     let input = TextInput::new(placeholder, value)
-        .style(|theme: &Theme, status| {
+        .style(move |theme: &Theme, status| {
             let gray = lighten(theme.palette().background, 0.5);
             let border_color = match status {
+                text_input::Status::Disabled => gray,
+                _ if error => red(),
                 text_input::Status::Active => Color::WHITE,
                 text_input::Status::Focused { .. } => theme.palette().primary,
                 text_input::Status::Hovered => darken(Color::WHITE, 0.2),
-                text_input::Status::Disabled => gray,
             };
             let border = Border {
                 color: border_color,
@@ -135,14 +148,24 @@ pub fn h_input(args: InputArgs<'_>) -> Element<Message, Theme> {
         input
     };
 
-    let input = if let Some(suffix) = suffix {
-        let suffix_text = text(suffix).size(24);
-        row![input, suffix_text]
-            .spacing(8)
-            .align_y(iced::Alignment::Center)
-    } else {
-        row![input]
-    };
+    let trailing_button = trailing_action.map(|(icon, message)| {
+        mini_icon_button(icon).on_press_maybe((!disabled).then_some(message))
+    });
+
+    let input = row![input]
+        .push_maybe(suffix.map(|suffix| text(suffix).size(24)))
+        .push_maybe(trailing_button)
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+
+    let helper = helper_text.map(|helper| {
+        let helper = text(helper).size(14);
+        if error {
+            helper.color(red())
+        } else {
+            helper.style(subtitle)
+        }
+    });
 
-    column![label, input].spacing(8).into()
+    column![label, input].push_maybe(helper).spacing(8).into()
 }
@@ -1,19 +1,54 @@
 use iced::Length;
-use iced::widget::{Column, container, horizontal_space, row, scrollable};
-use iced::{Element, Padding};
-
-use crate::Message;
-
-pub fn basic_layout(column: Column<Message>) -> Element<Message> {
-    container(
-        scrollable(row![
-            column
-                .width(Length::Fixed(512.))
-                .padding(Padding::new(48.))
-                .max_width(512),
-            horizontal_space(),
-        ])
-        .height(Length::Fill),
-    )
-    .into()
+use iced::widget::scrollable::{AbsoluteOffset, Id};
+use iced::widget::{Column, container, horizontal_space, row, scrollable, stack};
+use iced::{Alignment, Element, Padding, Task};
+
+use crate::routes::Route;
+use crate::{HarborWallet, Message};
+
+use super::{SvgIcon, h_small_button};
+
+// How far down a screen has to be before there's a button back to the top
+const SCROLL_TO_TOP_AFTER: f32 = 400.;
+
+pub fn basic_layout<'a>(
+    harbor: &'a HarborWallet,
+    column: Column<'a, Message>,
+) -> Element<'a, Message> {
+    // The id stays the same across redraws of a screen, so iced keeps the offset when the
+    // column is rebuilt
+    let key = harbor.active_route.scroll_key();
+    let scrolled = harbor.scroll_offsets.get(&key).copied().unwrap_or_default();
+
+    let content = scrollable(row![
+        column
+            .width(Length::Fixed(512.))
+            .padding(Padding::new(48.))
+            .max_width(512),
+        horizontal_space(),
+    ])
+    .id(Id::new(key.clone()))
+    .on_scroll(move |viewport| Message::LayoutScrolled(key.clone(), viewport.absolute_offset().y))
+    .height(Length::Fill);
+
+    let to_top = (scrolled > SCROLL_TO_TOP_AFTER).then(|| {
+        container(
+            h_small_button("Back to top", SvgIcon::ChevronUp, false)
+                .on_press(Message::ScrollToTop)
+                .width(Length::Fixed(160.)),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_x(Alignment::End)
+        .align_y(Alignment::End)
+        .padding(24)
+    });
+
+    container(stack![content].push_maybe(to_top)).into()
+}
+
+/// Scrolls a screen built on [`basic_layout`] so `y` is at the top, like to bring an item
+/// into view. Only does anything while that screen is showing.
+pub fn scroll_layout_to(route: &Route, y: f32) -> Task<Message> {
+    scrollable::scroll_to(Id::new(route.scroll_key()), AbsoluteOffset { x: 0., y })
 }
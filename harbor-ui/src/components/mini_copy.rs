@@ -11,10 +11,12 @@ use crate::Message;
 use super::{SvgIcon, darken, lighten, map_icon};
 
 pub fn mini_copy(text: String) -> Button<'static, Message, Theme> {
-    let icon = map_icon(SvgIcon::Copy, 24., 24.);
+    mini_icon_button(SvgIcon::Copy).on_press(Message::CopyToClipboard(text))
+}
 
-    Button::new(icon)
-        .on_press(Message::CopyToClipboard(text))
+/// A small borderless button with just an icon, for actions that sit next to a value
+pub fn mini_icon_button(icon: SvgIcon) -> Button<'static, Message, Theme> {
+    Button::new(map_icon(icon, 24., 24.))
         .style(|theme: &Theme, status| {
             let border = Border {
                 color: Color::WHITE,
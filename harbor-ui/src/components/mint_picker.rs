@@ -0,0 +1,207 @@
+use harbor_client::MintIdentifier;
+use harbor_client::db_models::MintItem;
+use iced::widget::{button, column, container, horizontal_space, row, text};
+use iced::{Alignment, Element, Length, Padding, Theme};
+
+use crate::{HarborWallet, Message};
+
+use super::{
+    InputArgs, SvgIcon, capability_badges, chip_style, format_amount, h_input, map_icon,
+    very_subtle,
+};
+
+/// Only show the filter once the list gets long enough to be unwieldy
+pub const MINT_FILTER_THRESHOLD: usize = 6;
+
+pub const MINT_PICKER_FILTER_ID: &str = "mint_picker_filter";
+
+/// Matches a mint against the filter by nickname, announced name, or id prefix
+pub fn matches_filter(item: &MintItem, filter: &str) -> bool {
+    let filter = filter.trim().to_lowercase();
+    if filter.is_empty() {
+        return true;
+    }
+
+    let id_matches = match &item.id {
+        MintIdentifier::Fedimint(id) => id.to_string().starts_with(&filter),
+        MintIdentifier::Cashu(url) => url.to_string().to_lowercase().contains(&filter),
+    };
+
+    id_matches
+        || item.name.to_lowercase().contains(&filter)
+        || item
+            .nickname
+            .as_ref()
+            .is_some_and(|n| n.to_lowercase().contains(&filter))
+}
+
+/// A mint offered by [`h_mint_picker`], with why it can't be picked if it can't
+#[derive(Debug, Clone, Copy)]
+pub struct MintChoice<'a> {
+    pub item: &'a MintItem,
+    pub disabled_reason: Option<&'static str>,
+}
+
+impl MintChoice<'_> {
+    pub const fn is_enabled(&self) -> bool {
+        self.disabled_reason.is_none()
+    }
+}
+
+/// The choices left once the filter is applied, in the order the picker shows them. The
+/// highlighted row is an index into these.
+pub fn visible_mint_choices<'a>(choices: Vec<MintChoice<'a>>, filter: &str) -> Vec<MintChoice<'a>> {
+    choices
+        .into_iter()
+        .filter(|choice| matches_filter(choice.item, filter))
+        .collect()
+}
+
+/// A mint as a row in a mint picker, with its balance.
+pub fn mint_choice(item: &MintItem) -> Element<'static, Message> {
+    let icon = match item.id {
+        MintIdentifier::Cashu(_) => map_icon(SvgIcon::Squirrel, 16., 16.),
+        MintIdentifier::Fedimint(_) => map_icon(SvgIcon::People, 16., 16.),
+    };
+    row![
+        icon,
+        text(item.display_name().to_string()).size(16),
+        horizontal_space(),
+        text(format_amount(item.balance)).size(16),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// The open list of a mint dropdown: a row per mint with its balance and what it supports,
+/// filtered as you type once there are enough mints to need it. Up and down move the
+/// highlight and Enter picks it. Mints that can't be picked stay in the list, greyed out with
+/// the reason underneath.
+pub fn h_mint_picker<'a>(
+    harbor: &'a HarborWallet,
+    mints: Vec<MintChoice<'a>>,
+    selected: Option<&MintIdentifier>,
+    on_select: fn(MintIdentifier) -> Message,
+) -> Element<'a, Message> {
+    let show_filter = mints.len() >= MINT_FILTER_THRESHOLD;
+    let filter = &harbor.mint_picker_filter;
+    let choices = visible_mint_choices(mints, filter);
+    let highlight = harbor
+        .mint_picker_highlight
+        .min(choices.len().saturating_sub(1));
+
+    let filter_input = show_filter.then(|| {
+        // Enter in the filter picks the highlighted mint, same as Enter anywhere else
+        let on_submit = choices
+            .get(highlight)
+            .filter(|choice| choice.is_enabled())
+            .map(|choice| on_select(choice.item.id.clone()));
+        h_input(InputArgs {
+            placeholder: "Filter by name or id",
+            value: filter,
+            on_input: Message::MintPickerFilterChanged,
+            on_submit,
+            id: Some(MINT_PICKER_FILTER_ID),
+            ..InputArgs::default()
+        })
+    });
+
+    let list: Element<Message> = if choices.is_empty() {
+        container(text(format!("No mints match \"{}\"", filter.trim())).style(very_subtle))
+            .padding(Padding::from(16))
+            .into()
+    } else {
+        column(choices.into_iter().enumerate().map(|(i, choice)| {
+            let is_selected = selected == Some(&choice.item.id);
+            mint_picker_row(harbor, choice, is_selected, i == highlight, on_select)
+        }))
+        .spacing(8)
+        .into()
+    };
+
+    column![]
+        .push_maybe(filter_input)
+        .push(list)
+        .spacing(8)
+        .into()
+}
+
+fn mint_picker_row<'a>(
+    harbor: &HarborWallet,
+    choice: MintChoice<'a>,
+    is_selected: bool,
+    highlighted: bool,
+    on_select: fn(MintIdentifier) -> Message,
+) -> Element<'a, Message> {
+    let check = is_selected.then(|| map_icon(SvgIcon::SmallCheck, 16., 16.));
+    let reason = choice
+        .disabled_reason
+        .map(|reason| text(reason).size(14).style(very_subtle));
+    let content = column![
+        row![container(mint_choice(choice.item)).width(Length::Fill)]
+            .push_maybe(check)
+            .spacing(8)
+            .align_y(Alignment::Center),
+        capability_badges(choice.item, harbor.config.network),
+    ]
+    .push_maybe(reason)
+    .spacing(8);
+
+    button(content)
+        .width(Length::Fill)
+        .padding(Padding::from(16))
+        .style(move |theme: &Theme, status| {
+            let mut style = chip_style(theme, status);
+            if highlighted {
+                style.border.color = theme.palette().primary;
+                style.border.width = 2.;
+            }
+            style
+        })
+        .on_press_maybe(
+            choice
+                .is_enabled()
+                .then(|| on_select(choice.item.id.clone())),
+        )
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use harbor_client::fedimint_core::config::FederationId;
+
+    #[test]
+    fn test_visible_mint_choices() {
+        let named = |name: &str, nickname: Option<&str>| MintItem {
+            name: name.to_string(),
+            nickname: nickname.map(str::to_string),
+            ..MintItem::unknown(FederationId::dummy())
+        };
+        let mints = [
+            named("Meetup Mint", None),
+            named("Family Federation", Some("Grandma's")),
+        ];
+        let choices: Vec<MintChoice> = mints
+            .iter()
+            .map(|item| MintChoice {
+                item,
+                disabled_reason: None,
+            })
+            .collect();
+
+        // By announced name or nickname, ignoring case and stray spaces
+        let names = |filter: &str| -> Vec<String> {
+            visible_mint_choices(choices.clone(), filter)
+                .iter()
+                .map(|choice| choice.item.display_name().to_string())
+                .collect()
+        };
+        assert_eq!(names(""), vec!["Meetup Mint", "Grandma's"]);
+        assert_eq!(names(" meetup "), vec!["Meetup Mint"]);
+        assert_eq!(names("grandma"), vec!["Grandma's"]);
+        assert_eq!(names("family"), vec!["Grandma's"]);
+        assert!(names("nothing like it").is_empty());
+    }
+}
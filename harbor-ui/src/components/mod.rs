@@ -72,4 +72,19 @@ pub use operation_status::*;
 mod checkbox;
 pub use checkbox::h_checkbox;
 
+mod relative_time;
+pub use relative_time::*;
+
+mod copyable;
+pub use copyable::*;
+
+mod amount;
+pub use amount::*;
+
+mod badge;
+pub use badge::*;
+
+mod mint_picker;
+pub use mint_picker::*;
+
 pub use button::h_button;
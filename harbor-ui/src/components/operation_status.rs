@@ -1,10 +1,15 @@
-use iced::widget::{column, container, text};
-use iced::{Element, Length};
+use harbor_client::startup_timings::short_federation_id;
+use harbor_client::{MintIdentifier, StatusSeverity};
+use iced::widget::{button, column, container, row, text};
+use iced::{Alignment, Element, Length, Padding};
 use uuid::Uuid;
 
-use super::{subtitle, tag_style, very_subtle};
+use super::{
+    SvgIcon, chip_style, font_mono, h_small_button, red, subtitle, tag_style, very_subtle, yellow,
+};
 use crate::HarborWallet;
 use crate::Message;
+use crate::routes::{MintSubroute, Route};
 
 pub fn operation_status(harbor: &HarborWallet) -> Option<Element<'static, Message>> {
     operation_status_for_id(harbor, None)
@@ -30,18 +35,37 @@ pub fn operation_status_for_id(
             .collect()
     };
 
-    let status_column = column![text(status_text.join("\n")).size(18).style(subtitle)]
-        // Add Tor notice if enabled
-        .push_maybe(if harbor.tor_enabled {
-            Some(
-                text("Tor enabled. Please be patient!")
-                    .size(14)
-                    .style(very_subtle),
-            )
-        } else {
-            None
-        })
-        .spacing(8);
+    let status = id.and_then(|id| harbor.operation_status.get(&id));
+    let target = status
+        .and_then(|status| status.target.as_ref())
+        .map(|target| target_chip(harbor, target));
+    let status_line = text(status_text.join("\n")).size(18);
+    let status_line = match status.map(|status| status.severity) {
+        Some(StatusSeverity::Warning) => status_line.color(yellow()),
+        _ => status_line.style(subtitle),
+    };
+    let status_column = column![
+        row![status_line]
+            .push_maybe(target)
+            .spacing(8)
+            .align_y(Alignment::Center)
+    ]
+    // Add Tor notice if enabled
+    .push_maybe(if harbor.tor_enabled {
+        Some(
+            text("Tor enabled. Please be patient!")
+                .size(14)
+                .style(very_subtle),
+        )
+    } else {
+        None
+    })
+    .push_maybe(id.and_then(|id| stuck_notice(harbor, id)))
+    .push_maybe(id.and_then(|id| cancel_button(harbor, id)))
+    .push_maybe(id.and_then(|id| retry_button(harbor, id)))
+    .push_maybe(id.and_then(|id| copy_details_button(harbor, id)))
+    .push_maybe(id.and_then(|id| operation_log(harbor, id)))
+    .spacing(8);
 
     if status_text.is_empty() {
         None
@@ -55,3 +79,268 @@ pub fn operation_status_for_id(
         )
     }
 }
+
+/// Everything running in one place, so several operations at once don't each need their own
+/// spot on screen. Falls back to the usual single status once only one is left.
+pub fn operation_status_summary(harbor: &HarborWallet) -> Option<Element<'static, Message>> {
+    operation_status_summary_excluding(harbor, &[])
+}
+
+// The operations still running, oldest first, other than the ones in `shown`
+fn running_operations(harbor: &HarborWallet, shown: &[Uuid]) -> Vec<Uuid> {
+    let mut running: Vec<_> = harbor
+        .cancellable_operations
+        .iter()
+        .filter(|(id, _)| !shown.contains(*id))
+        .collect();
+    running.sort_by_key(|(_, started)| **started);
+    running.into_iter().map(|(id, _)| *id).collect()
+}
+
+/// [`operation_status_summary`] leaving out the operations a screen already shows on their own
+pub fn operation_status_summary_excluding(
+    harbor: &HarborWallet,
+    shown: &[Uuid],
+) -> Option<Element<'static, Message>> {
+    let running = running_operations(harbor, shown);
+
+    match running.as_slice() {
+        [] => None,
+        [id] => operation_status_for_id(harbor, Some(*id)),
+        _ => {
+            let expanded = harbor.operation_summary_expanded;
+            let toggle = if expanded {
+                h_small_button("Hide", SvgIcon::ChevronDown, false)
+            } else {
+                h_small_button("Show", SvgIcon::ChevronRight, false)
+            }
+            .on_press(Message::SetOperationSummaryExpanded(!expanded));
+            let title = row![
+                text(format!("{} operations running", running.len()))
+                    .size(18)
+                    .style(subtitle),
+                toggle
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center);
+
+            let lines = expanded.then(|| {
+                column(running.iter().map(|id| {
+                    let status = harbor.operation_status.get(id);
+                    let line = status
+                        .map_or_else(|| "Starting".to_string(), |status| status.message.clone());
+                    let target = status
+                        .and_then(|status| status.target.as_ref())
+                        .map(|target| target_chip(harbor, target));
+                    let line = text(line).size(14).width(Length::Fill);
+                    let line = match status.map(|status| status.severity) {
+                        Some(StatusSeverity::Warning) => line.color(yellow()),
+                        _ => line,
+                    };
+                    row![]
+                        .push_maybe(target)
+                        .push(line)
+                        .push_maybe(cancel_button(harbor, *id))
+                        .spacing(8)
+                        .align_y(Alignment::Center)
+                        .into()
+                }))
+                .spacing(8)
+            });
+
+            Some(
+                container(column![title].push_maybe(lines).spacing(8))
+                    .width(Length::Fill)
+                    .padding(8)
+                    .style(tag_style)
+                    .into(),
+            )
+        }
+    }
+}
+
+// Which mint an operation is about, pressing it opens the mint. One that hasn't been joined
+// yet goes by its previewed name if there is one, or its ID.
+fn target_chip(harbor: &HarborWallet, target: &MintIdentifier) -> Element<'static, Message> {
+    let (label, opens) = target_link(harbor, target);
+    button(text(label).size(14))
+        .padding(Padding::from([2, 10]))
+        .style(chip_style)
+        .on_press_maybe(opens.map(Message::Navigate))
+        .into()
+}
+
+// What the chip for `target` says, and the screen it opens if the mint's been joined
+fn target_link(harbor: &HarborWallet, target: &MintIdentifier) -> (String, Option<Route>) {
+    let joined = harbor
+        .mint_list
+        .iter()
+        .find(|item| &item.id == target && item.active);
+    let label = joined
+        .or_else(|| {
+            harbor
+                .peek_federation_item
+                .as_ref()
+                .filter(|item| &item.id == target)
+        })
+        .map_or_else(
+            || match target {
+                MintIdentifier::Fedimint(id) => short_federation_id(id),
+                MintIdentifier::Cashu(url) => url.to_string(),
+            },
+            |item| item.display_name().to_string(),
+        );
+    let opens = joined.map(|item| Route::Mints(MintSubroute::Details(item.id.clone())));
+    (label, opens)
+}
+
+// The earlier updates for an operation, behind an expander so the latest line stays the focus
+fn operation_log(harbor: &HarborWallet, id: Uuid) -> Option<Element<'static, Message>> {
+    let status = harbor.operation_status.get(&id)?;
+    if status.log.len() < 2 {
+        return None;
+    }
+
+    let expanded = harbor.expanded_operation_logs.contains(&id);
+    let toggle = if expanded {
+        h_small_button("Hide log", SvgIcon::ChevronDown, false)
+    } else {
+        h_small_button("Show log", SvgIcon::ChevronRight, false)
+    }
+    .on_press(Message::SetOperationLogExpanded(id, !expanded));
+
+    if !expanded {
+        return Some(toggle.into());
+    }
+
+    let lines = status.log_lines();
+    let copy = h_small_button("Copy log", SvgIcon::Copy, false)
+        .on_press(Message::CopyToClipboard(lines.join("\n")));
+    let log = column(
+        status
+            .log
+            .iter()
+            .zip(lines)
+            .map(|((_, severity, _), line)| {
+                let line = text(line).size(12).font(font_mono());
+                match severity {
+                    StatusSeverity::Warning => line.color(yellow()).into(),
+                    StatusSeverity::Info => line.into(),
+                }
+            }),
+    )
+    .spacing(4);
+
+    Some(
+        column![log, row![toggle, copy].spacing(8)]
+            .spacing(8)
+            .into(),
+    )
+}
+
+// Shown once an operation has gone quiet for long enough that it's probably hung, it may
+// still finish or hit its time limit on its own
+fn stuck_notice(harbor: &HarborWallet, id: Uuid) -> Option<Element<'static, Message>> {
+    (harbor.stuck_operations.contains(&id) && harbor.cancellable_operations.contains_key(&id)).then(
+        || {
+            text("Something went wrong — this operation stopped responding")
+                .size(14)
+                .color(red())
+                .into()
+        },
+    )
+}
+
+// Only failed or stuck operations can be retried, one that worked is forgotten once it finishes
+fn retry_button(harbor: &HarborWallet, id: Uuid) -> Option<Element<'static, Message>> {
+    let stuck = harbor.stuck_operations.contains(&id);
+    harbor
+        .retryable_operations
+        .get(&id)
+        .filter(|operation| operation.stopped || stuck)
+        .map(|_| {
+            h_small_button("Retry", SvgIcon::Restart, false)
+                .on_press(Message::RetryOperation(id))
+                .into()
+        })
+}
+
+// Everything about a failure in one plain text block, so bug reports aren't screenshots
+fn copy_details_button(harbor: &HarborWallet, id: Uuid) -> Option<Element<'static, Message>> {
+    harbor.error_reports.get(&id).map(|report| {
+        h_small_button("Copy details", SvgIcon::Copy, false)
+            .on_press(Message::CopyToClipboard(report.to_text()))
+            .into()
+    })
+}
+
+fn cancel_button(harbor: &HarborWallet, id: Uuid) -> Option<Element<'static, Message>> {
+    harbor.cancellable_operations.contains_key(&id).then(|| {
+        h_small_button("Cancel", SvgIcon::SmallClose, false)
+            .on_press(Message::CancelOperation(id))
+            .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use harbor_client::db_models::MintItem;
+    use harbor_client::fedimint_core::config::FederationId;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_target_link() {
+        let id = MintIdentifier::Fedimint(FederationId::dummy());
+        let mut harbor = HarborWallet::default();
+
+        // Not known yet, so it goes by the start of its ID and can't be opened
+        let (label, opens) = target_link(&harbor, &id);
+        assert_eq!(label, short_federation_id(&FederationId::dummy()));
+        assert_eq!(opens, None);
+
+        // Being previewed, it has a name but still nothing to open
+        harbor.peek_federation_item = Some(MintItem {
+            name: "Meetup Mint".to_string(),
+            ..MintItem::unknown(FederationId::dummy())
+        });
+        assert_eq!(target_link(&harbor, &id), ("Meetup Mint".to_string(), None));
+
+        // Once joined it goes by the user's name for it and opens its details
+        harbor.mint_list = vec![MintItem {
+            name: "Meetup Mint".to_string(),
+            nickname: Some("Meetup".to_string()),
+            active: true,
+            ..MintItem::unknown(FederationId::dummy())
+        }];
+        assert_eq!(
+            target_link(&harbor, &id),
+            (
+                "Meetup".to_string(),
+                Some(Route::Mints(MintSubroute::Details(id.clone())))
+            )
+        );
+    }
+
+    #[test]
+    fn test_running_operations() {
+        let mut harbor = HarborWallet::default();
+        let now = Instant::now();
+        let (first, second, third) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        for (id, secs) in [(third, 2), (first, 0), (second, 1)] {
+            harbor
+                .cancellable_operations
+                .insert(id, now + Duration::from_secs(secs));
+        }
+        assert_eq!(running_operations(&harbor, &[]), vec![first, second, third]);
+
+        // A screen showing one of them on its own leaves it out
+        assert_eq!(running_operations(&harbor, &[first]), vec![second, third]);
+
+        // The list shrinks as they finish, down to the one left
+        let _ = harbor.update(Message::CancelOperation(second));
+        assert_eq!(running_operations(&harbor, &[]), vec![first, third]);
+        let _ = harbor.update(Message::CancelOperation(first));
+        assert_eq!(running_operations(&harbor, &[]), vec![third]);
+    }
+}
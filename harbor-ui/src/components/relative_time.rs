@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use iced::Element;
+use iced::widget::{container, text, tooltip};
+
+use crate::Message;
+
+use super::{format_timestamp, tag_style, very_subtle};
+
+/// How often a screen with relative times on it is redrawn. The coarsest step shown is a
+/// minute, so this keeps it from lagging by more than half of one.
+pub const RELATIVE_TIME_REFRESH: Duration = Duration::from_secs(30);
+
+// Past this a count of days stops meaning much and the date is clearer
+const DAYS_UNTIL_DATE: u64 = 30;
+
+/// "3 minutes ago" for a unix timestamp, with the exact time in a tooltip. Screens using it
+/// need to be in `HarborWallet::shows_relative_times` so it keeps counting.
+pub fn h_relative_time<'a>(timestamp: u64) -> Element<'a, Message> {
+    let now = chrono::Utc::now().timestamp().unsigned_abs();
+    tooltip(
+        text(relative_time_text(now, timestamp))
+            .size(14)
+            .style(very_subtle),
+        container(text(format_timestamp(&timestamp)).size(14))
+            .padding(8)
+            .style(tag_style),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+// Always English words and ASCII digits, and a numeric date past a month, so nothing depends
+// on the system locale
+fn relative_time_text(now: u64, timestamp: u64) -> String {
+    // A timestamp a little ahead of the local clock is still just now
+    let secs = now.saturating_sub(timestamp);
+    let plural = |count: u64, unit: &str| {
+        if count == 1 {
+            format!("1 {unit} ago")
+        } else {
+            format!("{count} {unit}s ago")
+        }
+    };
+    match secs {
+        0..10 => "just now".to_string(),
+        10..60 => "seconds ago".to_string(),
+        60..3_600 => plural(secs / 60, "minute"),
+        3_600..86_400 => plural(secs / 3_600, "hour"),
+        _ if secs / 86_400 < DAYS_UNTIL_DATE => plural(secs / 86_400, "day"),
+        _ => {
+            let date = DateTime::from_timestamp(timestamp.try_into().unwrap_or(i64::MAX), 0)
+                .map(|utc| DateTime::<Local>::from(utc).format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            format!("on {date}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_time_text() {
+        let now = 1_700_000_000;
+        let ago = |secs| relative_time_text(now, now - secs);
+
+        assert_eq!(ago(0), "just now");
+        assert_eq!(ago(9), "just now");
+        assert_eq!(ago(10), "seconds ago");
+        assert_eq!(ago(59), "seconds ago");
+        assert_eq!(ago(60), "1 minute ago");
+        assert_eq!(ago(119), "1 minute ago");
+        assert_eq!(ago(120), "2 minutes ago");
+        assert_eq!(ago(3_599), "59 minutes ago");
+        assert_eq!(ago(3_600), "1 hour ago");
+        assert_eq!(ago(86_399), "23 hours ago");
+        assert_eq!(ago(86_400), "1 day ago");
+        assert_eq!(ago(29 * 86_400), "29 days ago");
+
+        // Clock skew doesn't make something happen in the future
+        assert_eq!(relative_time_text(now, now + 30), "just now");
+
+        // A month on it's the date, numeric so it reads the same in any locale
+        let dated = ago(30 * 86_400);
+        assert!(dated.starts_with("on 2023-10-"));
+        for secs in [0, 45, 600, 7_200, 200_000, 40 * 86_400] {
+            assert!(ago(secs).is_ascii());
+        }
+    }
+}
@@ -14,7 +14,8 @@ pub fn h_screen_header(
     disable_switcher: bool,
 ) -> Element<Message> {
     if let Some(item) = harbor.active_federation() {
-        let MintItem { name, id, .. } = item;
+        let MintItem { id, .. } = item;
+        let name = item.display_name().to_string();
 
         // Choose the right icon based on the mint type
         let mint_icon = match id {
@@ -26,7 +27,7 @@ pub fn h_screen_header(
             .mint_list
             .iter()
             .filter(|m| m.active)
-            .map(|f| f.name.clone())
+            .map(|f| f.display_name().to_string())
             .collect();
 
         let is_generating = harbor.receive_status == ReceiveStatus::Generating;
@@ -34,7 +35,11 @@ pub fn h_screen_header(
 
         let federation_element: Element<Message> = if show_picker {
             pick_list(mint_names, Some(name.clone()), move |selected_name| {
-                if let Some(mint) = harbor.mint_list.iter().find(|f| f.name == selected_name) {
+                if let Some(mint) = harbor
+                    .mint_list
+                    .iter()
+                    .find(|f| f.display_name() == selected_name)
+                {
                     Message::ChangeMint(mint.id.clone())
                 } else {
                     Message::Noop
@@ -17,7 +17,7 @@ pub fn sidebar(harbor: &HarborWallet) -> Element<Message> {
         "Transfer",
         SvgIcon::LeftRight,
         Route::Transfer,
-        harbor.active_route,
+        &harbor.active_route,
     );
     let add_a_mint_cta = container(
         row![
@@ -50,14 +50,14 @@ pub fn sidebar(harbor: &HarborWallet) -> Element<Message> {
         column![
             space,
             harbor_logo(),
-            sidebar_button("Home", SvgIcon::Home, Route::Home, harbor.active_route)
+            sidebar_button("Home", SvgIcon::Home, Route::Home, &harbor.active_route)
                 .on_press(Message::Navigate(Route::Home)),
             indicator(
                 sidebar_button(
                     "Mints",
                     SvgIcon::People,
                     Route::Mints(MintSubroute::List),
-                    harbor.active_route
+                    &harbor.active_route
                 )
                 .on_press(Message::Navigate(Route::Mints(MintSubroute::List))),
                 add_a_mint_cta,
@@ -73,7 +73,7 @@ pub fn sidebar(harbor: &HarborWallet) -> Element<Message> {
                 "History",
                 SvgIcon::Clock,
                 Route::History,
-                harbor.active_route
+                &harbor.active_route
             )
             .on_press(Message::Navigate(Route::History)),
             vertical_space(),
@@ -81,11 +81,16 @@ pub fn sidebar(harbor: &HarborWallet) -> Element<Message> {
                 "Settings",
                 SvgIcon::Settings,
                 Route::Settings,
-                harbor.active_route
+                &harbor.active_route
             )
             .on_press(Message::Navigate(Route::Settings)),
-            sidebar_button("Donate", SvgIcon::Heart, Route::Donate, harbor.active_route)
-                .on_press(Message::Navigate(Route::Donate)),
+            sidebar_button(
+                "Donate",
+                SvgIcon::Heart,
+                Route::Donate,
+                &harbor.active_route
+            )
+            .on_press(Message::Navigate(Route::Donate)),
         ]
         .spacing(8)
         .align_x(Alignment::Start),
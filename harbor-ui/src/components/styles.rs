@@ -1,7 +1,7 @@
 use iced::{
     Border, Color, Shadow, Theme,
     overlay::menu,
-    widget::{checkbox, container::Style as ContainerStyle, pick_list, text::Style},
+    widget::{button, checkbox, container::Style as ContainerStyle, pick_list, text::Style},
 };
 
 use super::{darken, lighten};
@@ -60,6 +60,14 @@ pub fn red() -> Color {
     Color::from_rgb8(250, 0, 80)
 }
 
+pub fn yellow() -> Color {
+    Color::from_rgb8(250, 190, 40)
+}
+
+pub fn blue() -> Color {
+    Color::from_rgb8(80, 150, 240)
+}
+
 pub fn menu_style(theme: &Theme) -> menu::Style {
     let border = Border {
         color: Color::WHITE,
@@ -127,6 +135,15 @@ pub fn light_container_style(theme: &Theme) -> ContainerStyle {
     }
 }
 
+// For rows that are still there but matter less, like an empty mint
+pub fn dimmed_container_style(theme: &Theme) -> ContainerStyle {
+    ContainerStyle {
+        text_color: Some(lighten(theme.palette().background, 0.5)),
+        background: Some(lighten(theme.palette().background, 0.02).into()),
+        ..light_container_style(theme)
+    }
+}
+
 pub fn side_panel_style(theme: &Theme) -> ContainerStyle {
     let gray = lighten(theme.palette().background, 0.05);
     let border = Border {
@@ -159,6 +176,31 @@ pub fn tag_style(theme: &Theme) -> ContainerStyle {
     }
 }
 
+// A small pill that can be pressed, for naming the thing a line of text is about. Greyed out
+// when it can't be.
+pub fn chip_style(theme: &Theme, status: button::Status) -> button::Style {
+    let background = match status {
+        button::Status::Hovered => lighten(theme.palette().background, 0.2),
+        button::Status::Pressed => lighten(theme.palette().background, 0.05),
+        _ => lighten(theme.palette().background, 0.1),
+    };
+    let text_color = match status {
+        button::Status::Disabled => gray(),
+        _ => link(),
+    };
+
+    button::Style {
+        background: Some(background.into()),
+        text_color,
+        border: Border {
+            color: Color::TRANSPARENT,
+            width: 0.,
+            radius: (12.).into(),
+        },
+        shadow: Shadow::default(),
+    }
+}
+
 pub fn checkbox_style(theme: &Theme, status: checkbox::Status) -> checkbox::Style {
     let background = theme.palette().background;
     let gray = lighten(theme.palette().background, 0.5);
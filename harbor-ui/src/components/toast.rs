@@ -48,6 +48,8 @@ pub struct Toast {
     pub title: String,
     pub body: Option<String>,
     pub status: ToastStatus,
+    /// Where clicking View takes the user, like back to the screen an operation started on
+    pub action: Option<Box<Message>>,
 }
 
 pub struct ToastManager<'a> {
@@ -94,6 +96,27 @@ impl<'a> ToastManager<'a> {
                     .height(Length::Fixed(24.));
 
                 let body = toast.body.clone().map(text);
+                let action = toast.action.clone().map(|action| {
+                    button(text("View").size(14))
+                        .style(|theme: &Theme, status| button::Style {
+                            background: Some(
+                                match status {
+                                    Status::Hovered => darken(theme.palette().background, 0.1),
+                                    _ => theme.palette().background,
+                                }
+                                .into(),
+                            ),
+                            text_color: Color::WHITE,
+                            border: Border {
+                                color: Color::WHITE,
+                                width: 1.,
+                                radius: (4.).into(),
+                            },
+                            shadow: Shadow::default(),
+                        })
+                        .padding([4, 8])
+                        .on_press(*action)
+                });
 
                 container(column![
                     container(
@@ -111,6 +134,7 @@ impl<'a> ToastManager<'a> {
                             .align_y(Alignment::Center),
                         ]
                         .push_maybe(body)
+                        .push_maybe(action)
                     )
                     .width(Length::Fill)
                     .padding(16)
@@ -1,10 +1,18 @@
+use crate::routes::{MintSubroute, Route};
+use crate::{
+    AmountUnit, ArchiveHintAfter, FiatDisplay, MintSort, OperationTimeout, ReceiveMethod,
+    RequestTimeout, StaleAfter,
+};
 use harbor_client::bitcoin::Network;
-use harbor_client::data_dir;
+use harbor_client::operations::{OperationKind, OperationTimeouts};
+use harbor_client::{MintIdentifier, data_dir};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::io;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum ConfigError {
@@ -40,12 +48,198 @@ impl From<serde_json::Error> for ConfigError {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Config {
     pub network: Network,
+    #[serde(default)]
+    pub mint_sort: MintSort,
+    /// Shutdown notices the user has dismissed, keyed by federation id
+    #[serde(default)]
+    pub dismissed_shutdown_notices: HashMap<String, String>,
+    #[serde(default)]
+    pub stale_after: StaleAfter,
+    #[serde(default)]
+    pub archive_hint_after: ArchiveHintAfter,
+    /// Archive suggestions the user has dismissed, with the mint's last activity at the time
+    /// so the suggestion comes back if it's used and left empty again
+    #[serde(default)]
+    pub dismissed_archive_hints: Vec<(MintIdentifier, u64)>,
+    #[serde(default)]
+    pub operation_timeouts: OperationTimeoutSettings,
+    #[serde(default)]
+    pub receive_defaults: ReceiveDefaults,
+    #[serde(default)]
+    pub fiat_display: FiatDisplay,
+    /// Desktop notifications and banners when a federation's guardians go down or come back
+    #[serde(default)]
+    pub health_notifications: bool,
+    /// The mint picked first when receiving, sending or transferring
+    #[serde(default)]
+    pub default_mint: Option<MintIdentifier>,
+    /// Whether the total across mints counts deposits that haven't confirmed yet
+    #[serde(default)]
+    pub include_pending_in_total: bool,
+    /// Holds back health checks, metadata refreshes, icon downloads and price updates that
+    /// the user didn't ask for
+    #[serde(default)]
+    pub reduce_background_data: bool,
+    /// Offers to add a mint when Harbor comes into focus with an invite code in the clipboard
+    #[serde(default)]
+    pub detect_clipboard_invites: bool,
+    #[serde(default)]
+    pub amount_unit: AmountUnit,
+    /// The screen to open on after unlocking, from where the user was when Harbor closed
+    #[serde(default)]
+    pub last_route: Option<SavedRoute>,
+}
+
+/// A screen that's safe to open on again after a restart. This is the allowlist: anything in
+/// the middle of moving money, like Send, Receive or Transfer, and Add Mint with its typed
+/// invite, isn't here and opens on Home instead.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SavedRoute {
+    Home,
+    MintList,
+    MintDetails(MintIdentifier),
+    History,
+    Settings,
+    ActivityLog,
+    Donate,
+}
+
+impl SavedRoute {
+    pub fn from_route(route: &Route) -> Option<Self> {
+        match route {
+            Route::Home => Some(Self::Home),
+            Route::Mints(MintSubroute::List) => Some(Self::MintList),
+            Route::Mints(MintSubroute::Details(id)) => Some(Self::MintDetails(id.clone())),
+            Route::History => Some(Self::History),
+            Route::Settings => Some(Self::Settings),
+            Route::ActivityLog => Some(Self::ActivityLog),
+            Route::Donate => Some(Self::Donate),
+            Route::Mints(MintSubroute::Add)
+            | Route::Welcome
+            | Route::Restore
+            | Route::Unlock
+            | Route::Transfer
+            | Route::Receive
+            | Route::Send => None,
+        }
+    }
+
+    pub fn to_route(&self) -> Route {
+        match self {
+            Self::Home => Route::Home,
+            Self::MintList => Route::Mints(MintSubroute::List),
+            Self::MintDetails(id) => Route::Mints(MintSubroute::Details(id.clone())),
+            Self::History => Route::History,
+            Self::Settings => Route::Settings,
+            Self::ActivityLog => Route::ActivityLog,
+            Self::Donate => Route::Donate,
+        }
+    }
+}
+
+/// The mint last picked to receive into, for each way of receiving
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReceiveDefaults {
+    pub lightning: Option<MintIdentifier>,
+    pub on_chain: Option<MintIdentifier>,
+}
+
+impl ReceiveDefaults {
+    pub const fn get(&self, method: ReceiveMethod) -> Option<&MintIdentifier> {
+        match method {
+            ReceiveMethod::Lightning => self.lightning.as_ref(),
+            ReceiveMethod::OnChain => self.on_chain.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, method: ReceiveMethod, mint: MintIdentifier) {
+        match method {
+            ReceiveMethod::Lightning => self.lightning = Some(mint),
+            ReceiveMethod::OnChain => self.on_chain = Some(mint),
+        }
+    }
+}
+
+/// The time limit picked for each kind of operation, and for the requests they're made of
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct OperationTimeoutSettings {
+    pub peek: OperationTimeout,
+    pub join: OperationTimeout,
+    pub resync: OperationTimeout,
+    pub connect: RequestTimeout,
+    pub guardian_request: RequestTimeout,
+}
+
+impl Default for OperationTimeoutSettings {
+    // Matches the core's own defaults
+    fn default() -> Self {
+        Self {
+            peek: OperationTimeout::OneMinute,
+            join: OperationTimeout::FiveMinutes,
+            resync: OperationTimeout::TenMinutes,
+            connect: RequestTimeout::ThirtySeconds,
+            guardian_request: RequestTimeout::TenSeconds,
+        }
+    }
+}
+
+impl OperationTimeoutSettings {
+    /// Matches the core's `OperationTimeouts::TOR_FRIENDLY`
+    pub const TOR_FRIENDLY: Self = Self {
+        peek: OperationTimeout::FiveMinutes,
+        join: OperationTimeout::TenMinutes,
+        resync: OperationTimeout::ThirtyMinutes,
+        connect: RequestTimeout::OneMinute,
+        guardian_request: RequestTimeout::ThirtySeconds,
+    };
+
+    pub const fn get(&self, kind: OperationKind) -> OperationTimeout {
+        match kind {
+            OperationKind::Peek => self.peek,
+            OperationKind::Join => self.join,
+            OperationKind::Resync => self.resync,
+        }
+    }
+
+    pub const fn set(&mut self, kind: OperationKind, timeout: OperationTimeout) {
+        match kind {
+            OperationKind::Peek => self.peek = timeout,
+            OperationKind::Join => self.join = timeout,
+            OperationKind::Resync => self.resync = timeout,
+        }
+    }
+
+    pub const fn to_client(self) -> OperationTimeouts {
+        OperationTimeouts {
+            peek: Duration::from_secs(self.peek.secs()),
+            join: Duration::from_secs(self.join.secs()),
+            resync: Duration::from_secs(self.resync.secs()),
+            connect: Duration::from_secs(self.connect.secs()),
+            guardian_request: Duration::from_secs(self.guardian_request.secs()),
+        }
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             network: Network::Bitcoin,
+            mint_sort: MintSort::default(),
+            dismissed_shutdown_notices: HashMap::new(),
+            stale_after: StaleAfter::default(),
+            archive_hint_after: ArchiveHintAfter::default(),
+            dismissed_archive_hints: Vec::new(),
+            operation_timeouts: OperationTimeoutSettings::default(),
+            receive_defaults: ReceiveDefaults::default(),
+            fiat_display: FiatDisplay::default(),
+            health_notifications: false,
+            default_mint: None,
+            include_pending_in_total: false,
+            reduce_background_data: false,
+            detect_clipboard_invites: false,
+            amount_unit: AmountUnit::default(),
+            last_route: None,
         }
     }
 }
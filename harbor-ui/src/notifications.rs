@@ -0,0 +1,28 @@
+/// Shows a desktop notification, resolving to whether the user clicked it. Only Linux
+/// desktops tell us about clicks, elsewhere it resolves to false once it's shown.
+pub async fn desktop_notification(title: String, body: String) -> bool {
+    tokio::task::spawn_blocking(move || {
+        let mut notification = notify_rust::Notification::new();
+        notification.appname("Harbor").summary(&title).body(&body);
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            let mut clicked = false;
+            match notification.action("default", "Open").show() {
+                Ok(handle) => handle.wait_for_action(|action| clicked = action == "default"),
+                Err(e) => log::warn!("Could not show desktop notification: {e}"),
+            }
+            clicked
+        }
+
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        {
+            if let Err(e) = notification.show() {
+                log::warn!("Could not show desktop notification: {e}");
+            }
+            false
+        }
+    })
+    .await
+    .unwrap_or(false)
+}
@@ -0,0 +1,140 @@
+use harbor_client::MintIdentifier;
+use harbor_client::db_models::operation_record::{OperationOutcome, OperationRecord};
+use harbor_client::operations::OperationKind;
+use iced::widget::{column, horizontal_space, pick_list, row, text};
+use iced::{Alignment, Element, Length, Padding};
+
+use crate::components::{
+    SvgIcon, basic_layout, format_timestamp, green, h_header, h_small_button, hr, menu_style,
+    pick_list_style, red, very_subtle,
+};
+use crate::routes::Route;
+use crate::{ActivityFilter, HarborWallet, Message};
+
+// The name the user knows a mint by, falling back to whatever the record has
+fn mint_name(harbor: &HarborWallet, mint: &str) -> String {
+    harbor
+        .mint_list
+        .iter()
+        .find(|item| match &item.id {
+            MintIdentifier::Fedimint(id) => id.to_string() == mint,
+            MintIdentifier::Cashu(url) => url.to_string() == mint,
+        })
+        .map_or_else(
+            || mint.chars().take(24).collect(),
+            |item| item.display_name().to_string(),
+        )
+}
+
+fn record_row(harbor: &HarborWallet, record: &OperationRecord) -> Element<'static, Message> {
+    let outcome = record.outcome();
+    let outcome_text = text(outcome.to_string()).size(16);
+    let outcome_text = match outcome {
+        OperationOutcome::Succeeded => outcome_text.color(green()),
+        OperationOutcome::Failed | OperationOutcome::Interrupted => outcome_text.color(red()),
+        OperationOutcome::Running | OperationOutcome::Cancelled => outcome_text.style(very_subtle),
+    };
+
+    let started = format_timestamp(&record.started_at.and_utc().timestamp().unsigned_abs());
+    let when = match record.ended_at {
+        Some(ended) => {
+            let secs = (ended - record.started_at).num_seconds().max(0);
+            format!("{started} · took {secs}s")
+        }
+        None => started,
+    };
+
+    let details = column![
+        row![
+            text(format!(
+                "{} {}",
+                record.kind(),
+                mint_name(harbor, &record.mint)
+            ))
+            .size(16),
+            outcome_text,
+        ]
+        .spacing(8),
+        text(when).size(14).style(very_subtle),
+    ]
+    .push_maybe(
+        record
+            .error_kind()
+            .map(|kind| text(kind.to_string()).size(14)),
+    )
+    .spacing(4);
+
+    // Previews and joins can go through the add mint screen again, a resync is run from the
+    // mint's own page
+    let can_retry = record.kind() != OperationKind::Resync
+        && matches!(
+            outcome,
+            OperationOutcome::Failed | OperationOutcome::Interrupted | OperationOutcome::Cancelled
+        );
+    let retry = can_retry.then(|| {
+        h_small_button("Try again", SvgIcon::Restart, false)
+            .on_press(Message::RetryRecordedOperation(record.target.clone()))
+    });
+
+    row![details, horizontal_space()]
+        .push_maybe(retry)
+        .align_y(Alignment::Center)
+        .into()
+}
+
+fn filter_list<T: Copy + PartialEq + std::fmt::Display + 'static>(
+    all: &[T],
+    selected: ActivityFilter<T>,
+    on_select: impl Fn(ActivityFilter<T>) -> Message + 'static,
+) -> Element<'static, Message> {
+    pick_list(ActivityFilter::options(all), Some(selected), on_select)
+        .style(pick_list_style)
+        .padding(Padding::from(16))
+        .width(Length::Fill)
+        .handle(pick_list::Handle::Arrow {
+            size: Some(iced::Pixels(24.)),
+        })
+        .menu_style(menu_style)
+        .into()
+}
+
+pub fn activity_log(harbor: &HarborWallet) -> Element<Message> {
+    let header = h_header("Activity Log", "Previews, joins and resyncs, newest first.");
+
+    let filters = row![
+        filter_list(
+            &OperationKind::ALL,
+            harbor.activity_kind_filter,
+            Message::ActivityKindFilterChanged,
+        ),
+        filter_list(
+            &OperationOutcome::ALL,
+            harbor.activity_outcome_filter,
+            Message::ActivityOutcomeFilterChanged,
+        ),
+        h_small_button("Refresh", SvgIcon::Restart, false)
+            .on_press(Message::Navigate(Route::ActivityLog)),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    let records: Vec<&OperationRecord> = harbor
+        .operation_history
+        .iter()
+        .filter(|record| harbor.activity_kind_filter.matches(&record.kind()))
+        .filter(|record| harbor.activity_outcome_filter.matches(&record.outcome()))
+        .collect();
+
+    let list = if records.is_empty() {
+        column![text("Nothing to show yet.").size(18)]
+    } else {
+        records
+            .into_iter()
+            .fold(column![], |column, record| {
+                column.push(record_row(harbor, record)).push(hr())
+            })
+            .spacing(16)
+    };
+
+    basic_layout(harbor, column![header, filters, list].spacing(48))
+}
@@ -1,17 +1,49 @@
-use iced::{Element, Length};
+use iced::{Alignment, Element, Length};
 
-use crate::components::{basic_layout, h_header, h_transaction_details, h_transaction_item, hr};
+use crate::components::{
+    SvgIcon, basic_layout, h_header, h_small_button, h_transaction_details, h_transaction_item, hr,
+    tag_style,
+};
 use crate::{HarborWallet, Message};
-use iced::widget::{column, horizontal_space, row, stack, text};
+use harbor_client::MintIdentifier;
+use iced::widget::{column, container, horizontal_space, row, stack, text};
+
+// Shows which mint the history is narrowed to, with a way back to everything
+fn filter_chip<'a>(harbor: &'a HarborWallet, mint: &MintIdentifier) -> Element<'a, Message> {
+    let name = harbor
+        .mint_list
+        .iter()
+        .find(|m| &m.id == mint)
+        .map_or("Unknown mint", |m| m.display_name());
+
+    let clear_button =
+        h_small_button("Clear", SvgIcon::SmallClose, false).on_press(Message::ClearHistoryFilter);
+
+    container(
+        row![text(format!("Mint: {name}")).size(16), clear_button]
+            .align_y(Alignment::Center)
+            .spacing(16),
+    )
+    .padding(8)
+    .style(tag_style)
+    .into()
+}
 
 pub fn history(harbor: &HarborWallet) -> Element<Message> {
     let header = h_header("History", "Here's what's happened so far.");
 
-    let transactions = if harbor.transaction_history.is_empty() {
-        column![text("Nothing has happened yet.").size(18)]
+    let (history, empty_text) = match &harbor.history_filter {
+        Some(_) => (
+            &harbor.filtered_history,
+            "Nothing has happened with this mint yet.",
+        ),
+        None => (&harbor.transaction_history, "Nothing has happened yet."),
+    };
+
+    let transactions = if history.is_empty() {
+        column![text(empty_text).size(18)]
     } else {
-        harbor
-            .transaction_history
+        history
             .iter()
             .fold(column![], |column, item| {
                 let is_selected = harbor
@@ -24,9 +56,17 @@ pub fn history(harbor: &HarborWallet) -> Element<Message> {
             })
             .spacing(16)
     };
-    let left_column = column![header, transactions].spacing(48);
+    let left_column = column![header]
+        .push_maybe(
+            harbor
+                .history_filter
+                .as_ref()
+                .map(|mint| filter_chip(harbor, mint)),
+        )
+        .push(transactions)
+        .spacing(48);
 
-    let content = basic_layout(left_column);
+    let content = basic_layout(harbor, left_column);
     let mut layers = stack![content];
 
     if let Some(selected_tx) = &harbor.selected_transaction {
@@ -1,4 +1,4 @@
-use crate::components::{SvgIcon, format_amount, h_button, h_screen_header};
+use crate::components::{SvgIcon, format_amount, h_button, h_screen_header, subtitle};
 use iced::widget::{center, column, container, row, text};
 use iced::{Alignment, Element, Length};
 
@@ -12,6 +12,10 @@ pub fn home(harbor: &HarborWallet) -> Element<Message> {
         .map_or_else(|| format_amount(0), |f| format_amount(f.balance));
 
     let balance = text(formatted_balance).size(64);
+    let fiat = harbor
+        .active_federation()
+        .and_then(|f| harbor.fiat_value(f.balance))
+        .map(|fiat| text(fiat).size(24).style(subtitle));
     let send_disabled = harbor.active_federation().is_none_or(|f| f.balance == 0);
     let receive_disabled = harbor.active_federation().is_none();
     let send_button = h_button("Send", SvgIcon::UpRight, false);
@@ -34,10 +38,13 @@ pub fn home(harbor: &HarborWallet) -> Element<Message> {
     column![
         h_screen_header(harbor, false, false),
         container(center(
-            column![balance, buttons]
-                .spacing(32)
-                .align_x(Alignment::Center)
-                .max_width(512)
+            column![
+                column![balance].push_maybe(fiat).align_x(Alignment::Center),
+                buttons
+            ]
+            .spacing(32)
+            .align_x(Alignment::Center)
+            .max_width(512)
         ))
         .height(Length::Fill)
     ]
@@ -1,84 +1,498 @@
-use std::str::FromStr;
+use std::cmp::Reverse;
 
-use harbor_client::MintConnectionInfo;
-use iced::Element;
-use iced::widget::{column, row};
+use harbor_client::db_models::transaction_item::{BALANCE_HISTORY_DAYS, FlowTotals};
+use harbor_client::db_models::{GatewayInfo, GuardianHealth, MintItem, PendingAmounts};
+use harbor_client::diagnostics::MintDiagnostics;
+use harbor_client::fedimint_core::config::FederationId;
+use harbor_client::mint_error::{AddMintError, AddMintErrorKind};
+use harbor_client::{
+    FEDIMINT_CLIENT_VERSION, MAX_MINT_NOTES_LEN, MintConnectionError, MintIdentifier,
+};
+use iced::widget::{column, container, horizontal_space, pick_list, qr_code, row, text};
+use iced::{Alignment, Border, Color, Element, Font, Length, Padding};
 
 use crate::components::{
-    InputArgs, SvgIcon, basic_layout, h_button, h_federation_archived, h_federation_item,
-    h_federation_item_preview, h_header, h_input, operation_status_for_id,
+    ConfirmModalState, InputArgs, MINT_FILTER_THRESHOLD, SvgIcon, balance_refresh, basic_layout,
+    confirm_archive, fiat_rate_note, format_amount, format_timestamp, green,
+    guardian_health_summary, h_back_header, h_button, h_checkbox, h_copyable,
+    h_federation_archived, h_federation_item, h_federation_item_preview, h_header, h_input,
+    h_small_button, health_banner, light_container_style, map_icon, matches_filter,
+    max_balance_banner, menu_style, mini_copy, mint_icon, offline_caption, operation_status_for_id,
+    operation_status_summary, operation_status_summary_excluding, pending_caption, pick_list_style,
+    recovery_progress, red, rename_form, subtitle, text_link, very_subtle, yellow,
 };
-use crate::{AddFederationStatus, HarborWallet, Message, PeekStatus};
+use crate::{AddFederationStatus, HarborWallet, Message, MintSort, PeekStatus};
 
 use super::{MintSubroute, Route};
 
-// Expects to always have at least one federation, otherwise we should be on the add mint screen
-// TODO: now that we have archived mints, we should show them even if there are no active mints
+// Manual leaves the list in the order it already has, everything else sorts on top of that
+fn sort_mints(items: &mut [&MintItem], sort: MintSort) {
+    match sort {
+        MintSort::Manual => {}
+        MintSort::Balance => items.sort_by_key(|m| Reverse(m.balance)),
+        MintSort::Alphabetical => items.sort_by_key(|m| m.display_name().to_lowercase()),
+        // Mints without a timestamp end up last
+        MintSort::DateJoined => items.sort_by_key(|m| Reverse(m.joined_at)),
+        MintSort::LastUsed => items.sort_by_key(|m| Reverse(m.last_used_at)),
+    }
+}
+
+// Total of the active mints, with a bar showing how it's split between them
+fn balance_summary(harbor: &HarborWallet) -> Option<Element<Message>> {
+    let active: Vec<&MintItem> = harbor.mint_list.iter().filter(|m| m.active).collect();
+    if active.is_empty() {
+        return None;
+    }
+
+    let settled: u64 = active.iter().map(|m| m.balance).sum();
+    let pending = active
+        .iter()
+        .fold(PendingAmounts::default(), |sum, m| PendingAmounts {
+            incoming: sum.incoming + m.pending.incoming,
+            outgoing: sum.outgoing + m.pending.outgoing,
+        });
+    // Payments in flight have already left the balances, so counting pending only adds the
+    // deposits still confirming
+    let total = if harbor.config.include_pending_in_total {
+        settled + pending.incoming
+    } else {
+        settled
+    };
+    let any_syncing = active.iter().any(|m| m.syncing);
+
+    let total_row = row![text(format_amount(total)).size(24)]
+        .push_maybe(any_syncing.then(|| text("+ syncing").size(18).style(very_subtle)))
+        .push_maybe(
+            harbor
+                .fiat_value(total)
+                .map(|fiat| text(fiat).size(18).style(subtitle)),
+        )
+        .align_y(Alignment::Center)
+        .spacing(8);
+
+    let count = if active.len() == 1 {
+        "1 mint".to_string()
+    } else {
+        format!("{} mints", active.len())
+    };
+
+    let pending_toggle = (!pending.is_empty()).then(|| {
+        column![]
+            .push_maybe(pending_caption(pending))
+            .push(h_checkbox(
+                "Count pending deposits in the total",
+                None,
+                harbor.config.include_pending_in_total,
+                false,
+                Message::SetIncludePendingInTotal,
+            ))
+            .spacing(8)
+    });
+
+    let breakdown = (settled > 0).then(|| {
+        active
+            .iter()
+            .filter(|m| m.balance > 0)
+            .enumerate()
+            .fold(row![], |bar, (i, m)| {
+                // Give every mint with funds at least a sliver so it stays visible
+                let portion = (m.balance * 1000 / settled).clamp(1, 1000) as u16;
+                let alpha = [1.0, 0.7, 0.45, 0.25][i % 4];
+                bar.push(
+                    container(text(""))
+                        .width(Length::FillPortion(portion))
+                        .height(Length::Fixed(8.))
+                        .style(move |_theme| container::Style {
+                            background: Some(Color::WHITE.scale_alpha(alpha).into()),
+                            border: Border {
+                                radius: (4.).into(),
+                                ..Border::default()
+                            },
+                            ..container::Style::default()
+                        }),
+                )
+            })
+            .spacing(2)
+    });
+
+    let summary = column![
+        row![
+            total_row,
+            horizontal_space(),
+            text(count).size(18).style(subtitle)
+        ]
+        .align_y(Alignment::Center)
+    ]
+    .push_maybe(pending_toggle)
+    .push_maybe(breakdown)
+    .push_maybe(
+        harbor
+            .fiat_value(total)
+            .and(harbor.exchange_rate.as_ref())
+            .map(fiat_rate_note),
+    )
+    .spacing(16);
+
+    Some(
+        container(summary)
+            .padding(16)
+            .width(Length::Fill)
+            .style(light_container_style)
+            .into(),
+    )
+}
+
+// Toggles selection mode, and while selecting offers the bulk actions
+fn selection_bar(harbor: &HarborWallet) -> Element<Message> {
+    if !harbor.selecting_mints {
+        let select_button = h_small_button("Select", SvgIcon::SmallCheck, false)
+            .on_press(Message::SetMintSelectMode(true));
+        return row![horizontal_space(), select_button].into();
+    }
+
+    let count = harbor.selected_mints.len();
+    let confirm = Message::SetConfirmModal(Some(ConfirmModalState {
+        title: "Archive selected mints?".to_string(),
+        description: format!(
+            "This will remove {count} mint{} from your list of mints. Mints that still have a balance will be skipped.",
+            if count == 1 { "" } else { "s" }
+        ),
+        confirm_action: Box::new(Message::ArchiveSelectedMints),
+        cancel_action: Box::new(Message::SetConfirmModal(None)),
+        confirm_button_text: "Archive Mints".to_string(),
+        type_to_confirm: None,
+        enter_confirms: true,
+    }));
+
+    let archive_button = h_small_button("Archive selected", SvgIcon::Trash, false)
+        .on_press_maybe((count > 0).then_some(confirm));
+    let cancel_button = h_small_button("Cancel", SvgIcon::SmallClose, false)
+        .on_press(Message::SetMintSelectMode(false));
+
+    row![
+        text(format!("{count} selected")).size(18).style(subtitle),
+        horizontal_space(),
+        cancel_button,
+        archive_button
+    ]
+    .align_y(Alignment::Center)
+    .spacing(8)
+    .into()
+}
+
+// Expects to always have at least one mint, active or archived, otherwise we should be on the
+// add mint screen
 fn mints_list(harbor: &HarborWallet) -> Element<Message> {
-    let header = h_header("Mints", "Manage your mints here.");
+    let sort_picker = pick_list(
+        MintSort::ALL,
+        Some(harbor.config.mint_sort),
+        Message::MintSortChanged,
+    )
+    .style(pick_list_style)
+    .menu_style(menu_style)
+    .padding(Padding::from(8))
+    .text_size(14);
+
+    let header = row![
+        h_back_header(
+            "Mints",
+            "Manage your mints here.",
+            !harbor.route_history.is_empty()
+        ),
+        horizontal_space(),
+        sort_picker
+    ]
+    .align_y(Alignment::Center);
 
-    let active = harbor
+    let show_filter = harbor.mint_list.len() >= MINT_FILTER_THRESHOLD;
+    let filter = if show_filter {
+        harbor.mint_filter_str.as_str()
+    } else {
+        ""
+    };
+
+    // Only blame the filter when there's one typed in and the section had mints to hide
+    let empty_section = |active: bool, unfiltered: &'static str| {
+        let has_mints = harbor.mint_list.iter().any(|m| m.active == active);
+        let copy = if filter.trim().is_empty() || !has_mints {
+            unfiltered
+        } else {
+            "No mints match your filter."
+        };
+        column![text(copy).size(18).style(subtitle)]
+    };
+
+    let mut active_items: Vec<&MintItem> = harbor
         .mint_list
         .iter()
-        .filter(|a| a.active)
-        .fold(column![], |column, item| {
-            column.push(h_federation_item(item))
-        })
-        .spacing(48);
+        .filter(|a| a.active && matches_filter(a, filter))
+        .collect();
+    sort_mints(&mut active_items, harbor.config.mint_sort);
 
-    let inactive = harbor
+    let active = if active_items.is_empty() {
+        empty_section(
+            true,
+            "No active mints. Add one, or rejoin an archived one below.",
+        )
+    } else {
+        active_items
+            .into_iter()
+            .fold(column![], |column, item| {
+                column.push(h_federation_item(item, harbor))
+            })
+            .spacing(48)
+    };
+
+    let mut inactive_items: Vec<&MintItem> = harbor
         .mint_list
         .iter()
-        .filter(|a| !a.active)
-        .fold(column![], |column, item| {
-            column.push(h_federation_archived(item, harbor))
-        })
-        .spacing(48);
+        .filter(|a| !a.active && matches_filter(a, filter))
+        .collect();
+    sort_mints(&mut inactive_items, harbor.config.mint_sort);
+
+    let inactive = if inactive_items.is_empty() {
+        empty_section(false, "No archived mints.")
+    } else {
+        inactive_items
+            .into_iter()
+            .fold(column![], |column, item| {
+                column.push(h_federation_archived(item, harbor))
+            })
+            .spacing(48)
+    };
 
     let add_another_mint_button = h_button("Add Another Mint", SvgIcon::Plus, false)
         .on_press(Message::Navigate(Route::Mints(MintSubroute::Add)));
 
+    let export_button =
+        h_button("Export Mint List", SvgIcon::Download, false).on_press(Message::ExportMintList);
+
+    let is_importing = harbor.current_import_id.is_some();
+    let import_button = h_button("Import Mint List", SvgIcon::Upload, is_importing)
+        .on_press_maybe((!is_importing).then_some(Message::ImportMintList));
+
+    let import_url_button = h_button("Import from URL", SvgIcon::Upload, false)
+        .on_press_maybe((!is_importing).then_some(Message::SetImportUrlOpen(true)));
+
+    let list_actions = column![
+        add_another_mint_button,
+        row![export_button, import_button, import_url_button].spacing(16)
+    ]
+    .push_maybe(harbor.import_url_open.then(|| import_url_form(harbor)))
+    .push_maybe(
+        harbor
+            .current_import_id
+            .and_then(|id| operation_status_for_id(harbor, Some(id))),
+    )
+    .spacing(16);
+
+    let filter_input = show_filter.then(|| {
+        h_input(InputArgs {
+            label: "Filter",
+            placeholder: "Name, nickname, or federation ID",
+            value: &harbor.mint_filter_str,
+            on_input: Message::MintFilterChanged,
+            ..InputArgs::default()
+        })
+    });
+
+    let column = column![header]
+        .push_maybe(offline_caption(harbor))
+        .push_maybe(operation_status_summary(harbor))
+        .push_maybe(balance_summary(harbor))
+        .push_maybe(filter_input)
+        .push(selection_bar(harbor))
+        .push(active);
+
     // if we have inactive mints, display them
     let column = if harbor.mint_list.iter().filter(|a| !a.active).count() > 0 {
         let archived_header = h_header("Archived Mints", "Mints you've joined and left.");
-        column![
-            header,
-            active,
-            add_another_mint_button,
-            archived_header,
-            inactive
-        ]
-        .spacing(48)
+        column
+            .push(list_actions)
+            .push(archived_header)
+            .push(inactive)
+            .spacing(48)
     } else {
-        column![header, active, add_another_mint_button].spacing(48)
+        column.push(list_actions).spacing(48)
     };
 
-    basic_layout(column)
+    basic_layout(harbor, column)
+}
+
+// What to tell the user after a failed preview or join, and what they can do about it
+fn add_mint_failure_copy(kind: AddMintErrorKind) -> &'static str {
+    match kind {
+        AddMintErrorKind::Offline => {
+            "Nothing was sent to the mint. Preview and Join come back once you're online again."
+        }
+        AddMintErrorKind::NetworkUnreachable => {
+            "Check your internet connection, or the mint may be offline. Try again in a bit."
+        }
+        AddMintErrorKind::GuardianRejected => {
+            "The mint wouldn't accept the request. Ask its operators for a fresh invite code."
+        }
+        AddMintErrorKind::WrongNetwork => {
+            "This mint is for a different bitcoin network. Switch networks in Settings to use it."
+        }
+        AddMintErrorKind::InvalidConfig => {
+            "The mint's details don't match the invite code, so it wasn't joined. Double check where the code came from."
+        }
+        AddMintErrorKind::AlreadyJoined => "This mint is already in your wallet.",
+        AddMintErrorKind::TimedOut => {
+            "The mint took too long to answer. It may be busy, try again."
+        }
+        AddMintErrorKind::Database => {
+            "Harbor couldn't write the mint to disk, so nothing was joined. Check there's free disk space and that Harbor can write to its data directory, then try again."
+        }
+        AddMintErrorKind::Other => "Something unexpected went wrong.",
+    }
+}
+
+// Explains a failed preview or join, offering the next step when there's an obvious one. Trying
+// again is left to the Retry on the operation's status.
+fn add_mint_failure<'a>(error: &AddMintError) -> Element<'a, Message> {
+    let action = match error.kind {
+        AddMintErrorKind::WrongNetwork => Some(
+            h_small_button("Open Settings", SvgIcon::Settings, false)
+                .on_press(Message::Navigate(Route::Settings)),
+        ),
+        AddMintErrorKind::AlreadyJoined => Some(
+            h_small_button("View Mints", SvgIcon::People, false)
+                .on_press(Message::Navigate(Route::Mints(MintSubroute::List))),
+        ),
+        AddMintErrorKind::Database => Some(
+            h_small_button("Open Data Directory", SvgIcon::FolderLock, false)
+                .on_press(Message::OpenDataDirectory),
+        ),
+        AddMintErrorKind::Offline
+        | AddMintErrorKind::NetworkUnreachable
+        | AddMintErrorKind::TimedOut
+        | AddMintErrorKind::GuardianRejected
+        | AddMintErrorKind::InvalidConfig
+        | AddMintErrorKind::Other => None,
+    };
+
+    column![
+        text(error.kind.to_string()).size(16).color(red()),
+        text(add_mint_failure_copy(error.kind)).size(14),
+        text(error.message.clone()).size(12).style(very_subtle),
+    ]
+    .push_maybe(action)
+    .spacing(8)
+    .into()
+}
+
+fn connection_error_copy(error: &MintConnectionError, input: &str) -> String {
+    match error {
+        MintConnectionError::NotBech32 => {
+            "That doesn't look like an invite code or a mint URL".to_string()
+        }
+        MintConnectionError::BadChecksum => {
+            "This invite code has a typo in it, try copying it again".to_string()
+        }
+        MintConnectionError::WrongHrp(hrp) if hrp.starts_with("ln") => {
+            "That's a lightning invoice or offer, not an invite code".to_string()
+        }
+        MintConnectionError::WrongHrp(hrp) if hrp == "bc" || hrp == "tb" || hrp == "bcrt" => {
+            "That's a bitcoin address, not an invite code".to_string()
+        }
+        MintConnectionError::WrongHrp(hrp) => {
+            format!("That's a \"{hrp}\" code, invite codes start with fed1")
+        }
+        MintConnectionError::TruncatedInvite => {
+            "This invite code is cut off, make sure you copied all of it".to_string()
+        }
+        MintConnectionError::UnsupportedInviteVersion => {
+            "This invite code requires a newer version of Harbor".to_string()
+        }
+        MintConnectionError::MixedFederations => {
+            "These invite codes are for different federations, paste one federation at a time"
+                .to_string()
+        }
+        MintConnectionError::InvalidUrl(_) if !input.contains("://") => {
+            "Mint URLs need to start with https://".to_string()
+        }
+        MintConnectionError::InvalidUrl(e) => format!("That isn't a valid mint URL: {e}"),
+        MintConnectionError::UnsupportedScheme(scheme) => {
+            format!("Mints are reached over https://, not {scheme}://")
+        }
+    }
 }
 
 fn mints_add(harbor: &HarborWallet) -> Element<Message> {
-    let header = h_header("Add Mint", "Add a new mint to your wallet.");
+    let header = h_back_header(
+        "Add Mint",
+        "Add a new mint to your wallet.",
+        !harbor.route_history.is_empty(),
+    );
 
-    let mint_connection_info = MintConnectionInfo::from_str(&harbor.mint_invite_code_str).ok();
+    // Parsed in the update once typing pauses, so there's nothing to show until then
+    let parsed = harbor.parsed_connection_info.as_ref();
+    // Connecting to an onion service without Tor can only fail, so don't offer it
+    let needs_tor = parsed
+        .and_then(|r| r.as_ref().ok())
+        .is_some_and(|info| info.requires_tor() && !harbor.tor_enabled);
+    let mint_connection_info = parsed
+        .and_then(|r| r.as_ref().ok())
+        .cloned()
+        .filter(|_| !needs_tor);
 
     let column = match &harbor.peek_federation_item {
         None => {
+            // Let the user know what they pasted before they go looking it up, or what's wrong
+            // with it if it can't be used
+            let detected = match parsed {
+                None => None,
+                Some(_) if harbor.mint_invite_code_str.trim().is_empty() => None,
+                Some(Ok(info)) => Some(format!("{} detected", info.kind())),
+                Some(Err(e)) => Some(connection_error_copy(e, &harbor.mint_invite_code_str)),
+            };
+
             let mint_input = h_input(InputArgs {
                 label: "Invite Code or Mint URL",
                 value: &harbor.mint_invite_code_str,
                 on_input: Message::MintInviteCodeInputChanged,
                 disabled: harbor.peek_status == PeekStatus::Peeking,
+                trailing_action: Some((SvgIcon::Clipboard, Message::PasteMintInviteCode)),
+                helper_text: detected.as_deref(),
+                error: parsed.is_some_and(|r| r.is_err())
+                    && !harbor.mint_invite_code_str.trim().is_empty(),
                 ..InputArgs::default()
             });
 
+            let failure = harbor.add_mint_failure.as_ref().map(add_mint_failure);
+
             let peek_mint_button = h_button(
                 "Preview",
                 SvgIcon::Eye,
-                harbor.peek_status == PeekStatus::Peeking,
+                harbor.button_state(
+                    harbor.current_peek_id,
+                    harbor.peek_status == PeekStatus::Peeking,
+                ),
             )
-            .on_press_maybe(mint_connection_info.map(Message::PeekMint));
+            .on_press_maybe(
+                mint_connection_info
+                    .filter(|_| harbor.is_online())
+                    .map(Message::PeekMint),
+            );
+
+            let tor_notice = needs_tor.then(|| {
+                column![
+                    text("This mint is only reachable over Tor, turn on Tor in Settings")
+                        .size(14)
+                        .color(red()),
+                    h_small_button("Open Settings", SvgIcon::Settings, false)
+                        .on_press(Message::Navigate(Route::Settings))
+                ]
+                .spacing(8)
+            });
 
-            let mut peek_column = column![mint_input, peek_mint_button].spacing(16);
+            let mut peek_column = column![mint_input]
+                .push_maybe(tor_notice)
+                .push(peek_mint_button)
+                .push_maybe(offline_caption(harbor))
+                .push_maybe(failure)
+                .spacing(16);
 
             // Add status display for preview operation
             if let Some(current_peek_id) = harbor.current_peek_id {
@@ -87,7 +501,13 @@ fn mints_add(harbor: &HarborWallet) -> Element<Message> {
                 }
             }
 
-            column![header, peek_column].spacing(48)
+            column![header]
+                .push_maybe(operation_status_summary_excluding(
+                    harbor,
+                    harbor.current_peek_id.as_slice(),
+                ))
+                .push(peek_column)
+                .spacing(48)
         }
 
         Some(peek_federation_item) => {
@@ -95,14 +515,24 @@ fn mints_add(harbor: &HarborWallet) -> Element<Message> {
 
             let is_joining = harbor.add_federation_status == AddFederationStatus::Adding;
 
-            let add_mint_button = h_button("Join Mint", SvgIcon::Plus, is_joining)
-                .on_press_maybe(mint_connection_info.map(Message::AddMint));
+            let failure = harbor.add_mint_failure.as_ref().map(add_mint_failure);
+
+            let add_mint_state = harbor.button_state(harbor.current_add_id, is_joining);
+            let add_mint_button = h_button("Join Mint", SvgIcon::Plus, add_mint_state)
+                .on_press_maybe(
+                    mint_connection_info
+                        .filter(|_| harbor.is_online())
+                        .map(Message::AddMint),
+                );
 
             let start_over_button = h_button("Start Over", SvgIcon::Restart, false)
                 .on_press(Message::CancelAddFederation);
 
             let button_row = row![start_over_button, add_mint_button].spacing(16);
-            let mut preview_column = column![federation_preview, button_row].spacing(16);
+            let mut preview_column = column![federation_preview, button_row]
+                .push_maybe(offline_caption(harbor))
+                .push_maybe(failure)
+                .spacing(16);
 
             // Add status display for add operation
             if let Some(current_add_id) = harbor.current_add_id {
@@ -111,20 +541,1061 @@ fn mints_add(harbor: &HarborWallet) -> Element<Message> {
                 }
             }
 
-            column![header, preview_column].spacing(48)
+            column![header]
+                .push_maybe(operation_status_summary_excluding(
+                    harbor,
+                    harbor.current_add_id.as_slice(),
+                ))
+                .push(preview_column)
+                .spacing(48)
         }
     };
 
-    basic_layout(column)
+    basic_layout(harbor, column)
 }
 
-pub fn mints(harbor: &HarborWallet) -> Element<Message> {
-    if harbor.mint_list.iter().filter(|f| f.active).count() == 0 {
-        mints_add(harbor)
+// A label above its value, laid out like the transaction details panel
+fn detail_section<'a>(
+    label: &'static str,
+    value: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    column![text(label).size(16).style(subtitle), value.into()]
+        .spacing(8)
+        .into()
+}
+
+fn guardian_row(guardian: &GuardianHealth) -> Element<Message> {
+    let status = if guardian.online {
+        text("Online").size(16).color(green())
+    } else {
+        text("Offline").size(16).color(red())
+    };
+
+    let endpoint = if guardian.onion {
+        format!("{} (onion)", guardian.endpoint_host)
+    } else {
+        guardian.endpoint_host.clone()
+    };
+    let last_contact = guardian
+        .last_contact
+        .map_or_else(|| "Never".to_string(), |t| format_timestamp(&t));
+    let version = guardian.version.as_deref().unwrap_or("Unknown");
+
+    let mut column = column![
+        row![text(&guardian.name).size(16), horizontal_space(), status].align_y(Alignment::Center),
+        text(endpoint).size(14).style(subtitle),
+        text(format!("Last contact: {last_contact} · Version: {version}"))
+            .size(14)
+            .style(subtitle)
+    ]
+    .spacing(4);
+
+    if let Some(error) = &guardian.last_error {
+        column = column.push(text(error.clone()).size(14).color(red()));
+    }
+
+    column.into()
+}
+
+fn guardian_list(item: &MintItem) -> Option<Element<Message>> {
+    if let Some(health) = &item.guardian_health {
+        let guardians = health.iter().fold(column![], |column, guardian| {
+            column.push(guardian_row(guardian))
+        });
+
+        return Some(
+            column![guardian_health_summary(health), guardians.spacing(16)]
+                .spacing(16)
+                .into(),
+        );
+    }
+
+    // No probe has finished yet, so just list who the guardians are
+    let guardians = item.guardians.as_ref()?;
+    Some(
+        guardians
+            .iter()
+            .fold(column![], |column, name| column.push(text(name).size(16)))
+            .spacing(8)
+            .into(),
+    )
+}
+
+fn version_row<'a>(label: String, version: String) -> Element<'a, Message> {
+    row![
+        text(label).size(16),
+        horizontal_space(),
+        text(version).size(16).style(subtitle)
+    ]
+    .align_y(Alignment::Center)
+    .into()
+}
+
+// Fees are tracked in msats but nearly always come out to whole sats
+fn format_msats(msats: u64) -> String {
+    if msats % 1_000 == 0 {
+        format_amount(msats / 1_000)
+    } else {
+        format!("{msats} msat")
+    }
+}
+
+fn fee_schedule<'a>(harbor: &'a HarborWallet, mint: &MintIdentifier) -> Element<'a, Message> {
+    let Some((_, fees)) = harbor.mint_fees.as_ref().filter(|(id, _)| id == mint) else {
+        return text("Checking the mint's fees...")
+            .size(16)
+            .style(subtitle)
+            .into();
+    };
+
+    let rows = fees
+        .schedule
+        .as_ref()
+        .map(|schedule| {
+            [
+                ("On-chain deposit", schedule.peg_in_msats.map(format_msats)),
+                (
+                    "On-chain withdrawal",
+                    schedule.peg_out_msats.map(format_msats),
+                ),
+                (
+                    "Lightning contract funding",
+                    schedule.lightning_contract_input_msats.map(format_msats),
+                ),
+                (
+                    "Lightning contract claim",
+                    schedule.lightning_contract_output_msats.map(format_msats),
+                ),
+                (
+                    "Ecash spent",
+                    schedule
+                        .input_fee_ppk
+                        .map(|ppk| format!("{ppk} sats per 1,000 proofs")),
+                ),
+            ]
+            .into_iter()
+            .filter_map(|(label, value)| Some(version_row(label.to_string(), value?)))
+            .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    if rows.is_empty() {
+        return text("This mint doesn't publish a fee schedule Harbor can read.")
+            .size(16)
+            .style(subtitle)
+            .into();
+    }
+
+    column(rows).spacing(8).into()
+}
+
+fn fees_paid<'a>(harbor: &'a HarborWallet, mint: &MintIdentifier) -> Element<'a, Message> {
+    let Some((_, fees)) = harbor.mint_fees.as_ref().filter(|(id, _)| id == mint) else {
+        return text("Adding up fees...").size(16).style(subtitle).into();
+    };
+    let paid = fees.paid;
+
+    column![
+        version_row(
+            "Lightning, last 30 days".to_string(),
+            format_msats(paid.last_30_days.lightning_msats)
+        ),
+        version_row(
+            "Lightning, all time".to_string(),
+            format_msats(paid.all_time.lightning_msats)
+        ),
+        version_row(
+            "On-chain, last 30 days".to_string(),
+            format_msats(paid.last_30_days.onchain_msats)
+        ),
+        version_row(
+            "On-chain, all time".to_string(),
+            format_msats(paid.all_time.onchain_msats)
+        ),
+    ]
+    .spacing(8)
+    .into()
+}
+
+// One line per window, like "3 transactions · 5,000 sats in · 1,200 sats out · 4 sats in fees"
+fn flow_row<'a>(label: &str, totals: &FlowTotals) -> Element<'a, Message> {
+    let summary = if totals.count == 0 {
+        "Nothing yet".to_string()
+    } else {
+        let count = if totals.count == 1 {
+            "1 transaction".to_string()
+        } else {
+            format!("{} transactions", totals.count)
+        };
+        format!(
+            "{count} · {} in · {} out · {} in fees",
+            format_amount(totals.received_sats),
+            format_amount(totals.sent_sats),
+            format_msats(totals.fees_msats)
+        )
+    };
+    version_row(label.to_string(), summary)
+}
+
+// A bar per day of the balance history, scaled to the highest point
+#[allow(clippy::cast_precision_loss)]
+fn balance_sparkline<'a>(points: &[u64]) -> Element<'a, Message> {
+    let max = points.iter().copied().max().unwrap_or(0).max(1);
+    points
+        .iter()
+        .fold(row![], |bars, point| {
+            let height = 2. + 30. * (*point as f32 / max as f32);
+            bars.push(
+                container(text(""))
+                    .width(Length::Fill)
+                    .height(Length::Fixed(height))
+                    .style(|_theme| container::Style {
+                        background: Some(Color::WHITE.scale_alpha(0.7).into()),
+                        border: Border {
+                            radius: (1.).into(),
+                            ..Border::default()
+                        },
+                        ..container::Style::default()
+                    }),
+            )
+        })
+        .height(Length::Fixed(32.))
+        .align_y(Alignment::End)
+        .spacing(2)
+        .into()
+}
+
+fn mint_activity<'a>(harbor: &'a HarborWallet, mint: &MintIdentifier) -> Element<'a, Message> {
+    let Some(stats) = harbor.mint_stats.get(mint) else {
+        return text("Adding up activity...")
+            .size(16)
+            .style(subtitle)
+            .into();
+    };
+
+    column![
+        balance_sparkline(&stats.balance_history),
+        text(format!("Balance over the last {BALANCE_HISTORY_DAYS} days"))
+            .size(14)
+            .style(very_subtle),
+        flow_row("Last 7 days", &stats.last_7_days),
+        flow_row("Last 30 days", &stats.last_30_days),
+        flow_row("All time", &stats.all_time),
+    ]
+    .spacing(8)
+    .into()
+}
+
+fn version_info<'a>(
+    harbor: &'a HarborWallet,
+    federation_id: &FederationId,
+) -> Element<'a, Message> {
+    let client_row = version_row(
+        "Harbor's fedimint client".to_string(),
+        FEDIMINT_CLIENT_VERSION.to_string(),
+    );
+
+    let Some((_, versions)) = harbor
+        .mint_versions
+        .as_ref()
+        .filter(|(id, _)| id == federation_id)
+    else {
+        return column![
+            text("Checking the federation's versions...")
+                .size(16)
+                .style(subtitle),
+            client_row
+        ]
+        .spacing(8)
+        .into();
+    };
+
+    let mut column = column![].spacing(8);
+
+    if versions.consensus_unsupported {
+        let warning = "This federation runs a newer consensus version than Harbor supports. \
+            Some things may not work until Harbor is updated.";
+        column = column.push(
+            row![
+                map_icon(SvgIcon::ShieldAlert, 24., 24.),
+                text(warning).size(16).color(red()).width(Length::Fill)
+            ]
+            .align_y(Alignment::Center)
+            .spacing(8),
+        );
+    }
+
+    column = column.push(version_row(
+        "Consensus".to_string(),
+        versions.consensus_version.clone(),
+    ));
+    for module in &versions.modules {
+        column = column.push(version_row(module.kind.to_string(), module.version.clone()));
+    }
+    column = column.push(client_row);
+
+    if !versions.fresh {
+        column = column.push(
+            text("Couldn't reach the guardians, these are the versions from when you joined.")
+                .size(14)
+                .style(very_subtle),
+        );
+    }
+
+    column.into()
+}
+
+fn gateway_item<'a>(
+    gateway: &'a GatewayInfo,
+    federation_id: FederationId,
+    pinned: bool,
+) -> Element<'a, Message> {
+    let pin_button = if pinned {
+        h_small_button("Unpin", SvgIcon::SmallClose, false)
+            .on_press(Message::PinGateway(federation_id, None))
+    } else {
+        h_small_button("Pin", SvgIcon::SmallCheck, false).on_press(Message::PinGateway(
+            federation_id,
+            Some(gateway.gateway_id.clone()),
+        ))
+    };
+
+    let title = if pinned {
+        format!("{} (pinned)", gateway.alias)
     } else {
-        match harbor.active_route {
-            Route::Mints(MintSubroute::Add) => mints_add(harbor),
-            _ => mints_list(harbor),
+        gateway.alias.clone()
+    };
+
+    let mut features = vec![];
+    if gateway.vetted {
+        features.push("Vetted by the federation");
+    }
+    if gateway.supports_private_payments {
+        features.push("Private payments");
+    }
+
+    let fees = format!(
+        "{} msat + {} ppm",
+        gateway.base_msat, gateway.proportional_millionths
+    );
+
+    column![
+        row![text(title).size(16), horizontal_space(), pin_button].align_y(Alignment::Center),
+        text(fees).size(14).style(subtitle),
+    ]
+    .push_maybe((!features.is_empty()).then(|| text(features.join(", ")).size(14).style(subtitle)))
+    .push(
+        text(format!(
+            "Registered until {}",
+            format_timestamp(&gateway.registered_until)
+        ))
+        .size(14)
+        .style(very_subtle),
+    )
+    .spacing(4)
+    .into()
+}
+
+fn gateway_list<'a>(
+    harbor: &'a HarborWallet,
+    federation_id: &FederationId,
+) -> Element<'a, Message> {
+    let Some((_, gateways)) = harbor
+        .mint_gateways
+        .as_ref()
+        .filter(|(id, _)| id == federation_id)
+    else {
+        return text("Loading gateways...").size(16).style(subtitle).into();
+    };
+
+    if gateways.is_empty() {
+        return text("This federation has no registered gateways.")
+            .size(16)
+            .style(subtitle)
+            .into();
+    }
+
+    let pinned = harbor.mint_pinned_gateway.as_ref();
+    let mut column = column![].spacing(16);
+
+    match pinned {
+        // The pinned gateway went away, say so rather than silently ignoring the pin
+        Some(pinned) if !gateways.iter().any(|g| &g.gateway_id == pinned) => {
+            let notice = "Your pinned gateway is no longer registered, \
+                so Harbor is choosing one automatically.";
+            let unpin_button = h_small_button("Unpin", SvgIcon::SmallClose, false)
+                .on_press(Message::PinGateway(*federation_id, None));
+            column = column
+                .push(column![text(notice).size(14).color(yellow()), unpin_button].spacing(8));
+        }
+        Some(_) => {}
+        None => {
+            column = column.push(
+                text("Harbor picks a gateway automatically unless you pin one.")
+                    .size(14)
+                    .style(very_subtle),
+            );
+        }
+    }
+
+    gateways
+        .iter()
+        .fold(column, |column, gateway| {
+            let is_pinned = pinned == Some(&gateway.gateway_id);
+            column.push(gateway_item(gateway, *federation_id, is_pinned))
+        })
+        .into()
+}
+
+// Where to fetch a published mint list from, and who it has to be signed by
+fn import_url_form(harbor: &HarborWallet) -> Element<Message> {
+    let fetching = harbor.fetching_mint_list.is_some();
+    let can_fetch = !fetching && harbor.is_online() && !harbor.import_url_str.trim().is_empty();
+
+    let url_input = h_input(InputArgs {
+        label: "Mint list URL",
+        placeholder: "https://example.com/mints.json",
+        value: &harbor.import_url_str,
+        on_input: Message::ImportUrlChanged,
+        on_submit: can_fetch.then_some(Message::FetchMintList),
+        disabled: fetching,
+        ..InputArgs::default()
+    });
+    let signer_input = h_input(InputArgs {
+        label: "Signed by (optional)",
+        placeholder: "npub1...",
+        value: &harbor.import_signer_str,
+        on_input: Message::ImportSignerChanged,
+        on_submit: can_fetch.then_some(Message::FetchMintList),
+        disabled: fetching,
+        ..InputArgs::default()
+    });
+    let signer_caption = text(
+        "With a key, the signature published next to the list at the same URL plus .sig has to match it.",
+    )
+    .size(14)
+    .style(subtitle);
+
+    let fetch_button = h_button("Fetch List", SvgIcon::Download, fetching)
+        .on_press_maybe(can_fetch.then_some(Message::FetchMintList));
+    let cancel_button = h_button("Cancel", SvgIcon::SmallClose, false)
+        .on_press_maybe((!fetching).then_some(Message::SetImportUrlOpen(false)));
+
+    column![url_input, signer_input, signer_caption]
+        .push_maybe(offline_caption(harbor))
+        .push(row![fetch_button, cancel_button].spacing(16))
+        .spacing(16)
+        .into()
+}
+
+// What a mint's QR code encodes, the invite code for federations and the url for cashu mints
+fn qr_payload(item: &MintItem) -> Option<String> {
+    match &item.id {
+        MintIdentifier::Fedimint(_) => item.invite_code.as_ref().map(ToString::to_string),
+        MintIdentifier::Cashu(url) => Some(url.to_string()),
+    }
+}
+
+// A new invite code pointing at a guardian that's up, for when the stored one has gone stale
+fn regenerated_invite<'a>(
+    harbor: &'a HarborWallet,
+    item: &MintItem,
+) -> Option<Element<'a, Message>> {
+    let (federation_id, invite_code) = harbor.regenerated_invite.as_ref()?;
+    if item.id.federation_id() != Some(*federation_id) {
+        return None;
+    }
+
+    let guardian = item
+        .guardian_health
+        .iter()
+        .flatten()
+        .find(|g| g.peer_id == invite_code.peer())
+        .map_or_else(|| invite_code.url().to_string(), |g| g.name.clone());
+
+    let qr: Element<Message> = match &harbor.regenerated_invite_qr {
+        Some(data) => container(
+            qr_code(data)
+                .total_size(iced::Pixels(192.))
+                .style(|_theme| iced::widget::qr_code::Style {
+                    background: Color::WHITE,
+                    cell: Color::BLACK,
+                }),
+        )
+        .align_x(Alignment::Center)
+        .width(Length::Fill)
+        .into(),
+        None => text("Generating QR code...")
+            .size(16)
+            .style(subtitle)
+            .into(),
+    };
+
+    Some(
+        column![
+            text(format!("Connects through {guardian}"))
+                .size(14)
+                .style(subtitle),
+            qr,
+            h_copyable(harbor, "", invite_code.to_string())
+        ]
+        .spacing(16)
+        .into(),
+    )
+}
+
+fn mint_qr<'a>(harbor: &'a HarborWallet, item: &MintItem, expanded: bool) -> Element<'a, Message> {
+    let Some(data) = harbor.mint_qr_cache.get(&(item.id.clone(), expanded)) else {
+        return text("Generating QR code...")
+            .size(16)
+            .style(subtitle)
+            .into();
+    };
+
+    let size = if expanded { 512. } else { 192. };
+    let qr = qr_code(data)
+        .total_size(iced::Pixels(size))
+        .style(|_theme| iced::widget::qr_code::Style {
+            background: Color::WHITE,
+            cell: Color::BLACK,
+        });
+
+    container(qr)
+        .align_x(Alignment::Center)
+        .width(Length::Fill)
+        .into()
+}
+
+// Full screen QR with extra error correction so cameras across the room can still read it
+fn mint_qr_expanded<'a>(harbor: &'a HarborWallet, item: &MintItem) -> Element<'a, Message> {
+    let close_button = h_small_button("Close", SvgIcon::SmallClose, false)
+        .on_press(Message::SetMintQrExpanded(false));
+
+    let copy_button = qr_payload(item).map(|payload| {
+        h_small_button("Copy", SvgIcon::Copy, false).on_press(Message::CopyToClipboard(payload))
+    });
+
+    let column = column![
+        close_button,
+        text(item.display_name()).size(32),
+        mint_qr(harbor, item, true)
+    ]
+    .push_maybe(copy_button)
+    .spacing(48);
+
+    basic_layout(harbor, column)
+}
+
+// What's about to be saved, so nothing leaves the machine without the user seeing it first
+fn diagnostics_preview<'a>(
+    harbor: &'a HarborWallet,
+    item: &MintItem,
+    diagnostics: &MintDiagnostics,
+) -> Element<'a, Message> {
+    let cancel_button = h_small_button("Cancel", SvgIcon::SmallClose, false)
+        .on_press(Message::CancelMintDiagnostics);
+
+    let header = column![
+        text(format!("Diagnostics for {}", item.display_name())).size(32),
+        text("Share this with the mint's operators when reporting a problem.")
+            .size(18)
+            .style(subtitle)
+    ]
+    .spacing(8);
+
+    let included = diagnostics
+        .summary()
+        .into_iter()
+        .fold(column![], |column, line| {
+            column.push(text(format!("• {line}")).size(16))
+        })
+        .spacing(8);
+    let excluded = text(
+        "Never included: seed words, ecash notes or tokens, invoices, preimages, or \
+        transaction IDs.",
+    )
+    .size(14)
+    .style(subtitle);
+
+    let json = diagnostics
+        .to_json()
+        .unwrap_or_else(|e| format!("Could not show the bundle: {e}"));
+    let json = container(text(json).size(12).font(Font::MONOSPACE))
+        .padding(16)
+        .width(Length::Fill)
+        .style(light_container_style);
+
+    let save_button = h_button("Save Diagnostics", SvgIcon::Download, false)
+        .on_press(Message::SaveMintDiagnostics);
+
+    let column = column![
+        cancel_button,
+        header,
+        column![included, excluded].spacing(16),
+        json,
+        save_button
+    ]
+    .spacing(48);
+
+    basic_layout(harbor, column)
+}
+
+fn session_info<'a>(
+    harbor: &'a HarborWallet,
+    federation_id: &FederationId,
+) -> Element<'a, Message> {
+    let Some(progress) = harbor.session_progress.get(federation_id) else {
+        return text("Checking the federation's progress...")
+            .size(16)
+            .style(subtitle)
+            .into();
+    };
+
+    let last_progress = match progress.last_progress_at {
+        Some(at) => format!("Last new session seen {}", format_timestamp(&at)),
+        None => format!(
+            "No new session seen since {}",
+            format_timestamp(&progress.watching_since)
+        ),
+    };
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    let stale_after = harbor.config.stale_after;
+
+    let mut column = column![
+        text(format!("Session {}", progress.session_count)).size(16),
+        text(last_progress).size(14).style(subtitle)
+    ]
+    .spacing(8);
+
+    if progress.is_stale(now, stale_after.secs()) {
+        column = column.push(
+            row![
+                map_icon(SvgIcon::ShieldAlert, 24., 24.),
+                text(format!(
+                    "No progress in over {stale_after}, the federation may be stalled."
+                ))
+                .size(14)
+                .color(yellow())
+                .width(Length::Fill)
+            ]
+            .align_y(Alignment::Center)
+            .spacing(8),
+        );
+    }
+
+    column.into()
+}
+
+fn mint_details<'a>(harbor: &'a HarborWallet, id: &MintIdentifier) -> Element<'a, Message> {
+    // Back to wherever the details were opened from, the list if that's not known
+    let back = if harbor.route_history.is_empty() {
+        Message::Navigate(Route::Mints(MintSubroute::List))
+    } else {
+        Message::NavigateBack
+    };
+    let back_button = h_small_button("Back", SvgIcon::ArrowLeft, false).on_press(back);
+
+    // The mint can disappear from under us, e.g. after it's archived
+    let Some(item) = harbor.mint_list.iter().find(|m| &m.id == id) else {
+        let column = column![
+            back_button,
+            text("This mint is no longer in your list.")
+                .size(18)
+                .style(subtitle)
+        ]
+        .spacing(48);
+        return basic_layout(harbor, column);
+    };
+
+    if harbor.mint_qr_expanded {
+        return mint_qr_expanded(harbor, item);
+    }
+
+    if let Some((_, diagnostics)) = harbor
+        .mint_diagnostics
+        .as_ref()
+        .filter(|(mint, _)| mint == &item.id)
+    {
+        return diagnostics_preview(harbor, item, diagnostics);
+    }
+
+    let header_subtitle = match (&item.nickname, &item.id) {
+        (Some(_), _) => format!("Announced as {}", item.name),
+        (None, MintIdentifier::Fedimint(_)) => "Fedimint federation".to_string(),
+        (None, MintIdentifier::Cashu(_)) => "Cashu mint".to_string(),
+    };
+    let header = row![
+        mint_icon(item, 48.),
+        column![
+            text(item.display_name()).size(32),
+            text(header_subtitle).size(18).style(subtitle)
+        ]
+        .spacing(8)
+    ]
+    .align_y(Alignment::Center)
+    .spacing(16);
+
+    let mut balance = if item.syncing {
+        format!("{} + syncing", format_amount(item.balance))
+    } else {
+        format_amount(item.balance)
+    };
+    if let Some(fiat) = harbor.fiat_value(item.balance) {
+        balance = format!("{balance} ({fiat})");
+    }
+
+    let balance_row = row![text(balance).size(16), balance_refresh(item, harbor)]
+        .align_y(Alignment::Center)
+        .spacing(16);
+    let progress = item
+        .id
+        .federation_id()
+        .and_then(|id| harbor.mint_recovery.get(&id))
+        .and_then(recovery_progress);
+    let balance: Element<Message> = column![balance_row]
+        .push_maybe(pending_caption(item.pending))
+        .push_maybe(progress)
+        .spacing(8)
+        .into();
+
+    let mut details = column![
+        detail_section("Balance", balance),
+        detail_section("Network", text(harbor.config.network.to_string()).size(16)),
+    ]
+    .spacing(16);
+
+    match &item.id {
+        MintIdentifier::Fedimint(federation_id) => {
+            details = details.push(h_copyable(
+                harbor,
+                "Federation ID",
+                federation_id.to_string(),
+            ));
+            if let Some(invite_code) = &item.invite_code {
+                details = details.push(h_copyable(harbor, "Invite code", invite_code.to_string()));
+            }
+        }
+        MintIdentifier::Cashu(mint_url) => {
+            details = details.push(detail_section(
+                "Mint URL",
+                row![
+                    text_link(mint_url.to_string(), mint_url.to_string()),
+                    horizontal_space(),
+                    mini_copy(mint_url.to_string())
+                ]
+                .align_y(Alignment::Center)
+                .spacing(8),
+            ));
         }
     }
+
+    if let Some(site) = item.metadata.site_url() {
+        details = details.push(detail_section(
+            "Website",
+            text_link(site.to_string(), site.to_string()),
+        ));
+    }
+
+    if let Some(icon_url) = &item.metadata.federation_icon_url {
+        details = details.push(detail_section(
+            "Icon source",
+            text_link(icon_url.clone(), icon_url.clone()),
+        ));
+    }
+
+    if let Some(welcome) = &item.metadata.welcome_message {
+        details = details.push(detail_section("Welcome message", text(welcome).size(16)));
+    }
+
+    if let Some(payload) = qr_payload(item) {
+        let copy_button = h_small_button("Copy", SvgIcon::Copy, false)
+            .on_press(Message::CopyToClipboard(payload));
+        let expand_button =
+            h_small_button("Expand", SvgIcon::Qr, false).on_press(Message::SetMintQrExpanded(true));
+        let save_button = h_small_button("Save Page", SvgIcon::Download, false)
+            .on_press(Message::SaveShareBundle(item.id.clone()));
+        let regenerate_button = item.id.federation_id().map(|federation_id| {
+            h_small_button("Regenerate", SvgIcon::Restart, harbor.regenerating_invite)
+                .on_press_maybe(
+                    (!harbor.regenerating_invite)
+                        .then_some(Message::RegenerateInviteCode(federation_id)),
+                )
+        });
+        details = details.push(detail_section(
+            "Share",
+            column![
+                mint_qr(harbor, item, false),
+                row![copy_button, expand_button, save_button]
+                    .push_maybe(regenerate_button)
+                    .spacing(8)
+            ]
+            .spacing(16),
+        ));
+    }
+
+    if let Some(regenerated) = regenerated_invite(harbor, item) {
+        details = details.push(detail_section("New invite code", regenerated));
+    }
+
+    if let Some(guardians) = guardian_list(item) {
+        let guardians = match &item.id {
+            MintIdentifier::Fedimint(federation_id) => {
+                let pinging = harbor.pinging_guardians == Some(*federation_id);
+                let ping_button = h_small_button("Ping all", SvgIcon::Restart, pinging)
+                    .on_press_maybe(
+                        (!pinging && harbor.is_online())
+                            .then_some(Message::PingGuardians(*federation_id)),
+                    );
+                column![guardians, ping_button].spacing(16).into()
+            }
+            MintIdentifier::Cashu(_) => guardians,
+        };
+        details = details.push(detail_section("Guardians", guardians));
+    }
+
+    if let Some(modules) = item.module_kinds.as_ref().filter(|m| !m.is_empty()) {
+        let modules = modules
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        details = details.push(detail_section("Modules", text(modules).size(16)));
+    }
+
+    if let MintIdentifier::Fedimint(federation_id) = &item.id {
+        details = details.push(detail_section(
+            "Lightning gateways",
+            gateway_list(harbor, federation_id),
+        ));
+        details = details.push(detail_section(
+            "Versions",
+            version_info(harbor, federation_id),
+        ));
+        details = details.push(detail_section(
+            "Consensus progress",
+            session_info(harbor, federation_id),
+        ));
+    }
+
+    details = details.push(detail_section(
+        "Fee schedule",
+        fee_schedule(harbor, &item.id),
+    ));
+    details = details.push(detail_section("Activity", mint_activity(harbor, &item.id)));
+    details = details.push(detail_section("Fees paid", fees_paid(harbor, &item.id)));
+
+    let joined = item
+        .joined_at
+        .as_ref()
+        .map_or_else(|| "Unknown".to_string(), format_timestamp);
+    details = details.push(detail_section("Joined", text(joined).size(16)));
+
+    let notes_input = h_input(InputArgs {
+        label: "Notes",
+        placeholder: "Anything you want to remember about this mint",
+        value: &harbor.mint_notes_input,
+        on_input: Message::MintNotesChanged,
+        on_submit: Some(Message::SaveMintNotes),
+        ..InputArgs::default()
+    });
+    let max_balance_hint = item.metadata.max_balance().map_or_else(
+        || "Leave empty for no limit".to_string(),
+        |max| {
+            format!(
+                "This mint recommends at most {}, leave empty to use that",
+                format_amount(max.sats_round_down())
+            )
+        },
+    );
+    let max_balance_input = h_input(InputArgs {
+        label: "Max balance",
+        placeholder: "No limit of your own",
+        value: &harbor.mint_max_balance_input,
+        on_input: Message::MintMaxBalanceChanged,
+        on_submit: Some(Message::SaveMintMaxBalance),
+        numeric: true,
+        suffix: Some("sats"),
+        ..InputArgs::default()
+    });
+    let max_balance = column![
+        max_balance_input,
+        row![
+            text(max_balance_hint).size(14).style(very_subtle),
+            horizontal_space(),
+            h_small_button("Save", SvgIcon::SmallCheck, false)
+                .on_press(Message::SaveMintMaxBalance),
+        ]
+        .align_y(Alignment::Center),
+    ]
+    .push_maybe(max_balance_banner(item))
+    .spacing(8);
+
+    let notes = column![
+        notes_input,
+        text(format!(
+            "{}/{MAX_MINT_NOTES_LEN}",
+            harbor.mint_notes_input.len()
+        ))
+        .size(14)
+        .style(very_subtle)
+    ]
+    .spacing(8);
+
+    let actions: Element<Message> = if harbor.renaming_mint.as_ref() == Some(&item.id) {
+        rename_form(harbor)
+    } else {
+        let rename_button = h_small_button("Rename", SvgIcon::Pencil, false)
+            .on_press(Message::StartRenameMint(item.id.clone()));
+        let history_button = h_small_button("History", SvgIcon::Clock, false)
+            .on_press(Message::ViewMintHistory(item.id.clone()));
+        let resync = harbor.mint_resyncs.get(&item.id);
+        let resyncing = resync.is_some_and(|(_, running)| *running);
+        let resync_button = h_small_button("Resync", SvgIcon::Restart, resyncing).on_press_maybe(
+            (!resyncing && harbor.is_online()).then(|| Message::ResyncMint(item.id.clone())),
+        );
+        let diagnostics_button = h_small_button(
+            "Export diagnostics",
+            SvgIcon::Download,
+            harbor.gathering_diagnostics,
+        )
+        .on_press_maybe(
+            (!harbor.gathering_diagnostics)
+                .then(|| Message::GatherMintDiagnostics(item.id.clone())),
+        );
+        let archive_button =
+            h_small_button("Archive", SvgIcon::Trash, false).on_press(confirm_archive(item));
+
+        let buttons = row![
+            rename_button,
+            history_button,
+            resync_button,
+            diagnostics_button,
+            archive_button
+        ]
+        .spacing(8);
+        column![buttons]
+            .push_maybe(offline_caption(harbor))
+            .push_maybe(resync.and_then(|(id, _)| operation_status_for_id(harbor, Some(*id))))
+            .spacing(16)
+            .into()
+    };
+
+    let resync_id = harbor.mint_resyncs.get(id).map(|(resync_id, _)| *resync_id);
+    let column = column![back_button, header]
+        .push_maybe(health_banner(item, harbor))
+        .push_maybe(operation_status_summary_excluding(
+            harbor,
+            resync_id.as_slice(),
+        ))
+        .push(container(details).padding(16).style(light_container_style))
+        .push(notes)
+        .push(max_balance)
+        .push(actions)
+        .spacing(48);
+
+    basic_layout(harbor, column)
+}
+
+/// Which screen the Mints route shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MintsView {
+    List,
+    /// Typing or pasting an invite code
+    Add,
+    /// Looking over a mint before joining it
+    AddPreview,
+    AddJoining,
+    Details(MintIdentifier),
+}
+
+// With no mints at all there's nothing to list, but archived mints still get the list so
+// they can be rejoined
+fn mints_view(harbor: &HarborWallet) -> MintsView {
+    let adding = match &harbor.active_route {
+        _ if harbor.mint_list.is_empty() => true,
+        Route::Mints(MintSubroute::Add) => true,
+        Route::Mints(MintSubroute::Details(id)) => return MintsView::Details(id.clone()),
+        _ => false,
+    };
+    if !adding {
+        MintsView::List
+    } else if harbor.peek_federation_item.is_none() {
+        MintsView::Add
+    } else if harbor.add_federation_status == AddFederationStatus::Adding {
+        MintsView::AddJoining
+    } else {
+        MintsView::AddPreview
+    }
+}
+
+pub fn mints(harbor: &HarborWallet) -> Element<Message> {
+    match mints_view(harbor) {
+        MintsView::List => mints_list(harbor),
+        MintsView::Add | MintsView::AddPreview | MintsView::AddJoining => mints_add(harbor),
+        MintsView::Details(id) => mint_details(harbor, &id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use harbor_client::cdk::mint_url::MintUrl;
+    use std::str::FromStr;
+
+    fn wallet(mints: Vec<MintItem>, route: Route) -> HarborWallet {
+        HarborWallet {
+            mint_list: mints,
+            active_route: route,
+            ..HarborWallet::default()
+        }
+    }
+
+    fn mint(active: bool) -> MintItem {
+        MintItem {
+            active,
+            ..MintItem::unknown(FederationId::dummy())
+        }
+    }
+
+    #[test]
+    fn test_mints_view() {
+        let list = Route::Mints(MintSubroute::List);
+        let add = Route::Mints(MintSubroute::Add);
+
+        // With nothing joined, adding one is all there is to do
+        assert_eq!(mints_view(&wallet(vec![], list.clone())), MintsView::Add);
+
+        // Archived mints are still listed so they can be rejoined
+        let archived = wallet(vec![mint(false)], list.clone());
+        assert_eq!(mints_view(&archived), MintsView::List);
+
+        let both = vec![
+            mint(true),
+            MintItem {
+                id: MintIdentifier::Cashu(MintUrl::from_str("https://mint.example.com").unwrap()),
+                ..mint(false)
+            },
+        ];
+        assert_eq!(mints_view(&wallet(both.clone(), list)), MintsView::List);
+        assert_eq!(
+            mints_view(&wallet(both.clone(), add.clone())),
+            MintsView::Add
+        );
+
+        // An archived mint's details can be opened too
+        let id = MintIdentifier::Fedimint(FederationId::dummy());
+        let details = wallet(
+            vec![mint(false)],
+            Route::Mints(MintSubroute::Details(id.clone())),
+        );
+        assert_eq!(mints_view(&details), MintsView::Details(id));
+
+        // The add screen follows the preview and the join
+        let mut adding = wallet(both, add);
+        adding.peek_federation_item = Some(mint(true));
+        assert_eq!(mints_view(&adding), MintsView::AddPreview);
+        adding.add_federation_status = AddFederationStatus::Adding;
+        assert_eq!(mints_view(&adding), MintsView::AddJoining);
+    }
 }
@@ -1,3 +1,5 @@
+use harbor_client::MintIdentifier;
+
 pub mod home;
 pub use home::*;
 
@@ -25,20 +27,24 @@ pub use history::*;
 pub mod settings;
 pub use settings::*;
 
+pub mod activity_log;
+pub use activity_log::*;
+
 pub mod welcome;
 pub use welcome::*;
 
 pub mod restore;
 pub use restore::*;
 
-#[derive(Default, PartialEq, Debug, Clone, Copy)]
+#[derive(Default, PartialEq, Debug, Clone)]
 pub enum MintSubroute {
     #[default]
     List,
     Add,
+    Details(MintIdentifier),
 }
 
-#[derive(Default, PartialEq, Debug, Clone, Copy)]
+#[derive(Default, PartialEq, Debug, Clone)]
 pub enum Route {
     #[default]
     Welcome,
@@ -49,7 +55,41 @@ pub enum Route {
     Transfer,
     History,
     Settings,
+    ActivityLog,
     Receive,
     Send,
     Donate,
 }
+
+impl Route {
+    /// Names the scrollable for this screen. It's the same on every redraw so iced keeps the
+    /// offset, and different for each mint's details so one mint's offset isn't another's.
+    pub fn scroll_key(&self) -> String {
+        format!("layout-{self:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use harbor_client::fedimint_core::config::FederationId;
+
+    #[test]
+    fn test_scroll_key() {
+        // Rebuilding a screen, like when a mint is added to the list, keeps its scrollable
+        let list = Route::Mints(MintSubroute::List);
+        assert_eq!(
+            list.scroll_key(),
+            Route::Mints(MintSubroute::List).scroll_key()
+        );
+        assert_ne!(
+            list.scroll_key(),
+            Route::Mints(MintSubroute::Add).scroll_key()
+        );
+
+        let details = Route::Mints(MintSubroute::Details(MintIdentifier::Fedimint(
+            FederationId::dummy(),
+        )));
+        assert_ne!(details.scroll_key(), list.scroll_key());
+    }
+}
@@ -1,11 +1,14 @@
 use crate::components::{
-    InputArgs, SvgIcon, basic_layout, font_mono, h_button, h_caption_text, h_header, h_input,
-    h_screen_header, h_small_button, operation_status_for_id,
+    InputArgs, SvgIcon, basic_layout, chip_style, explain_disabled, font_mono, h_button,
+    h_caption_text, h_header, h_input, h_mint_picker, h_screen_header, h_small_button, map_icon,
+    max_balance_banner, mint_choice, operation_status_for_id,
 };
 use crate::{HarborWallet, Message, ReceiveMethod, ReceiveStatus};
+use harbor_client::MintIdentifier;
+use harbor_client::db_models::MintItem;
 use iced::widget::container::Style;
-use iced::widget::{column, container, horizontal_space, qr_code, radio, row, text};
-use iced::{Border, Element};
+use iced::widget::{button, column, container, horizontal_space, qr_code, radio, row, text};
+use iced::{Alignment, Border, Element, Padding};
 use iced::{Color, Length};
 
 /// Main view function.
@@ -22,18 +25,53 @@ pub fn receive(harbor: &HarborWallet) -> Element<Message> {
     }
 }
 
+/// Why `item` can't take a receive by `method`, if it can't.
+pub fn receive_unsupported(
+    item: &MintItem,
+    method: ReceiveMethod,
+    onchain_receive_enabled: bool,
+) -> Option<&'static str> {
+    let capabilities = item.capabilities();
+    match (method, &item.id) {
+        (ReceiveMethod::Lightning, _) => {
+            (!capabilities.lightning).then_some("This federation doesn't have the lightning module")
+        }
+        (ReceiveMethod::OnChain, MintIdentifier::Cashu(_)) => {
+            Some("Cashu mints can't take on-chain deposits")
+        }
+        (ReceiveMethod::OnChain, MintIdentifier::Fedimint(_)) => {
+            if !capabilities.on_chain {
+                Some("This federation doesn't have the on-chain module")
+            } else if !(onchain_receive_enabled || item.on_chain_supported) {
+                Some("On-chain receive for this federation can be turned on in Settings")
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// On-chain is offered as long as one of the mints can take it
+fn offers_on_chain(harbor: &HarborWallet) -> bool {
+    harbor.mint_list.iter().any(|item| {
+        item.active
+            && receive_unsupported(item, ReceiveMethod::OnChain, harbor.onchain_receive_enabled)
+                .is_none()
+    })
+}
+
+/// The method the receive form is showing, which is lightning when on-chain isn't offered
+pub fn receive_form_method(harbor: &HarborWallet) -> ReceiveMethod {
+    if offers_on_chain(harbor) {
+        harbor.receive_method
+    } else {
+        ReceiveMethod::Lightning
+    }
+}
+
 /// Renders the view before an invoice/address is generated.
 fn render_receive_form(harbor: &HarborWallet) -> Element<Message> {
-    // for on-chain to be shown, it needs to be a federation and either
-    // the user turned on on-chain receive or the federation supports it
-    let on_chain_enabled = harbor
-        .active_mint
-        .as_ref()
-        .is_some_and(|a| a.federation_id().is_some())
-        && (harbor.onchain_receive_enabled
-            || harbor
-                .active_federation()
-                .is_some_and(|x| x.on_chain_supported));
+    let on_chain_enabled = offers_on_chain(harbor);
 
     let header = if on_chain_enabled {
         h_header("Deposit", "Receive on-chain or via lightning.")
@@ -44,24 +82,90 @@ fn render_receive_form(harbor: &HarborWallet) -> Element<Message> {
     let content = if on_chain_enabled {
         let method_choice = render_method_choice(harbor);
         match harbor.receive_method {
-            ReceiveMethod::Lightning => {
-                column![header, method_choice, render_lightning_view(harbor)]
-            }
-            ReceiveMethod::OnChain => {
-                column![header, method_choice, render_onchain_view(harbor)]
-            }
+            ReceiveMethod::Lightning => column![
+                header,
+                method_choice,
+                render_mint_picker(harbor),
+                render_lightning_view(harbor)
+            ],
+            ReceiveMethod::OnChain => column![
+                header,
+                method_choice,
+                render_mint_picker(harbor),
+                render_onchain_view(harbor)
+            ],
         }
     } else {
-        column![header, render_lightning_view(harbor)]
+        column![
+            header,
+            render_mint_picker(harbor),
+            render_lightning_view(harbor)
+        ]
     };
 
+    // The form has its own mint picker, the header's switcher would be a second one
     column![
-        h_screen_header(harbor, true, false),
-        basic_layout(content.spacing(48))
+        h_screen_header(harbor, true, true),
+        basic_layout(harbor, content.spacing(48))
     ]
     .into()
 }
 
+/// Why the selected mint can't take this receive, if it can't
+fn selected_mint_unsupported(harbor: &HarborWallet, method: ReceiveMethod) -> Option<&'static str> {
+    match harbor.active_federation() {
+        Some(item) => receive_unsupported(item, method, harbor.onchain_receive_enabled),
+        None => Some("Choose a mint to receive into"),
+    }
+}
+
+/// Renders the choice of mint the funds land in. Mints that can't take the current method stay
+/// in the list, greyed out with the reason.
+fn render_mint_picker(harbor: &HarborWallet) -> Element<Message> {
+    let generating = harbor.receive_status == ReceiveStatus::Generating;
+    let open = harbor.receive_mint_picker_open && !generating;
+
+    let selected: Element<Message> = harbor
+        .active_federation()
+        .map_or_else(|| text("Choose a mint").size(16).into(), mint_choice);
+    let chevron = if open {
+        SvgIcon::ChevronDown
+    } else {
+        SvgIcon::ChevronRight
+    };
+    let mut toggle = button(
+        row![
+            container(selected).width(Length::Fill),
+            map_icon(chevron, 24., 24.)
+        ]
+        .spacing(16)
+        .align_y(Alignment::Center),
+    )
+    .width(Length::Fill)
+    .padding(Padding::from(16))
+    .style(chip_style);
+    if !generating {
+        toggle = toggle.on_press(Message::SetReceiveMintPickerOpen(!open));
+    }
+
+    let choices = open.then(|| {
+        h_mint_picker(
+            harbor,
+            harbor.mint_picker_choices(),
+            harbor.active_mint.as_ref(),
+            Message::ReceiveMintChanged,
+        )
+    });
+
+    let over_max = harbor.active_federation().and_then(max_balance_banner);
+
+    column![text("Receive to").size(24), toggle]
+        .push_maybe(over_max)
+        .push_maybe(choices)
+        .spacing(16)
+        .into()
+}
+
 /// Renders the Lightning view including the amount input.
 fn render_lightning_view(harbor: &HarborWallet) -> Element<Message> {
     let generating = harbor.receive_status == ReceiveStatus::Generating;
@@ -78,10 +182,12 @@ fn render_lightning_view(harbor: &HarborWallet) -> Element<Message> {
     });
 
     // Create the "Generate Invoice" button.
+    let unsupported = selected_mint_unsupported(harbor, ReceiveMethod::Lightning);
     let mut generate_invoice_button = h_button("Generate Invoice", SvgIcon::Qr, generating);
-    if !harbor.receive_amount_str.is_empty() {
+    if !harbor.receive_amount_str.is_empty() && unsupported.is_none() {
         generate_invoice_button = generate_invoice_button.on_press(Message::GenerateInvoice);
     }
+    let generate_invoice_button = explain_disabled(generate_invoice_button, unsupported);
 
     let buttons = if generating {
         // When generating, include a "Start Over" next to the generate button.
@@ -108,8 +214,12 @@ fn render_onchain_view(harbor: &HarborWallet) -> Element<Message> {
     let generating = harbor.receive_status == ReceiveStatus::Generating;
 
     // Create the "Generate Address" button.
-    let generate_address_button =
-        h_button("Generate Address", SvgIcon::Qr, generating).on_press(Message::GenerateAddress);
+    let unsupported = selected_mint_unsupported(harbor, ReceiveMethod::OnChain);
+    let mut generate_address_button = h_button("Generate Address", SvgIcon::Qr, generating);
+    if unsupported.is_none() {
+        generate_address_button = generate_address_button.on_press(Message::GenerateAddress);
+    }
+    let generate_address_button = explain_disabled(generate_address_button, unsupported);
 
     let buttons = if generating {
         let start_over_button = h_button("Start Over", SvgIcon::Restart, false)
@@ -173,18 +283,20 @@ fn render_generated_view(receive_string: String, harbor: &HarborWallet) -> Eleme
         "On-chain Address"
     };
 
-    let data = harbor
-        .receive_qr_data
-        .as_ref()
-        .expect("QR data should be present");
-
-    // TODO: update iced so we can set the size of the qr code
-    let qr = qr_code(data)
-        .total_size(iced::Pixels(256.))
-        .style(|_theme| iced::widget::qr_code::Style {
-            background: Color::WHITE,
-            cell: Color::BLACK,
-        });
+    // The QR is encoded in the background, so hold its space until it's ready
+    let qr: Element<Message> = match harbor.receive_qr_data.as_ref() {
+        // TODO: update iced so we can set the size of the qr code
+        Some(data) => qr_code(data)
+            .total_size(iced::Pixels(256.))
+            .style(|_theme| iced::widget::qr_code::Style {
+                background: Color::WHITE,
+                cell: Color::BLACK,
+            })
+            .into(),
+        None => container(text("Generating QR code...").size(16).color(Color::BLACK))
+            .center(iced::Pixels(256.))
+            .into(),
+    };
     let qr_container = container(qr)
         .align_x(iced::Alignment::Center)
         .width(iced::Length::Fill);
@@ -245,7 +357,62 @@ fn render_generated_view(receive_string: String, harbor: &HarborWallet) -> Eleme
     column![
         // Disable the network switcher once we have an invoice or address
         h_screen_header(harbor, true, true),
-        basic_layout(content.spacing(48))
+        basic_layout(harbor, content.spacing(48))
     ]
     .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use harbor_client::cdk::mint_url::MintUrl;
+    use harbor_client::fedimint_core::config::FederationId;
+    use harbor_client::fedimint_core::core::ModuleKind;
+    use std::str::FromStr;
+
+    fn federation(modules: Option<&[&'static str]>) -> MintItem {
+        MintItem {
+            module_kinds: modules.map(|kinds| {
+                kinds
+                    .iter()
+                    .copied()
+                    .map(ModuleKind::from_static_str)
+                    .collect()
+            }),
+            ..MintItem::unknown(FederationId::dummy())
+        }
+    }
+
+    #[test]
+    fn test_receive_unsupported() {
+        use ReceiveMethod::{Lightning, OnChain};
+
+        let everything = federation(Some(&["mint", "ln", "wallet"]));
+        assert_eq!(receive_unsupported(&everything, Lightning, true), None);
+        assert_eq!(receive_unsupported(&everything, OnChain, true), None);
+
+        // On-chain can be switched off in Settings, unless the federation is allowed it anyway
+        assert!(receive_unsupported(&everything, OnChain, false).is_some());
+        let allowed = MintItem {
+            on_chain_supported: true,
+            ..everything
+        };
+        assert_eq!(receive_unsupported(&allowed, OnChain, false), None);
+
+        let ecash_only = federation(Some(&["mint"]));
+        assert!(receive_unsupported(&ecash_only, Lightning, true).is_some());
+        assert!(receive_unsupported(&ecash_only, OnChain, true).is_some());
+
+        // One whose modules aren't known yet gets the benefit of the doubt
+        let unknown = federation(None);
+        assert_eq!(receive_unsupported(&unknown, Lightning, true), None);
+        assert_eq!(receive_unsupported(&unknown, OnChain, true), None);
+
+        let cashu = MintItem {
+            id: MintIdentifier::Cashu(MintUrl::from_str("https://mint.example.com").unwrap()),
+            ..federation(None)
+        };
+        assert_eq!(receive_unsupported(&cashu, Lightning, true), None);
+        assert!(receive_unsupported(&cashu, OnChain, true).is_some());
+    }
+}
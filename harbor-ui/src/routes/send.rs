@@ -1,19 +1,201 @@
 use std::str::FromStr;
 
-use iced::Element;
-use iced::widget::{column, row};
+use iced::widget::{button, column, container, row, text};
+use iced::{Alignment, Element, Length, Padding};
 
-use harbor_client::Bolt11Invoice;
 use harbor_client::bitcoin::Address;
 use harbor_client::bitcoin::address::NetworkUnchecked;
+use harbor_client::db_models::{MintItem, SendFeeEstimate};
 use harbor_client::lightning_address::parse_lnurl;
+use harbor_client::{Bolt11Invoice, MintIdentifier};
 
 use crate::components::{
-    ConfirmModalState, InputArgs, SvgIcon, basic_layout, h_button, h_checkbox, h_header, h_input,
-    h_screen_header, operation_status_for_id,
+    ConfirmModalState, InputArgs, SvgIcon, basic_layout, chip_style, explain_disabled,
+    format_amount, green, h_button, h_caption_text, h_checkbox, h_header, h_input, h_mint_picker,
+    h_screen_header, map_icon, mint_choice, operation_status_for_id, very_subtle,
 };
 use crate::{HarborWallet, Message, SendDestination, SendStatus};
 
+fn mint_name(harbor: &HarborWallet, mint: &MintIdentifier) -> String {
+    harbor
+        .mint_list
+        .iter()
+        .find(|item| &item.id == mint)
+        .map_or_else(
+            || match mint {
+                MintIdentifier::Fedimint(id) => id.to_string().chars().take(8).collect(),
+                MintIdentifier::Cashu(url) => url.to_string(),
+            },
+            |item| item.display_name().to_string(),
+        )
+}
+
+// Why `item` can't pay `destination`, if it can't. Nothing is ruled out before there's a
+// destination to go on.
+pub fn send_unsupported(
+    item: &MintItem,
+    destination: Option<&SendDestination>,
+) -> Option<&'static str> {
+    let capabilities = item.capabilities();
+    match destination? {
+        SendDestination::Invoice(_) | SendDestination::LnUrl(_) => (!capabilities.lightning)
+            .then_some("This mint can't pay lightning, it has no lightning module"),
+        SendDestination::Address(_) => (!capabilities.on_chain)
+            .then_some("This mint can't send on-chain, it has no on-chain module"),
+    }
+}
+
+fn auto_choice() -> Element<'static, Message> {
+    row![
+        map_icon(SvgIcon::Bolt, 16., 16.),
+        text("Auto (best fee)").size(16)
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Renders the choice of mint to pay from, with an option to let the invoice's fees decide.
+fn render_mint_picker(harbor: &HarborWallet) -> Element<Message> {
+    let busy =
+        harbor.send_status == SendStatus::Sending || harbor.current_fee_estimate_id.is_some();
+    let open = harbor.send_mint_picker_open && !busy;
+
+    let selected: Element<Message> = if harbor.send_auto_mint {
+        auto_choice()
+    } else {
+        harbor
+            .active_federation()
+            .map_or_else(|| text("Choose a mint").size(16).into(), mint_choice)
+    };
+    let chevron = if open {
+        SvgIcon::ChevronDown
+    } else {
+        SvgIcon::ChevronRight
+    };
+    let mut toggle = button(
+        row![
+            container(selected).width(Length::Fill),
+            map_icon(chevron, 24., 24.)
+        ]
+        .spacing(16)
+        .align_y(Alignment::Center),
+    )
+    .width(Length::Fill)
+    .padding(Padding::from(16))
+    .style(chip_style);
+    if !busy {
+        toggle = toggle.on_press(Message::SetSendMintPickerOpen(!open));
+    }
+
+    let choices = open.then(|| {
+        let auto = button(auto_choice())
+            .width(Length::Fill)
+            .padding(Padding::from(16))
+            .style(chip_style)
+            .on_press(Message::SendMintChanged(None));
+        let mints = h_mint_picker(
+            harbor,
+            harbor.mint_picker_choices(),
+            harbor
+                .active_mint
+                .as_ref()
+                .filter(|_| !harbor.send_auto_mint),
+            |mint| Message::SendMintChanged(Some(mint)),
+        );
+        column![auto, mints].spacing(8)
+    });
+
+    column![text("Pay from").size(24), toggle]
+        .push_maybe(choices)
+        .spacing(16)
+        .into()
+}
+
+// Fees are shown rounded up so the estimate is never lower than what gets paid
+fn format_fee(estimate: &SendFeeEstimate) -> String {
+    match &estimate.fee {
+        Ok(fee) => format_amount(fee.msats.div_ceil(1_000)),
+        Err(_) => "unknown".to_string(),
+    }
+}
+
+/// Renders the fee each mint quoted for the invoice and a button to pay from the cheapest.
+fn render_fee_comparison<'a>(
+    harbor: &'a HarborWallet,
+    estimates: &'a [SendFeeEstimate],
+) -> Element<'a, Message> {
+    let cheapest = SendFeeEstimate::cheapest(estimates);
+
+    let mut usable: Vec<&SendFeeEstimate> = estimates
+        .iter()
+        .filter(|estimate| estimate.sufficient_balance && estimate.fee.is_ok())
+        .collect();
+    usable.sort_by_key(|estimate| estimate.fee.as_ref().ok().copied());
+    let comparison = usable
+        .iter()
+        .map(|estimate| {
+            format!(
+                "{}: {}",
+                mint_name(harbor, &estimate.mint),
+                format_fee(estimate)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut content = column![text("Fees").size(24)].spacing(16);
+    if !comparison.is_empty() {
+        content = content.push(text(format!("via {comparison}")).size(16));
+    }
+
+    for estimate in estimates
+        .iter()
+        .filter(|estimate| !estimate.sufficient_balance || estimate.fee.is_err())
+    {
+        let why = match &estimate.fee {
+            Err(e) => format!("couldn't get a fee ({e})"),
+            Ok(_) => format!("not enough balance to cover {}", format_fee(estimate)),
+        };
+        content = content.push(
+            text(format!(
+                "Skipped {}: {why}",
+                mint_name(harbor, &estimate.mint)
+            ))
+            .size(14)
+            .style(very_subtle),
+        );
+    }
+
+    let confirm = match cheapest {
+        Some(best) => {
+            let pay = h_button(
+                "Pay",
+                SvgIcon::UpRight,
+                harbor.send_status == SendStatus::Sending,
+            );
+            column![
+                text(format!(
+                    "Paying via {} for {}",
+                    mint_name(harbor, &best.mint),
+                    format_fee(best)
+                ))
+                .size(16)
+                .color(green()),
+                pay.on_press_maybe(
+                    (harbor.send_status != SendStatus::Sending).then_some(Message::SendWithBestFee)
+                )
+            ]
+            .spacing(16)
+        }
+        None => column![h_caption_text(
+            "None of your mints can pay this invoice right now."
+        )],
+    };
+
+    content.push(confirm).into()
+}
+
 pub fn send(harbor: &HarborWallet) -> Element<Message> {
     let header = h_header("Send", "Send to an on-chain address or lightning invoice.");
 
@@ -36,12 +218,39 @@ pub fn send(harbor: &HarborWallet) -> Element<Message> {
         ..InputArgs::default()
     });
 
+    let destination = parse_send_destination(&harbor.send_dest_input_str);
+    let send_blocked = if harbor.send_auto_mint {
+        match &destination {
+            Some(SendDestination::Invoice(invoice))
+                if invoice.amount_milli_satoshis().is_none() =>
+            {
+                Some("Pick a mint to pay an invoice without an amount")
+            }
+            Some(SendDestination::Invoice(_)) | None => None,
+            Some(_) => Some("Auto only works for lightning invoices, pick a mint"),
+        }
+    } else {
+        harbor.active_federation().and_then(|item| {
+            harbor
+                .send_disabled_reason(&item.id)
+                .or_else(|| send_unsupported(item, destination.as_ref()))
+        })
+    };
     let send_button = h_button(
-        "Send",
+        if harbor.send_auto_mint {
+            "Compare Fees"
+        } else {
+            "Send"
+        },
         SvgIcon::UpRight,
-        harbor.send_status == SendStatus::Sending,
+        harbor.send_status == SendStatus::Sending || harbor.current_fee_estimate_id.is_some(),
     )
-    .on_press_maybe(parse_send_destination(&harbor.send_dest_input_str).map(Message::Send));
+    .on_press_maybe(
+        destination
+            .filter(|_| send_blocked.is_none())
+            .map(Message::Send),
+    );
+    let send_button = explain_disabled(send_button, send_blocked);
 
     let checkbox = h_checkbox(
         "Send Max",
@@ -60,6 +269,8 @@ pub fn send(harbor: &HarborWallet) -> Element<Message> {
                 confirm_action: Box::new(Message::SendStateReset),
                 cancel_action: Box::new(Message::SetConfirmModal(None)),
                 confirm_button_text: "Start Over".to_string(),
+                type_to_confirm: None,
+                enter_confirms: false,
             })));
         column![row![start_over_button, send_button].spacing(8)]
     } else {
@@ -74,19 +285,31 @@ pub fn send(harbor: &HarborWallet) -> Element<Message> {
         button_and_status = button_and_status.push(status).spacing(16);
     }
 
+    let fee_comparison = harbor
+        .send_fee_estimates
+        .as_deref()
+        .filter(|_| harbor.send_auto_mint)
+        .map(|estimates| render_fee_comparison(harbor, estimates));
+
     let content = column![
         header,
+        render_mint_picker(harbor),
         dest_input,
         amount_input,
         checkbox,
         button_and_status
     ]
+    .push_maybe(fee_comparison)
     .spacing(48);
 
-    column![h_screen_header(harbor, true, false), basic_layout(content)].into()
+    column![
+        h_screen_header(harbor, true, true),
+        basic_layout(harbor, content)
+    ]
+    .into()
 }
 
-fn parse_send_destination(input: &str) -> Option<SendDestination> {
+pub fn parse_send_destination(input: &str) -> Option<SendDestination> {
     if let Ok(invoice) = Bolt11Invoice::from_str(input) {
         return Some(SendDestination::Invoice(invoice));
     }
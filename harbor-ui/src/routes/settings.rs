@@ -1,12 +1,21 @@
 use harbor_client::bitcoin::Network;
+use harbor_client::nostr::NostrKeySource;
+use harbor_client::operations::OperationKind;
+use harbor_client::startup_timings::format_timings;
 use iced::widget::{column, pick_list, row, text};
-use iced::{Element, Length, Padding};
+use iced::{Alignment, Element, Length, Padding};
 
 use crate::components::{
-    SvgIcon, basic_layout, debug_stuff, h_button, h_checkbox, h_header, menu_style,
-    pick_list_style, regular_text, very_subtle,
+    ConfirmModalState, InputArgs, SvgIcon, basic_layout, debug_stuff, font_mono, h_button,
+    h_checkbox, h_header, h_input, h_small_button, menu_style, mini_copy, pick_list_style, red,
+    regular_text, very_subtle,
+};
+use crate::config::OperationTimeoutSettings;
+use crate::routes::Route;
+use crate::{
+    AmountUnit, ArchiveHintAfter, FiatDisplay, HarborWallet, Message, OperationTimeout,
+    RequestTimeout, StaleAfter,
 };
-use crate::{HarborWallet, Message};
 
 pub fn settings(harbor: &HarborWallet) -> Element<Message> {
     let header = h_header("Settings", "The fun stuff.");
@@ -25,6 +34,8 @@ pub fn settings(harbor: &HarborWallet) -> Element<Message> {
                     confirm_action: Box::new(Message::SetOnchainReceiveEnabled(enabled)),
                 cancel_action: Box::new(Message::SetConfirmModal(None)),
                     confirm_button_text: "YOLO".to_string(),
+                    type_to_confirm: None,
+                    enter_confirms: false,
                 }))
             } else {
                 Message::SetOnchainReceiveEnabled(false)
@@ -54,6 +65,8 @@ pub fn settings(harbor: &HarborWallet) -> Element<Message> {
                 confirm_action: Box::new(Message::ChangeNetwork(net)),
                 cancel_action: Box::new(Message::SetConfirmModal(None)),
                 confirm_button_text: "Confirm".to_string(),
+                type_to_confirm: None,
+                enter_confirms: true,
             }))
         },
     )
@@ -67,6 +80,125 @@ pub fn settings(harbor: &HarborWallet) -> Element<Message> {
 
     let network_column = column![network_label, network_list, network_description].spacing(8);
 
+    let stale_after_label = regular_text("Federation Stall Warning".to_string(), 24);
+    let stale_after_description = text(
+        "Warn on a mint's details when its federation hasn't finished a session in this long.",
+    )
+    .style(very_subtle)
+    .size(14);
+    let stale_after_list = pick_list(
+        StaleAfter::ALL,
+        Some(harbor.config.stale_after),
+        Message::StaleAfterChanged,
+    )
+    .style(pick_list_style)
+    .padding(Padding::from(16))
+    .width(Length::Fill)
+    .handle(pick_list::Handle::Arrow {
+        size: Some(iced::Pixels(24.)),
+    })
+    .menu_style(menu_style);
+
+    let stale_after_column =
+        column![stale_after_label, stale_after_list, stale_after_description].spacing(8);
+
+    let archive_hint_label = regular_text("Suggest Archiving Empty Mints".to_string(), 24);
+    let archive_hint_description =
+        text("Suggest archiving a mint once it's been empty and unused for this long.")
+            .style(very_subtle)
+            .size(14);
+    let archive_hint_list = pick_list(
+        ArchiveHintAfter::ALL,
+        Some(harbor.config.archive_hint_after),
+        Message::ArchiveHintAfterChanged,
+    )
+    .style(pick_list_style)
+    .padding(Padding::from(16))
+    .width(Length::Fill)
+    .handle(pick_list::Handle::Arrow {
+        size: Some(iced::Pixels(24.)),
+    })
+    .menu_style(menu_style);
+
+    let archive_hint_column = column![
+        archive_hint_label,
+        archive_hint_list,
+        archive_hint_description
+    ]
+    .spacing(8);
+
+    let fiat_label = regular_text("Fiat Value".to_string(), 24);
+    let fiat_description = text(
+        "Show roughly what balances are worth. Prices come from mempool.space, over Tor when it's on. Off never asks for one.",
+    )
+    .style(very_subtle)
+    .size(14);
+    let fiat_list = pick_list(
+        FiatDisplay::options(),
+        Some(harbor.config.fiat_display),
+        Message::FiatDisplayChanged,
+    )
+    .style(pick_list_style)
+    .padding(Padding::from(16))
+    .width(Length::Fill)
+    .handle(pick_list::Handle::Arrow {
+        size: Some(iced::Pixels(24.)),
+    })
+    .menu_style(menu_style);
+
+    let fiat_column = column![fiat_label, fiat_list, fiat_description].spacing(8);
+
+    let unit_label = regular_text("Amount Unit".to_string(), 24);
+    let unit_list = pick_list(
+        AmountUnit::ALL,
+        Some(harbor.config.amount_unit),
+        Message::AmountUnitChanged,
+    )
+    .style(pick_list_style)
+    .padding(Padding::from(16))
+    .width(Length::Fill)
+    .handle(pick_list::Handle::Arrow {
+        size: Some(iced::Pixels(24.)),
+    })
+    .menu_style(menu_style);
+
+    let unit_column = column![unit_label, unit_list].spacing(8);
+
+    let timeouts_label = regular_text("Operation Time Limits".to_string(), 24);
+    let timeouts_description = text(
+        "How long to wait on a mint before giving up. Raise these if a big or far away mint keeps timing out. Connect and Guardian are for each single connection and guardian request, and never run longer than Preview.",
+    )
+    .style(very_subtle)
+    .size(14);
+    let timeouts = harbor.config.operation_timeouts;
+    let tor_friendly_button = h_small_button("Tor-friendly", SvgIcon::Shield, false)
+        .on_press_maybe(
+            (timeouts != OperationTimeoutSettings::TOR_FRIENDLY)
+                .then_some(Message::SetTimeouts(OperationTimeoutSettings::TOR_FRIENDLY)),
+        );
+    let defaults_button = h_small_button("Defaults", SvgIcon::Restart, false).on_press_maybe(
+        (timeouts != OperationTimeoutSettings::default())
+            .then(|| Message::SetTimeouts(OperationTimeoutSettings::default())),
+    );
+    let timeouts_column = column![
+        timeouts_label,
+        operation_timeout_list(harbor, "Preview", OperationKind::Peek),
+        operation_timeout_list(harbor, "Join", OperationKind::Join),
+        operation_timeout_list(harbor, "Resync", OperationKind::Resync),
+        request_timeout_list("Connect", timeouts.connect, Message::ConnectTimeoutChanged),
+        request_timeout_list(
+            "Guardian",
+            timeouts.guardian_request,
+            Message::GuardianRequestTimeoutChanged
+        ),
+        row![tor_friendly_button, defaults_button].spacing(8),
+        timeouts_description,
+    ]
+    .spacing(8);
+
+    let activity_log_button = h_button("Activity Log", SvgIcon::Clock, false)
+        .on_press(Message::Navigate(Route::ActivityLog));
+
     let open_data_dir_button = h_button("Open Data Directory", SvgIcon::FolderLock, false)
         .on_press(Message::OpenDataDirectory);
 
@@ -85,10 +217,74 @@ pub fn settings(harbor: &HarborWallet) -> Element<Message> {
                 confirm_action: Box::new(Message::SetTorEnabled(enabled)),
                 cancel_action: Box::new(Message::SetConfirmModal(None)),
                 confirm_button_text: "Confirm".to_string(),
+                type_to_confirm: None,
+                enter_confirms: true,
             }))
         },
     );
 
+    let health_notifications_checkbox = h_checkbox(
+        "Mint Health Notifications",
+        Some(
+            "Notify when a federation's guardians stop answering, and when they come back. Uses the guardian checks Harbor already runs.",
+        ),
+        harbor.config.health_notifications,
+        false,
+        Message::SetHealthNotifications,
+    );
+
+    let reduce_background_data_checkbox = h_checkbox(
+        "Reduce Background Data",
+        Some(
+            "Stop the guardian health checks, metadata refreshes, icon downloads and price updates Harbor runs on its own. Anything you do yourself still goes through.",
+        ),
+        harbor.config.reduce_background_data,
+        false,
+        Message::SetReduceBackgroundData,
+    );
+
+    let clipboard_invites_checkbox = h_checkbox(
+        "Detect Invite Codes in Clipboard",
+        Some(
+            "When Harbor comes into focus, check the clipboard for an invite code to a mint you haven't joined and offer to add it. Nothing from the clipboard is saved.",
+        ),
+        harbor.config.detect_clipboard_invites,
+        false,
+        Message::SetDetectClipboardInvites,
+    );
+
+    let startup_timings_label = regular_text("Startup Timings".to_string(), 24);
+    let startup_timings_description =
+        text("How long each step of opening the wallet took, to help track down a slow start.")
+            .style(very_subtle)
+            .size(14);
+    let startup_timings_column = match &harbor.startup_timings {
+        Some(spans) => {
+            let report = format_timings(spans);
+            column![
+                startup_timings_label,
+                startup_timings_description,
+                text(report.clone()).size(14).font(font_mono()),
+                row![
+                    h_button("Refresh", SvgIcon::Restart, false)
+                        .on_press(Message::LoadStartupTimings),
+                    h_button("Copy Timings", SvgIcon::Copy, false)
+                        .on_press(Message::CopyToClipboard(report)),
+                ]
+                .spacing(8),
+            ]
+        }
+        None => column![
+            startup_timings_label,
+            startup_timings_description,
+            h_button("Show Startup Timings", SvgIcon::Eye, false)
+                .on_press(Message::LoadStartupTimings),
+        ],
+    }
+    .spacing(8);
+
+    let nostr_column = nostr_identity(harbor);
+
     let show_seed_words_button =
         h_button("Show Seed Words", SvgIcon::Eye, false).on_press(Message::ShowSeedWords(true));
 
@@ -102,13 +298,185 @@ pub fn settings(harbor: &HarborWallet) -> Element<Message> {
         header,
         onchain_receive_checkbox,
         tor_enabled_checkbox,
+        health_notifications_checkbox,
+        reduce_background_data_checkbox,
+        clipboard_invites_checkbox,
         network_column,
+        stale_after_column,
+        archive_hint_column,
+        fiat_column,
+        unit_column,
+        timeouts_column,
+        nostr_column,
         show_seed_words_button,
         open_data_dir_button,
+        activity_log_button,
+        startup_timings_column,
     ]
     .push_maybe(debug_stuff);
 
-    basic_layout(column.spacing(48))
+    basic_layout(harbor, column.spacing(48))
+}
+
+// Set up, show or remove the key nostr features sign with
+fn nostr_identity(harbor: &HarborWallet) -> Element<Message> {
+    let label = regular_text("Nostr Identity".to_string(), 24);
+
+    let import_form = harbor.nostr_import_open.then(|| {
+        let nsec_input = h_input(InputArgs {
+            label: "Secret key",
+            placeholder: "nsec1...",
+            value: &harbor.nostr_import_input,
+            on_input: Message::NostrImportInputChanged,
+            on_submit: Some(Message::ImportNostrKey),
+            secure: true,
+            ..InputArgs::default()
+        });
+        column![nsec_input]
+            .push_maybe(
+                harbor
+                    .nostr_import_error
+                    .as_ref()
+                    .map(|error| text(error.clone()).size(14).color(red())),
+            )
+            .push(
+                row![
+                    h_button("Cancel", SvgIcon::SmallClose, false)
+                        .on_press(Message::SetNostrImportOpen(false)),
+                    h_button("Generate Key", SvgIcon::Plus, false)
+                        .on_press(Message::GenerateNostrKey),
+                    h_button("Import", SvgIcon::SmallCheck, false).on_press_maybe(
+                        (!harbor.nostr_import_input.trim().is_empty())
+                            .then_some(Message::ImportNostrKey)
+                    ),
+                ]
+                .spacing(8),
+            )
+            .spacing(8)
+    });
+
+    if let Some(identity) = &harbor.nostr_identity {
+        let source_list = pick_list(
+            NostrKeySource::ALL,
+            Some(identity.source),
+            Message::NostrKeySourceChanged,
+        )
+        .style(pick_list_style)
+        .padding(Padding::from(16))
+        .width(Length::Fill)
+        .handle(pick_list::Handle::Arrow {
+            size: Some(iced::Pixels(24.)),
+        })
+        .menu_style(menu_style);
+        let source_description = match identity.source {
+            NostrKeySource::Seed => {
+                "Restoring your wallet from its seed words brings this key back."
+            }
+            NostrKeySource::Separate => {
+                "This key isn't part of your seed words, keep a copy of its nsec to restore it."
+            }
+        };
+
+        let remove_button = h_button("Remove Key", SvgIcon::Trash, false).on_press(
+            Message::SetConfirmModal(Some(ConfirmModalState {
+                title: "Remove your nostr key?".to_string(),
+                description: "Anything that signs as you on nostr stops working until you set up a key again. Import the nsec later to keep the same identity.".to_string(),
+                confirm_action: Box::new(Message::SaveNostrIdentity(None)),
+                cancel_action: Box::new(Message::SetConfirmModal(None)),
+                confirm_button_text: "Remove Key".to_string(),
+                type_to_confirm: None,
+                enter_confirms: true,
+            })),
+        );
+        return column![
+            label,
+            row![
+                text(identity.npub.clone())
+                    .size(14)
+                    .font(font_mono())
+                    .width(Length::Fill),
+                mini_copy(identity.npub.clone())
+            ]
+            .align_y(Alignment::Center)
+            .spacing(8),
+            text("Your public key. The secret key is kept encrypted with your wallet.")
+                .style(very_subtle)
+                .size(14),
+            source_list,
+            text(source_description).style(very_subtle).size(14),
+        ]
+        .push_maybe(import_form)
+        .push(remove_button)
+        .spacing(8)
+        .into();
+    }
+
+    let description = text("No nostr key yet. Features that post or message on nostr need one.")
+        .style(very_subtle)
+        .size(14);
+
+    if let Some(import_form) = import_form {
+        return column![label, description, import_form].spacing(8).into();
+    }
+
+    column![
+        label,
+        description,
+        row![
+            h_button("Derive from Seed", SvgIcon::Shield, false)
+                .on_press(Message::NostrKeySourceChanged(NostrKeySource::Seed)),
+            h_button("Generate Key", SvgIcon::Plus, false).on_press(Message::GenerateNostrKey),
+            h_button("Import nsec", SvgIcon::Download, false)
+                .on_press(Message::SetNostrImportOpen(true)),
+        ]
+        .spacing(8),
+    ]
+    .spacing(8)
+    .into()
+}
+
+fn operation_timeout_list(
+    harbor: &HarborWallet,
+    label: &'static str,
+    kind: OperationKind,
+) -> Element<'static, Message> {
+    let list = pick_list(
+        OperationTimeout::ALL,
+        Some(harbor.config.operation_timeouts.get(kind)),
+        move |timeout| Message::OperationTimeoutChanged(kind, timeout),
+    )
+    .style(pick_list_style)
+    .padding(Padding::from(16))
+    .width(Length::Fill)
+    .handle(pick_list::Handle::Arrow {
+        size: Some(iced::Pixels(24.)),
+    })
+    .menu_style(menu_style);
+
+    row![text(label).size(16).width(Length::Fixed(96.)), list]
+        .spacing(8)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+fn request_timeout_list(
+    label: &'static str,
+    selected: RequestTimeout,
+    on_select: fn(RequestTimeout) -> Message,
+) -> Element<'static, Message> {
+    let list = pick_list(RequestTimeout::ALL, Some(selected), on_select)
+        .style(pick_list_style)
+        .padding(Padding::from(16))
+        .width(Length::Fill)
+        .handle(pick_list::Handle::Arrow {
+            size: Some(iced::Pixels(24.)),
+        })
+        .menu_style(menu_style);
+
+    row![text(label).size(16).width(Length::Fixed(96.)), list]
+        .spacing(8)
+        .align_y(iced::Alignment::Center)
+        .into()
 }
 
 // Function to format seed words in a two-column layout
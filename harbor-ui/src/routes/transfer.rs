@@ -1,69 +1,150 @@
 use iced::widget::{PickList, column, container, pick_list, row, scrollable, text};
 use iced::{Alignment, Element, Length, Padding};
 
+use harbor_client::MintIdentifier;
+use harbor_client::db_models::MintItem;
+use harbor_client::transfer::{TransferResolution, TransferState};
+
 use crate::components::{
-    ConfirmModalState, InputArgs, SvgIcon, h_balance_display, h_button, h_header, h_input,
-    menu_style, operation_status_for_id, pick_list_style,
+    ConfirmModalState, InputArgs, SvgIcon, format_amount, h_balance_display, h_button, h_header,
+    h_input, menu_style, operation_status_for_id, pick_list_style, red, very_subtle,
 };
 use crate::{HarborWallet, Message, SendStatus};
 
+/// A mint in the transfer pick lists. It's picked by id, so two mints with the same name are
+/// still two different choices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TransferMint {
+    id: MintIdentifier,
+    name: String,
+}
+
+impl TransferMint {
+    fn new(item: &MintItem) -> Self {
+        Self {
+            id: item.id.clone(),
+            name: item.display_name().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for TransferMint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Renders where a transfer's money is, its fee once known, and what can be done if it
+/// didn't finish.
+fn render_transfer_state<'a>(
+    harbor: &'a HarborWallet,
+    state: &'a TransferState,
+) -> Element<'a, Message> {
+    let location = state.funds_location(
+        &harbor.mint_display_name(&state.from),
+        &harbor.mint_display_name(&state.to),
+    );
+
+    let mut content = column![
+        text("Where your money is").size(24),
+        text(location).size(16),
+    ]
+    .spacing(16);
+
+    if let Some(fee) = state.fee {
+        content = content.push(
+            text(format!(
+                "Fee paid: {}",
+                format_amount(fee.msats.div_ceil(1_000))
+            ))
+            .size(14)
+            .style(very_subtle),
+        );
+    }
+    if let Some(error) = &state.error {
+        content = content.push(text(error.clone()).size(14).color(red()));
+    }
+
+    let busy = harbor.transfer_status == SendStatus::Sending;
+    let resolution = match state.resolution() {
+        Some(TransferResolution::Retry) => {
+            Some(h_button("Try Again", SvgIcon::Restart, busy).on_press(Message::Transfer))
+        }
+        // Federations finish their claims on their own, only a cashu mint needs asking again
+        Some(TransferResolution::RetryClaim) if matches!(state.to, MintIdentifier::Cashu(_)) => {
+            Some(
+                h_button("Claim Again", SvgIcon::Restart, busy)
+                    .on_press(Message::RetryTransferClaim),
+            )
+        }
+        Some(TransferResolution::RetryClaim) | None => None,
+    };
+
+    content.push_maybe(resolution).into()
+}
+
 pub fn transfer(harbor: &HarborWallet) -> Element<Message> {
     // We have to have at least 2 federations to be on this screen!
     assert!(harbor.mint_list.iter().filter(|f| f.active).count() >= 2);
-    let federation_names: Vec<&str> = harbor
-        .mint_list
-        .iter()
-        .filter(|f| f.active)
-        .map(|f| f.name.as_str())
+    let choices: Vec<TransferMint> = harbor
+        .transfer_mints()
+        .into_iter()
+        .map(TransferMint::new)
         .collect();
-
-    let source_list: PickList<'_, &str, Vec<&str>, &str, Message> = pick_list(
-        federation_names.clone(),
-        harbor.transfer_from_federation_selection.as_deref(),
-        |s| Message::SetTransferFrom(s.to_string()),
-    )
-    .placeholder("Pick a source mint")
-    .style(pick_list_style)
-    .padding(Padding::from(16))
-    .handle(pick_list::Handle::Arrow {
-        size: Some(iced::Pixels(24.)),
-    })
-    .menu_style(menu_style);
+    let selected = |selection: &Option<MintIdentifier>| {
+        choices
+            .iter()
+            .find(|choice| selection.as_ref() == Some(&choice.id))
+            .cloned()
+    };
+    let source_choice = selected(&harbor.transfer_from_federation_selection);
+    let dest_choice = selected(&harbor.transfer_to_federation_selection);
+
+    let source_list: PickList<'_, TransferMint, Vec<TransferMint>, TransferMint, Message> =
+        pick_list(choices.clone(), source_choice.clone(), |choice| {
+            Message::SetTransferFrom(choice.id)
+        })
+        .placeholder("Pick a source mint")
+        .style(pick_list_style)
+        .padding(Padding::from(16))
+        .handle(pick_list::Handle::Arrow {
+            size: Some(iced::Pixels(24.)),
+        })
+        .menu_style(menu_style);
 
     let source = column![text("Source").size(24), source_list].spacing(16);
 
     let mut source_row = row![source].spacing(16).align_y(Alignment::End);
 
-    // Show balance for source federation if selected
-    if let Some(source_fed) = harbor.transfer_from_federation_selection.as_ref() {
-        if let Some(federation) = harbor.mint_list.iter().find(|f| f.name == *source_fed) {
-            source_row = source_row.push(h_balance_display(federation.balance));
-        }
-    }
+    let balance = |choice: &TransferMint| {
+        harbor
+            .mint_list
+            .iter()
+            .find(|f| f.id == choice.id)
+            .map(|f| h_balance_display(harbor, f.balance))
+    };
 
-    let destination_list: PickList<'_, &str, Vec<&str>, &str, Message> = pick_list(
-        federation_names,
-        harbor.transfer_to_federation_selection.as_deref(),
-        |s| Message::SetTransferTo(s.to_string()),
-    )
-    .placeholder("Pick a destination mint")
-    .style(pick_list_style)
-    .padding(Padding::from(16))
-    .handle(pick_list::Handle::Arrow {
-        size: Some(iced::Pixels(24.)),
-    })
-    .menu_style(menu_style);
+    // Show balance for source federation if selected
+    source_row = source_row.push_maybe(source_choice.as_ref().and_then(balance));
+
+    let destination_list: PickList<'_, TransferMint, Vec<TransferMint>, TransferMint, Message> =
+        pick_list(choices, dest_choice.clone(), |choice| {
+            Message::SetTransferTo(choice.id)
+        })
+        .placeholder("Pick a destination mint")
+        .style(pick_list_style)
+        .padding(Padding::from(16))
+        .handle(pick_list::Handle::Arrow {
+            size: Some(iced::Pixels(24.)),
+        })
+        .menu_style(menu_style);
 
     let destination = column![text("Destination").size(24), destination_list].spacing(16);
 
     let mut destination_row = row![destination].spacing(16).align_y(Alignment::End);
 
     // Show balance for destination federation if selected
-    if let Some(dest_fed) = harbor.transfer_to_federation_selection.as_ref() {
-        if let Some(federation) = harbor.mint_list.iter().find(|f| f.name == *dest_fed) {
-            destination_row = destination_row.push(h_balance_display(federation.balance));
-        }
-    }
+    destination_row = destination_row.push_maybe(dest_choice.as_ref().and_then(balance));
 
     let amount_input = h_input(InputArgs {
         label: "Amount",
@@ -91,6 +172,8 @@ pub fn transfer(harbor: &HarborWallet) -> Element<Message> {
                 confirm_action: Box::new(Message::TransferStateReset),
                 cancel_action: Box::new(Message::SetConfirmModal(None)),
                 confirm_button_text: "Start Over".to_string(),
+                type_to_confirm: None,
+                enter_confirms: true,
             })));
         column![row![start_over_button, transfer_button].spacing(8)]
     } else {
@@ -105,7 +188,14 @@ pub fn transfer(harbor: &HarborWallet) -> Element<Message> {
         button_and_status = button_and_status.push(status).spacing(16);
     }
 
-    let list = column![source_row, destination_row, amount_input, button_and_status].spacing(48);
+    let transfer_state = harbor
+        .transfer_state
+        .as_ref()
+        .map(|state| render_transfer_state(harbor, state));
+
+    let list = column![source_row, destination_row, amount_input, button_and_status]
+        .push_maybe(transfer_state)
+        .spacing(48);
 
     container(scrollable(
         column![h_header("Transfer", "Rebalance your funds."), list]